@@ -14,6 +14,15 @@
 #include "Components/PrimitiveComponent.h"
 #include "Sound/SoundBase.h"
 #include "VisualLogger/VisualLogger.h"
+#include "Engine/AssetManager.h"
+#include "DrawDebugHelpers.h"
+#include "RustEvent.h"
+#include "GameFramework/DamageType.h"
+#include "GameFramework/Pawn.h"
+#include "GameplayTagAssetInterface.h"
+#include "Engine/Level.h"
+#include "Engine/LevelStreaming.h"
+#include "Misc/ScopeLock.h"
 
 DEFINE_LOG_CATEGORY(RustVisualLog);
 
@@ -41,11 +50,37 @@ void GetSpatialData(const AActorOpaque* actor,
 	*scale = ToVector3(Transform.GetScale3D());
 }
 
-void Log(const char* s, int32 len)
+// Every `log` category that we have seen from Rust. Creating a `FLogCategoryBase` registers the
+// category with unreal, which means it can be filtered with the `Log <Category> <Verbosity>` command.
+// Rust logs from worker threads as well, so the map is guarded by a lock. The categories themselves
+// are never removed, which keeps the returned references valid.
+static TMap<FName, TUniquePtr<FLogCategoryBase>> RustLogCategories;
+static FCriticalSection RustLogCategoriesLock;
+
+static FLogCategoryBase& GetRustLogCategory(const FString& Name)
+{
+	FName CategoryName(*Name);
+	FScopeLock Lock(&RustLogCategoriesLock);
+	if (TUniquePtr<FLogCategoryBase>* Category = RustLogCategories.Find(CategoryName))
+	{
+		return **Category;
+	}
+	return *RustLogCategories.Add(
+		CategoryName,
+		MakeUnique<FLogCategoryBase>(*Name, ELogVerbosity::Log, ELogVerbosity::All));
+}
+
+void Log(const LogRecord* record)
 {
+	const ELogVerbosity::Type Verbosity = ToELogVerbosity(record->verbosity);
+	FLogCategoryBase& Category = GetRustLogCategory(ToFString(record->category));
+	if (Category.IsSuppressed(Verbosity))
+		return;
+
 	// TODO: Can we get rid of that allocation?
-	FString LogString = FString(len, UTF8_TO_TCHAR(s));
-	UE_LOG(LogTemp, Warning, TEXT("%s"), *LogString);
+	FString LogString = ToFString(record->message);
+	FString File = ToFString(record->file);
+	FMsg::Logf(TCHAR_TO_ANSI(*File), record->line, Category.GetCategoryName(), Verbosity, TEXT("%s"), *LogString);
 }
 
 void IterateActors(AActorOpaque** array, uint64_t* len)
@@ -228,6 +263,7 @@ uint32_t LineTrace(Vector3 start, Vector3 end, LineTraceParams Params, HitResult
 {
 	FHitResult Out;
 	auto CollisionParams = FCollisionQueryParams();
+	CollisionParams.bReturnPhysicalMaterial = true;
 	for (uintptr_t i = 0; i < Params.ignored_actors_len; ++i)
 	{
 		CollisionParams.AddIgnoredActor((AActor*)Params.ignored_actors[i]);
@@ -236,13 +272,7 @@ uint32_t LineTrace(Vector3 start, Vector3 end, LineTraceParams Params, HitResult
 		Out, ToFVector(start), ToFVector(end), ECollisionChannel::ECC_MAX, CollisionParams, FCollisionResponseParams{});
 	if (IsHit)
 	{
-		result->actor = (AActorOpaque*)Out.GetActor();
-		result->primtive = (UPrimtiveOpaque*)Out.GetComponent();
-		result->distance = Out.Distance;
-		result->location = ToVector3(Out.Location);
-		result->normal = ToVector3(Out.Normal);
-		result->impact_location = ToVector3(Out.ImpactPoint);
-		result->pentration_depth = Out.PenetrationDepth;
+		*result = ToHitResult(Out);
 	}
 
 	return IsHit;
@@ -253,7 +283,7 @@ uint32_t OverlapMulti(CollisionShape shape,
                       Quaternion rotation,
                       LineTraceParams params,
                       uintptr_t max_results,
-                      OverlapResult** results)
+                      OverlapResult* results)
 {
 	TArray<FOverlapResult> Out;
 	auto CollisionParams = FCollisionQueryParams();
@@ -268,40 +298,153 @@ uint32_t OverlapMulti(CollisionShape shape,
 	                                                                         ToFCollisionShape(shape),
 	                                                                         CollisionParams,
 	                                                                         FCollisionResponseParams{});
-	if (IsHit)
+	if (!IsHit)
+		return 0;
+
+	uintptr_t Length = FGenericPlatformMath::Min(max_results, (uintptr_t)Out.Num());
+	for (uintptr_t i = 0; i < Length; ++i)
 	{
-		uintptr_t Length = FGenericPlatformMath::Min(max_results, (uintptr_t)Out.Num());
-		for (uintptr_t i = 0; i < Length; ++i)
-		{
-			OverlapResult* result = results[i];
-			FOverlapResult* Hit = &Out[i];
-			result->actor = (AActorOpaque*)Hit->GetActor();
-			result->primtive = (UPrimtiveOpaque*)Hit->GetComponent();
-		}
+		results[i].actor = (AActorOpaque*)Out[i].GetActor();
+		results[i].primtive = (UPrimtiveOpaque*)Out[i].GetComponent();
 	}
+	return Length;
+}
 
-	return IsHit;
+// The UE_VLOG_* macros only accept a verbosity that is known at compile time, which is why we call
+// into FVisualLogger directly.
+uint32_t VisualLogIsRecording()
+{
+#if ENABLE_VISUAL_LOG
+	return FVisualLogger::IsRecording();
+#else
+	return 0;
+#endif
 }
 
-void VisualLogSegment(const AActorOpaque* actor, Vector3 start, Vector3 end, Color color)
+void VisualLogText(const VisualLogEntry* entry)
 {
-	UE_VLOG_SEGMENT(ToAActor(actor), RustVisualLog, Log, ToFVector(start), ToFVector(end), ToFColor(color), TEXT(""));
+#if ENABLE_VISUAL_LOG
+	FVisualLogger::CategorizedLogf(ToAActor(entry->owner), FName(ToFString(entry->category)),
+	                               ToELogVerbosity(entry->verbosity), TEXT("%s"), *ToFString(entry->label));
+#endif
+}
+
+void VisualLogSegment(const VisualLogEntry* entry, Vector3 start, Vector3 end)
+{
+#if ENABLE_VISUAL_LOG
+	FVisualLogger::GeometryShapeLogf(ToAActor(entry->owner), FName(ToFString(entry->category)),
+	                                 ToELogVerbosity(entry->verbosity), ToFVector(start), ToFVector(end),
+	                                 ToFColor(entry->color), 0, TEXT("%s"), *ToFString(entry->label));
+#endif
+}
+
+void VisualLogArrow(const VisualLogEntry* entry, Vector3 start, Vector3 end)
+{
+#if ENABLE_VISUAL_LOG
+	FVisualLogger::ArrowLogf(ToAActor(entry->owner), FName(ToFString(entry->category)),
+	                         ToELogVerbosity(entry->verbosity), ToFVector(start), ToFVector(end),
+	                         ToFColor(entry->color), TEXT("%s"), *ToFString(entry->label));
+#endif
+}
+
+void VisualLogLocation(const VisualLogEntry* entry, Vector3 position, float radius)
+{
+#if ENABLE_VISUAL_LOG
+	FVisualLogger::GeometryShapeLogf(ToAActor(entry->owner), FName(ToFString(entry->category)),
+	                                 ToELogVerbosity(entry->verbosity), ToFVector(position), radius,
+	                                 ToFColor(entry->color), TEXT("%s"), *ToFString(entry->label));
+#endif
 }
 
 void VisualLogCapsule(
-	Utf8Str category,
-	const AActorOpaque* owner,
+	const VisualLogEntry* entry,
 	Vector3 position,
 	Quaternion rotation,
 	float half_height,
-	float radius,
-	Color color)
+	float radius)
 {
-	FString CategoryStr = ToFString(category);
-	auto LogCat = FLogCategory<ELogVerbosity::Log, ELogVerbosity::All>(*CategoryStr);
+#if ENABLE_VISUAL_LOG
 	FVector Base = ToFVector(position) - half_height * (ToFQuat(rotation) * FVector::UpVector);
-	UE_VLOG_CAPSULE(ToAActor(owner), LogCat, Log, Base, half_height, radius, ToFQuat(rotation),
-	                ToFColor(color), TEXT(""));
+	FVisualLogger::GeometryShapeLogf(ToAActor(entry->owner), FName(ToFString(entry->category)),
+	                                 ToELogVerbosity(entry->verbosity), Base, half_height, radius,
+	                                 ToFQuat(rotation), ToFColor(entry->color), TEXT("%s"),
+	                                 *ToFString(entry->label));
+#endif
+}
+
+void VisualLogBox(const VisualLogEntry* entry, Vector3 min, Vector3 max)
+{
+#if ENABLE_VISUAL_LOG
+	FVisualLogger::GeometryShapeLogf(ToAActor(entry->owner), FName(ToFString(entry->category)),
+	                                 ToELogVerbosity(entry->verbosity), FBox(ToFVector(min), ToFVector(max)),
+	                                 FMatrix::Identity, ToFColor(entry->color), TEXT("%s"),
+	                                 *ToFString(entry->label));
+#endif
+}
+
+void VisualLogOrientedBox(const VisualLogEntry* entry, Vector3 position, Quaternion rotation, Vector3 half_extent)
+{
+#if ENABLE_VISUAL_LOG
+	FVector Extent = ToFVector(half_extent);
+	FMatrix Transform = FTransform(ToFQuat(rotation), ToFVector(position)).ToMatrixNoScale();
+	FVisualLogger::GeometryShapeLogf(ToAActor(entry->owner), FName(ToFString(entry->category)),
+	                                 ToELogVerbosity(entry->verbosity), FBox(-Extent, Extent), Transform,
+	                                 ToFColor(entry->color), TEXT("%s"), *ToFString(entry->label));
+#endif
+}
+
+void VisualLogCone(const VisualLogEntry* entry, Vector3 origin, Vector3 direction, float length, float angle)
+{
+#if ENABLE_VISUAL_LOG
+	FVisualLogger::GeometryShapeLogf(ToAActor(entry->owner), FName(ToFString(entry->category)),
+	                                 ToELogVerbosity(entry->verbosity), ToFVector(origin),
+	                                 ToFVector(direction).GetSafeNormal(), length, FMath::RadiansToDegrees(angle),
+	                                 ToFColor(entry->color), TEXT("%s"), *ToFString(entry->label));
+#endif
+}
+
+void VisualLogPolyline(const VisualLogEntry* entry, const Vector3* points, uintptr_t len)
+{
+#if ENABLE_VISUAL_LOG
+	const FName Category(ToFString(entry->category));
+	const FString Label = ToFString(entry->label);
+	for (uintptr_t i = 1; i < len; ++i)
+	{
+		FVisualLogger::GeometryShapeLogf(ToAActor(entry->owner), Category, ToELogVerbosity(entry->verbosity),
+		                                 ToFVector(points[i - 1]), ToFVector(points[i]), ToFColor(entry->color), 0,
+		                                 TEXT("%s"), i == 1 ? *Label : TEXT(""));
+	}
+#endif
+}
+
+void VisualLogHistogram(const VisualLogEntry* entry, Utf8Str graph_name, Utf8Str data_name, float x, float y)
+{
+#if ENABLE_VISUAL_LOG
+	FVisualLogger::HistogramDataLogf(ToAActor(entry->owner), FName(ToFString(entry->category)),
+	                                 ToELogVerbosity(entry->verbosity), FName(ToFString(graph_name)),
+	                                 FName(ToFString(data_name)), FVector2D(x, y), ToFColor(entry->color),
+	                                 TEXT("%s"), *ToFString(entry->label));
+#endif
+}
+
+void VisualLogStatus(const VisualLogEntry* entry, const VisualLogStatusEntry* status, uintptr_t len)
+{
+#if ENABLE_VISUAL_LOG
+	AActor* Owner = ToAActor(entry->owner);
+	if (Owner == nullptr || !FVisualLogger::IsRecording())
+		return;
+
+	FVisualLogEntry* LogEntry = FVisualLogger::Get().GetEntryToWrite(Owner, Owner->GetWorld()->TimeSeconds);
+	if (LogEntry == nullptr)
+		return;
+
+	FVisualLogStatusCategory StatusCategory(ToFString(entry->category));
+	for (uintptr_t i = 0; i < len; ++i)
+	{
+		StatusCategory.Add(ToFString(status[i].key), ToFString(status[i].value));
+	}
+	LogEntry->Status.Add(StatusCategory);
+#endif
 }
 
 void GetRootComponent(const AActorOpaque* actor, ActorComponentPtr* data)
@@ -329,6 +472,7 @@ uint32_t Sweep(Vector3 start,
 {
 	FHitResult Out;
 	auto CollisionParams = FCollisionQueryParams();
+	CollisionParams.bReturnPhysicalMaterial = true;
 	for (uintptr_t i = 0; i < params.ignored_actors_len; ++i)
 	{
 		CollisionParams.AddIgnoredActor((AActor*)params.ignored_actors[i]);
@@ -346,14 +490,7 @@ uint32_t Sweep(Vector3 start,
 		CollisionParams, FCollisionResponseParams{});
 	if (IsHit)
 	{
-		result->actor = (AActorOpaque*)Out.GetActor();
-		result->distance = Out.Distance;
-		result->location = ToVector3(Out.Location);
-		result->normal = ToVector3(Out.Normal);
-		result->impact_location = ToVector3(Out.ImpactPoint);
-		result->impact_normal = ToVector3(Out.ImpactNormal);
-		result->pentration_depth = Out.PenetrationDepth;
-		result->start_penetrating = Out.bStartPenetrating;
+		*result = ToHitResult(Out);
 	}
 
 	return IsHit;
@@ -370,6 +507,7 @@ uint32_t SweepMulti(Vector3 start,
 	TArray<FHitResult> Out;
 
 	auto CollisionParams = FCollisionQueryParams();
+	CollisionParams.bReturnPhysicalMaterial = true;
 	for (uintptr_t i = 0; i < params.ignored_actors_len; ++i)
 	{
 		CollisionParams.AddIgnoredActor((AActor*)params.ignored_actors[i]);
@@ -393,14 +531,7 @@ uint32_t SweepMulti(Vector3 start,
 		{
 			FHitResult& Hit = Out[i];
 
-			results[i].actor = (AActorOpaque*)Hit.GetActor();
-			results[i].distance = Hit.Distance;
-			results[i].location = ToVector3(Hit.Location);
-			results[i].normal = ToVector3(Hit.Normal);
-			results[i].impact_location = ToVector3(Hit.ImpactPoint);
-			results[i].impact_normal = ToVector3(Hit.ImpactNormal);
-			results[i].pentration_depth = Hit.PenetrationDepth;
-			results[i].start_penetrating = Hit.bStartPenetrating;
+			results[i] = ToHitResult(Hit);
 		}
 	}
 	return Length;
@@ -488,13 +619,6 @@ uint32_t GetCollisionShape(const UPrimtiveOpaque* primitive, CollisionShape* out
 	return 0;
 }
 
-void VisualLogLocation(Utf8Str category, const AActorOpaque* owner, Vector3 position, float radius, Color color)
-{
-	FString CategoryStr = ToFString(category);
-	auto LogCat = FLogCategory<ELogVerbosity::Log, ELogVerbosity::All>(*CategoryStr);
-	UE_VLOG_LOCATION(ToAActor(owner), LogCat, Log, ToFVector(position), radius, ToFColor(color), TEXT(""));
-}
-
 uint32_t GetEditorComponentUuids(const AActorOpaque* actor, Uuid* data, uintptr_t* len)
 {
 	AActor* Actor = ToAActor(actor);
@@ -589,18 +713,12 @@ uint32_t GetEditorComponentUObject(const AActorOpaque* actor, Uuid uuid, Utf8Str
 	if (Prop == nullptr)
 		return 0;
 
-	if (Prop->Tag == ERustPropertyTag::Class)
-	{
-		*out = static_cast<UObjectOpague*>(Prop->Class.Get());
-		return 1;
-	}
-	if (Prop->Tag == ERustPropertyTag::Sound)
-	{
-		*out = static_cast<UObjectOpague*>(Prop->Sound.Get());
-		return 1;
-	}
+	UObject* Object = Prop->GetUObject(ty);
+	if (Object == nullptr)
+		return 0;
 
-	return 0;
+	*out = static_cast<UObjectOpague*>(Object);
+	return 1;
 }
 
 void PlaySoundAtLocation(const USoundBaseOpague* sound, Vector3 location, Quaternion rotation,
@@ -618,14 +736,42 @@ void GetActorName(const AActorOpaque* actor, RustAlloc* data)
 	FMemory::Memcpy(data->ptr, Utf8.Get(), data->size);
 }
 
+void GetActorPath(const AActorOpaque* actor, RustAlloc* data)
+{
+	FString Path = UWorld::RemovePIEPrefix(ToAActor(actor)->GetPathName());
+	auto Utf8 = FTCHARToUTF8(*Path);
+	GetRustModule().Plugin.Rust.allocate_fns.allocate(Utf8.Length(), 1, data);
+	FMemory::Memcpy(data->ptr, Utf8.Get(), data->size);
+}
+
+uint32_t GetOwnedGameplayTags(const AActorOpaque* actor, RustAlloc* data)
+{
+	const IGameplayTagAssetInterface* TagInterface = Cast<IGameplayTagAssetInterface>(ToAActor(actor));
+	if (TagInterface == nullptr)
+		return 0;
+
+	FGameplayTagContainer Tags;
+	TagInterface->GetOwnedGameplayTags(Tags);
+	auto Utf8 = FTCHARToUTF8(*ToTagString(Tags));
+	// An empty container is an empty allocation
+	if (Utf8.Length() == 0)
+		return 1;
+	GetRustModule().Plugin.Rust.allocate_fns.allocate(Utf8.Length(), 1, data);
+	FMemory::Memcpy(data->ptr, Utf8.Get(), data->size);
+	return 1;
+}
+
 void RegisterActorOnOverlap(AActorOpaque* actor)
 {
 	auto GameMode = GetRustModule().GameMode;
 	AActor* Actor = ToAActor(actor);
 	if (!GameMode || !Actor)
 		return;
-	Actor->OnActorBeginOverlap.AddUniqueDynamic(GameMode, &ARustGameModeBase::OnActorBeginOverlap);
-	Actor->OnActorEndOverlap.AddUniqueDynamic(GameMode, &ARustGameModeBase::OnActorEndOverlap);
+	Actor->ForEachComponent<UPrimitiveComponent>(false, [GameMode](UPrimitiveComponent* Component)
+	{
+		Component->OnComponentBeginOverlap.AddUniqueDynamic(GameMode, &ARustGameModeBase::OnComponentBeginOverlap);
+		Component->OnComponentEndOverlap.AddUniqueDynamic(GameMode, &ARustGameModeBase::OnComponentEndOverlap);
+	});
 }
 
 void RegisterActorOnHit(AActorOpaque* actor)
@@ -634,7 +780,75 @@ void RegisterActorOnHit(AActorOpaque* actor)
 	AActor* Actor = ToAActor(actor);
 	if (!GameMode || !Actor)
 		return;
-	Actor->OnActorHit.AddUniqueDynamic(GameMode, &ARustGameModeBase::OnActorHit);
+	Actor->ForEachComponent<UPrimitiveComponent>(false, [GameMode](UPrimitiveComponent* Component)
+	{
+		Component->OnComponentHit.AddUniqueDynamic(GameMode, &ARustGameModeBase::OnComponentHit);
+	});
+}
+
+void UnregisterActorOnOverlap(AActorOpaque* actor)
+{
+	auto GameMode = GetRustModule().GameMode;
+	AActor* Actor = ToAActor(actor);
+	if (!GameMode || !Actor)
+		return;
+	Actor->ForEachComponent<UPrimitiveComponent>(false, [GameMode](UPrimitiveComponent* Component)
+	{
+		Component->OnComponentBeginOverlap.RemoveDynamic(GameMode, &ARustGameModeBase::OnComponentBeginOverlap);
+		Component->OnComponentEndOverlap.RemoveDynamic(GameMode, &ARustGameModeBase::OnComponentEndOverlap);
+	});
+}
+
+void UnregisterActorOnHit(AActorOpaque* actor)
+{
+	auto GameMode = GetRustModule().GameMode;
+	AActor* Actor = ToAActor(actor);
+	if (!GameMode || !Actor)
+		return;
+	Actor->ForEachComponent<UPrimitiveComponent>(false, [GameMode](UPrimitiveComponent* Component)
+	{
+		Component->OnComponentHit.RemoveDynamic(GameMode, &ARustGameModeBase::OnComponentHit);
+	});
+}
+
+float ApplyDamage(AActorOpaque* actor, float damage, AActorOpaque* instigator)
+{
+	AActor* Actor = ToAActor(actor);
+	if (Actor == nullptr)
+		return 0.0f;
+
+	AActor* DamageCauser = ToAActor(instigator);
+	AController* InstigatorController = nullptr;
+	if (APawn* Pawn = Cast<APawn>(DamageCauser))
+	{
+		InstigatorController = Pawn->GetController();
+	}
+	return UGameplayStatics::ApplyDamage(Actor, damage, InstigatorController, DamageCauser,
+	                                     UDamageType::StaticClass());
+}
+
+void SetGlobalTimeDilation(float dilation)
+{
+	auto GameMode = GetRustModule().GameMode;
+	if (!GameMode)
+		return;
+	UGameplayStatics::SetGlobalTimeDilation(GameMode, dilation);
+}
+
+uint32_t SetGamePaused(uint32_t paused)
+{
+	auto GameMode = GetRustModule().GameMode;
+	if (!GameMode)
+		return 0;
+	return UGameplayStatics::SetGamePaused(GameMode, paused != 0) ? 1 : 0;
+}
+
+void SetCustomTimeDilation(AActorOpaque* actor, float dilation)
+{
+	if (AActor* Actor = ToAActor(actor))
+	{
+		Actor->CustomTimeDilation = dilation;
+	}
 }
 
 void DestroyActor(const AActorOpaque* actor)
@@ -642,3 +856,558 @@ void DestroyActor(const AActorOpaque* actor)
 	// TODO: What do we do if we can't destroy the actor?
 	ToAActor(actor)->Destroy();
 }
+
+uint32_t GetEditorComponentSoftObjectPath(const AActorOpaque* actor, Uuid uuid, Utf8Str field, RustAlloc* out)
+{
+	FRustProperty* Prop = GetRustProperty(actor, uuid, field);
+	if (Prop == nullptr)
+		return 0;
+
+	if (Prop->Tag != ERustPropertyTag::SoftObject)
+		return 0;
+
+	FString Path = Prop->SoftObject.ToString();
+	auto Utf8 = FTCHARToUTF8(*Path);
+	// An unset path is an empty allocation
+	if (Utf8.Length() == 0)
+		return 1;
+	GetRustModule().Plugin.Rust.allocate_fns.allocate(Utf8.Length(), 1, out);
+	FMemory::Memcpy(out->ptr, Utf8.Get(), out->size);
+	return 1;
+}
+
+uint32_t GetEditorComponentGameplayTags(const AActorOpaque* actor, Uuid uuid, Utf8Str field, RustAlloc* out)
+{
+	FRustProperty* Prop = GetRustProperty(actor, uuid, field);
+	if (Prop == nullptr)
+		return 0;
+
+	if (Prop->Tag != ERustPropertyTag::GameplayTags)
+		return 0;
+
+	auto Utf8 = FTCHARToUTF8(*ToTagString(Prop->GameplayTags));
+	// An empty container is an empty allocation
+	if (Utf8.Length() == 0)
+		return 1;
+	GetRustModule().Plugin.Rust.allocate_fns.allocate(Utf8.Length(), 1, out);
+	FMemory::Memcpy(out->ptr, Utf8.Get(), out->size);
+	return 1;
+}
+
+UObjectOpague* LoadAsset(Utf8Str path, UObjectType ty)
+{
+	FSoftObjectPath Path(ToFString(path));
+	UObject* Object = Path.TryLoad();
+	if (Object == nullptr || !Object->IsA(ToUClass(ty)))
+		return nullptr;
+	return (UObjectOpague*)Object;
+}
+
+uint32_t LoadAssetAsync(Utf8Str path, UObjectType ty, uint64_t handle)
+{
+	auto GameMode = GetRustModule().GameMode;
+	if (!GameMode)
+		return 0;
+
+	FSoftObjectPath Path(ToFString(path));
+	if (!Path.IsValid())
+		return 0;
+
+	UClass* Class = ToUClass(ty);
+	TWeakObjectPtr<ARustGameModeBase> WeakGameMode(GameMode);
+	TSharedPtr<FStreamableHandle> Handle = UAssetManager::GetStreamableManager().RequestAsyncLoad(
+		Path,
+		FStreamableDelegate::CreateLambda([WeakGameMode, Path, Class, handle]()
+		{
+			if (!WeakGameMode.IsValid())
+				return;
+			UObject* Object = Path.ResolveObject();
+			if (Object != nullptr && !Object->IsA(Class))
+			{
+				Object = nullptr;
+			}
+			WeakGameMode->OnAssetLoaded(handle, Object);
+		}));
+	if (!Handle.IsValid())
+		return 0;
+
+	GameMode->AssetHandles.Add(handle, Handle);
+	return 1;
+}
+
+void UnloadAsset(uint64_t handle)
+{
+	auto GameMode = GetRustModule().GameMode;
+	if (!GameMode)
+		return;
+
+	TSharedPtr<FStreamableHandle> Handle;
+	if (GameMode->AssetHandles.RemoveAndCopyValue(handle, Handle) && Handle.IsValid())
+	{
+		Handle->ReleaseHandle();
+	}
+}
+
+void DebugDraw(const DebugDrawCommand* commands, uintptr_t len)
+{
+#if ENABLE_DRAW_DEBUG
+	UWorld* World = GetRustModule().GameMode->GetWorld();
+	for (uintptr_t i = 0; i < len; ++i)
+	{
+		const DebugDrawCommand& Command = commands[i];
+		const FColor Color = ToFColor(Command.color);
+		// A negative life time only draws for a single frame
+		const float LifeTime = Command.duration > 0.0f ? Command.duration : -1.0f;
+		const uint8 DepthPriority = Command.depth_test != 0 ? SDPG_World : SDPG_Foreground;
+		const FVector Position = ToFVector(Command.position);
+		const FQuat Rotation = ToFQuat(Command.rotation);
+
+		switch (Command.ty)
+		{
+		case DebugDrawType::Line:
+			DrawDebugLine(World, Position, ToFVector(Command.end), Color, false, LifeTime, DepthPriority,
+			              Command.thickness);
+			break;
+		case DebugDrawType::Sphere:
+			DrawDebugSphere(World, Position, Command.size, 16, Color, false, LifeTime, DepthPriority,
+			                Command.thickness);
+			break;
+		case DebugDrawType::Capsule:
+			DrawDebugCapsule(World, Position, Command.half_height, Command.size, Rotation, Color, false, LifeTime,
+			                 DepthPriority, Command.thickness);
+			break;
+		case DebugDrawType::Box:
+			DrawDebugBox(World, Position, ToFVector(Command.end), Rotation, Color, false, LifeTime, DepthPriority,
+			             Command.thickness);
+			break;
+		case DebugDrawType::Arrow:
+			DrawDebugDirectionalArrow(World, Position, ToFVector(Command.end), Command.size, Color, false, LifeTime,
+			                          DepthPriority, Command.thickness);
+			break;
+		case DebugDrawType::Text:
+			DrawDebugString(World, Position, ToFString(Command.text), nullptr, Color, LifeTime,
+			                Command.depth_test == 0);
+			break;
+		case DebugDrawType::CoordinateFrame:
+			DrawDebugCoordinateSystem(World, Position, Rotation.Rotator(), Command.size, false, LifeTime,
+			                          DepthPriority, Command.thickness);
+			break;
+		}
+	}
+#endif
+}
+
+void SendBlueprintEvent(Uuid uuid, const ReflectValue* fields, uintptr_t len)
+{
+	ARustGameModeBase* GameMode = GetRustModule().GameMode;
+	if (GameMode == nullptr)
+		return;
+
+	GameMode->OnRustEvent.Broadcast(FRustEvent::FromReflectValues(uuid, fields, len));
+}
+
+// Strings that are returned to Rust, they stay valid until the next call
+static TUniquePtr<FTCHARToUTF8> ReturnedString;
+
+static bool WritePropertyValue(FProperty* Property, void* Container, const ReflectValue& Value)
+{
+	switch (Value.ty)
+	{
+	case ReflectionType::Float:
+		if (FFloatProperty* FloatProperty = CastField<FFloatProperty>(Property))
+		{
+			FloatProperty->SetPropertyValue_InContainer(Container, Value.data.float32);
+			return true;
+		}
+		if (FDoubleProperty* DoubleProperty = CastField<FDoubleProperty>(Property))
+		{
+			DoubleProperty->SetPropertyValue_InContainer(Container, Value.data.float32);
+			return true;
+		}
+		return false;
+	case ReflectionType::Bool:
+		if (FBoolProperty* BoolProperty = CastField<FBoolProperty>(Property))
+		{
+			BoolProperty->SetPropertyValue_InContainer(Container, Value.data.boolean != 0);
+			return true;
+		}
+		return false;
+	case ReflectionType::Vector3:
+		if (FStructProperty* StructProperty = CastField<FStructProperty>(Property))
+		{
+			if (StructProperty->Struct == TBaseStructure<FVector>::Get())
+			{
+				*StructProperty->ContainerPtrToValuePtr<FVector>(Container) = ToFVector(Value.data.vector3);
+				return true;
+			}
+		}
+		return false;
+	case ReflectionType::Quaternion:
+		if (FStructProperty* StructProperty = CastField<FStructProperty>(Property))
+		{
+			if (StructProperty->Struct == TBaseStructure<FQuat>::Get())
+			{
+				*StructProperty->ContainerPtrToValuePtr<FQuat>(Container) = ToFQuat(Value.data.quaternion);
+				return true;
+			}
+			if (StructProperty->Struct == TBaseStructure<FRotator>::Get())
+			{
+				*StructProperty->ContainerPtrToValuePtr<FRotator>(Container) = ToFQuat(Value.data.quaternion).
+					Rotator();
+				return true;
+			}
+		}
+		return false;
+	case ReflectionType::SoftObjectPath:
+		{
+			FSoftObjectPath Path(ToFString(Value.data.soft_object_path.path));
+			if (FSoftObjectProperty* SoftProperty = CastField<FSoftObjectProperty>(Property))
+			{
+				SoftProperty->SetPropertyValue_InContainer(Container, FSoftObjectPtr(Path));
+				return true;
+			}
+			if (FStructProperty* StructProperty = CastField<FStructProperty>(Property))
+			{
+				if (StructProperty->Struct == TBaseStructure<FSoftObjectPath>::Get())
+				{
+					*StructProperty->ContainerPtrToValuePtr<FSoftObjectPath>(Container) = Path;
+					return true;
+				}
+			}
+			return false;
+		}
+	case ReflectionType::GameplayTags:
+		if (FStructProperty* StructProperty = CastField<FStructProperty>(Property))
+		{
+			FGameplayTagContainer Tags = ToFGameplayTagContainer(Value.data.gameplay_tags);
+			if (StructProperty->Struct == FGameplayTagContainer::StaticStruct())
+			{
+				*StructProperty->ContainerPtrToValuePtr<FGameplayTagContainer>(Container) = Tags;
+				return true;
+			}
+			// A single tag only accepts a container with at most one tag
+			if (StructProperty->Struct == FGameplayTag::StaticStruct() && Tags.Num() <= 1)
+			{
+				*StructProperty->ContainerPtrToValuePtr<FGameplayTag>(Container) = Tags.First();
+				return true;
+			}
+		}
+		return false;
+	case ReflectionType::Composite:
+		return false;
+	default:
+		if (FObjectPropertyBase* ObjectProperty = CastField<FObjectPropertyBase>(Property))
+		{
+			UObject* Object = (UObject*)Value.data.uobject;
+			if (Object != nullptr && !Object->IsA(ObjectProperty->PropertyClass))
+				return false;
+			if (FClassProperty* ClassProperty = CastField<FClassProperty>(Property))
+			{
+				UClass* Class = Cast<UClass>(Object);
+				if (Object != nullptr && (Class == nullptr || !Class->IsChildOf(ClassProperty->MetaClass)))
+					return false;
+			}
+			ObjectProperty->SetObjectPropertyValue_InContainer(Container, Object);
+			return true;
+		}
+		return false;
+	}
+}
+
+static bool ReadPropertyValue(FProperty* Property, const void* Container, ReflectValue& Out)
+{
+	Out = {};
+	if (FFloatProperty* FloatProperty = CastField<FFloatProperty>(Property))
+	{
+		Out.ty = ReflectionType::Float;
+		Out.data.float32 = FloatProperty->GetPropertyValue_InContainer(Container);
+		return true;
+	}
+	if (FDoubleProperty* DoubleProperty = CastField<FDoubleProperty>(Property))
+	{
+		Out.ty = ReflectionType::Float;
+		Out.data.float32 = DoubleProperty->GetPropertyValue_InContainer(Container);
+		return true;
+	}
+	if (FBoolProperty* BoolProperty = CastField<FBoolProperty>(Property))
+	{
+		Out.ty = ReflectionType::Bool;
+		Out.data.boolean = BoolProperty->GetPropertyValue_InContainer(Container) ? 1 : 0;
+		return true;
+	}
+	if (FStructProperty* StructProperty = CastField<FStructProperty>(Property))
+	{
+		if (StructProperty->Struct == TBaseStructure<FVector>::Get())
+		{
+			Out.ty = ReflectionType::Vector3;
+			Out.data.vector3 = ToVector3(*StructProperty->ContainerPtrToValuePtr<FVector>(Container));
+			return true;
+		}
+		if (StructProperty->Struct == TBaseStructure<FQuat>::Get())
+		{
+			Out.ty = ReflectionType::Quaternion;
+			Out.data.quaternion = ToQuaternion(*StructProperty->ContainerPtrToValuePtr<FQuat>(Container));
+			return true;
+		}
+		if (StructProperty->Struct == TBaseStructure<FRotator>::Get())
+		{
+			Out.ty = ReflectionType::Quaternion;
+			Out.data.quaternion = ToQuaternion(
+				StructProperty->ContainerPtrToValuePtr<FRotator>(Container)->Quaternion());
+			return true;
+		}
+		if (StructProperty->Struct == TBaseStructure<FSoftObjectPath>::Get())
+		{
+			// Unreal doesn't know the asset type of an unloaded soft reference, Rust only uses the path
+			ReturnedString = MakeUnique<FTCHARToUTF8>(
+				*StructProperty->ContainerPtrToValuePtr<FSoftObjectPath>(Container)->ToString());
+			Out.ty = ReflectionType::SoftObjectPath;
+			Out.data.soft_object_path.path.ptr = ReturnedString->Get();
+			Out.data.soft_object_path.path.len = ReturnedString->Length();
+			return true;
+		}
+		if (StructProperty->Struct == FGameplayTagContainer::StaticStruct())
+		{
+			ReturnedString = MakeUnique<FTCHARToUTF8>(
+				*ToTagString(*StructProperty->ContainerPtrToValuePtr<FGameplayTagContainer>(Container)));
+			Out.ty = ReflectionType::GameplayTags;
+			Out.data.gameplay_tags.ptr = ReturnedString->Get();
+			Out.data.gameplay_tags.len = ReturnedString->Length();
+			return true;
+		}
+		if (StructProperty->Struct == FGameplayTag::StaticStruct())
+		{
+			const FGameplayTag* Tag = StructProperty->ContainerPtrToValuePtr<FGameplayTag>(Container);
+			ReturnedString = MakeUnique<FTCHARToUTF8>(Tag->IsValid() ? *Tag->ToString() : TEXT(""));
+			Out.ty = ReflectionType::GameplayTags;
+			Out.data.gameplay_tags.ptr = ReturnedString->Get();
+			Out.data.gameplay_tags.len = ReturnedString->Length();
+			return true;
+		}
+		return false;
+	}
+	if (FSoftObjectProperty* SoftProperty = CastField<FSoftObjectProperty>(Property))
+	{
+		ReturnedString = MakeUnique<FTCHARToUTF8>(
+			*SoftProperty->GetPropertyValue_InContainer(Container).ToSoftObjectPath().ToString());
+		Out.ty = ReflectionType::SoftObjectPath;
+		ToUObjectType(SoftProperty->PropertyClass, Out.data.soft_object_path.ty);
+		Out.data.soft_object_path.path.ptr = ReturnedString->Get();
+		Out.data.soft_object_path.path.len = ReturnedString->Length();
+		return true;
+	}
+	if (FObjectPropertyBase* ObjectProperty = CastField<FObjectPropertyBase>(Property))
+	{
+		UObjectType Type;
+		if (!ToUObjectType(ObjectProperty->PropertyClass, Type))
+			return false;
+		Out.ty = ToReflectionType(Type);
+		Out.data.uobject = (UObjectOpague*)ObjectProperty->GetObjectPropertyValue_InContainer(Container);
+		return true;
+	}
+	return false;
+}
+
+uint32_t CallUFunction(UObjectOpague* object,
+                       Utf8Str name,
+                       const ReflectValue* arguments,
+                       uintptr_t len,
+                       uint32_t* has_return_value,
+                       ReflectValue* out)
+{
+	UObject* Object = (UObject*)object;
+	if (!IsValid(Object))
+		return 0;
+
+	FString FunctionName = ToFString(name);
+	UFunction* Function = Object->FindFunction(FName(*FunctionName));
+	if (Function == nullptr)
+	{
+		UE_LOG(LogTemp, Warning, TEXT("%s has no function named %s"), *Object->GetName(), *FunctionName);
+		return 0;
+	}
+
+	uint8* Params = (uint8*)FMemory_Alloca_Aligned(Function->ParmsSize, Function->GetMinAlignment());
+	FMemory::Memzero(Params, Function->ParmsSize);
+	for (TFieldIterator<FProperty> It(Function); It && It->HasAnyPropertyFlags(CPF_Parm); ++It)
+	{
+		It->InitializeValue_InContainer(Params);
+	}
+
+	bool Success = true;
+	uintptr_t ArgumentIdx = 0;
+	FProperty* ReturnProperty = nullptr;
+	for (TFieldIterator<FProperty> It(Function); It && It->HasAnyPropertyFlags(CPF_Parm); ++It)
+	{
+		if (It->HasAnyPropertyFlags(CPF_ReturnParm))
+		{
+			ReturnProperty = *It;
+			continue;
+		}
+		// Pure out parameters are not passed in, but parameters that are passed by reference are
+		if (It->HasAnyPropertyFlags(CPF_OutParm) && !It->HasAnyPropertyFlags(CPF_ReferenceParm))
+			continue;
+
+		if (ArgumentIdx >= len || !WritePropertyValue(*It, Params, arguments[ArgumentIdx]))
+		{
+			Success = false;
+			break;
+		}
+		ArgumentIdx++;
+	}
+	Success = Success && ArgumentIdx == len;
+
+	if (Success)
+	{
+		Object->ProcessEvent(Function, Params);
+		*has_return_value = ReturnProperty != nullptr ? 1 : 0;
+		if (ReturnProperty != nullptr && !ReadPropertyValue(ReturnProperty, Params, *out))
+		{
+			UE_LOG(LogTemp, Warning, TEXT("Rust can't read the return value of %s::%s"),
+			       *Object->GetClass()->GetName(), *FunctionName);
+			Success = false;
+		}
+	}
+	else
+	{
+		UE_LOG(LogTemp, Warning, TEXT("The arguments from Rust don't match the parameters of %s::%s"),
+		       *Object->GetClass()->GetName(), *FunctionName);
+	}
+
+	for (TFieldIterator<FProperty> It(Function); It && It->HasAnyPropertyFlags(CPF_Parm); ++It)
+	{
+		It->DestroyValue_InContainer(Params);
+	}
+	return Success ? 1 : 0;
+}
+
+static FProperty* FindUProperty(UObject* Object, Utf8Str name)
+{
+	if (!IsValid(Object))
+		return nullptr;
+
+	FString PropertyName = ToFString(name);
+	FProperty* Property = Object->GetClass()->FindPropertyByName(FName(*PropertyName));
+	if (Property == nullptr)
+	{
+		UE_LOG(LogTemp, Warning, TEXT("%s has no property named %s"), *Object->GetName(), *PropertyName);
+	}
+	return Property;
+}
+
+uint32_t GetUProperty(UObjectOpague* object, Utf8Str name, ReflectValue* out)
+{
+	UObject* Object = (UObject*)object;
+	FProperty* Property = FindUProperty(Object, name);
+	if (Property == nullptr)
+		return 0;
+	return ReadPropertyValue(Property, Object, *out) ? 1 : 0;
+}
+
+uint32_t SetUProperty(UObjectOpague* object, Utf8Str name, const ReflectValue* value)
+{
+	UObject* Object = (UObject*)object;
+	FProperty* Property = FindUProperty(Object, name);
+	if (Property == nullptr)
+		return 0;
+	return WritePropertyValue(Property, Object, *value) ? 1 : 0;
+}
+
+ULevelOpaque* GetActorLevel(const AActorOpaque* actor)
+{
+	return (ULevelOpaque*)ToAActor(actor)->GetLevel();
+}
+
+void GetLevelName(const ULevelOpaque* level, RustAlloc* data)
+{
+	const ULevel* Level = (const ULevel*)level;
+	auto Utf8 = FTCHARToUTF8(*ToLevelName(Level->GetOutermost()->GetName()));
+	GetRustModule().Plugin.Rust.allocate_fns.allocate(Utf8.Length(), 1, data);
+	FMemory::Memcpy(data->ptr, Utf8.Get(), data->size);
+}
+
+uint32_t IsPersistentLevel(const ULevelOpaque* level)
+{
+	return ((const ULevel*)level)->IsPersistentLevel() ? 1 : 0;
+}
+
+static ULevelStreaming* FindStreamingLevel(Utf8Str name)
+{
+	auto GameMode = GetRustModule().GameMode;
+	if (!GameMode)
+		return nullptr;
+	ULevelStreaming* Streaming = UGameplayStatics::GetStreamingLevel(GameMode, FName(*ToFString(name)));
+	if (Streaming == nullptr)
+	{
+		UE_LOG(LogTemp, Warning, TEXT("There is no streaming level named %s"), *ToFString(name));
+	}
+	return Streaming;
+}
+
+uint32_t LoadStreamLevel(Utf8Str name, uint32_t make_visible, uint32_t block)
+{
+	ULevelStreaming* Streaming = FindStreamingLevel(name);
+	if (Streaming == nullptr)
+		return 0;
+	Streaming->SetShouldBeLoaded(true);
+	Streaming->SetShouldBeVisible(make_visible != 0);
+	// Flushing here would spawn the actors of the level and call back into Rust while it is still running. The world
+	// blocks on the load when it updates streaming in its next tick instead.
+	Streaming->bShouldBlockOnLoad = block != 0;
+	return 1;
+}
+
+uint32_t UnloadStreamLevel(Utf8Str name)
+{
+	ULevelStreaming* Streaming = FindStreamingLevel(name);
+	if (Streaming == nullptr)
+		return 0;
+	Streaming->SetShouldBeVisible(false);
+	Streaming->SetShouldBeLoaded(false);
+	return 1;
+}
+
+uint32_t SetStreamLevelVisible(Utf8Str name, uint32_t visible)
+{
+	ULevelStreaming* Streaming = FindStreamingLevel(name);
+	if (Streaming == nullptr)
+		return 0;
+	Streaming->SetShouldBeVisible(visible != 0);
+	return 1;
+}
+
+void OpenLevel(Utf8Str name)
+{
+	auto GameMode = GetRustModule().GameMode;
+	if (!GameMode)
+		return;
+	UGameplayStatics::OpenLevel(GameMode, FName(*ToFString(name)));
+}
+
+uint32_t SaveDataToSlot(Utf8Str slot, int32_t user_index, const uint8_t* data, uintptr_t len)
+{
+	TArray<uint8> SaveData(data, len);
+	return UGameplayStatics::SaveDataToSlot(SaveData, ToFString(slot), user_index) ? 1 : 0;
+}
+
+uint32_t LoadDataFromSlot(Utf8Str slot, int32_t user_index, RustAlloc* out)
+{
+	TArray<uint8> SaveData;
+	if (!UGameplayStatics::LoadDataFromSlot(SaveData, ToFString(slot), user_index))
+		return 0;
+	if (SaveData.Num() == 0)
+		return 1;
+	GetRustModule().Plugin.Rust.allocate_fns.allocate(SaveData.Num(), 1, out);
+	FMemory::Memcpy(out->ptr, SaveData.GetData(), out->size);
+	return 1;
+}
+
+uint32_t DoesSaveGameExist(Utf8Str slot, int32_t user_index)
+{
+	return UGameplayStatics::DoesSaveGameExist(ToFString(slot), user_index) ? 1 : 0;
+}
+
+uint32_t DeleteSaveGameInSlot(Utf8Str slot, int32_t user_index)
+{
+	return UGameplayStatics::DeleteGameInSlot(ToFString(slot), user_index) ? 1 : 0;
+}
@@ -0,0 +1,241 @@
+#include "K2Node_CallRustFunction.h"
+
+#include "BlueprintActionDatabaseRegistrar.h"
+#include "BlueprintNodeSpawner.h"
+#include "EntityComponent.h"
+#include "K2Node_CallFunction.h"
+#include "KismetCompiler.h"
+#include "RustFunction.h"
+#include "RustPlugin.h"
+#include "RustUtils.h"
+
+#define LOCTEXT_NAMESPACE "K2Node_CallRustFunction"
+
+static const FName EntityParamName(TEXT("EntityId"));
+static const FName SuccessParamName(TEXT("Success"));
+static const FName ReturnValueParamName(TEXT("ReturnValue"));
+
+static const FName CallNameParamName(TEXT("Name"));
+static const FName CallArgumentsParamName(TEXT("Arguments"));
+static const FName CallResultParamName(TEXT("Result"));
+static const FName ValuesParamName(TEXT("Values"));
+static const FName ValueParamName(TEXT("Value"));
+static const FName TypeParamName(TEXT("Type"));
+static const FName IndexParamName(TEXT("Index"));
+
+static bool IsUObjectType(ReflectionType Type)
+{
+	UObjectType ObjectType;
+	return FRustProperty::Default(Type).GetUObjectType(ObjectType);
+}
+
+static UFunction* FindLibraryFunction(FName Name)
+{
+	return GetDefault<URustFunctionLibrary>()->FindFunctionChecked(Name);
+}
+
+static UFunction* AddValueFunction(ReflectionType Type)
+{
+	switch (Type)
+	{
+	case ReflectionType::Float:
+		return FindLibraryFunction(GET_FUNCTION_NAME_CHECKED(URustFunctionLibrary, K2_AddRustFloat));
+	case ReflectionType::Bool:
+		return FindLibraryFunction(GET_FUNCTION_NAME_CHECKED(URustFunctionLibrary, K2_AddRustBool));
+	case ReflectionType::Vector3:
+		return FindLibraryFunction(GET_FUNCTION_NAME_CHECKED(URustFunctionLibrary, K2_AddRustVector));
+	case ReflectionType::Quaternion:
+		return FindLibraryFunction(GET_FUNCTION_NAME_CHECKED(URustFunctionLibrary, K2_AddRustQuat));
+	case ReflectionType::SoftObjectPath:
+		return FindLibraryFunction(GET_FUNCTION_NAME_CHECKED(URustFunctionLibrary, K2_AddRustSoftObject));
+	case ReflectionType::GameplayTags:
+		return FindLibraryFunction(GET_FUNCTION_NAME_CHECKED(URustFunctionLibrary, K2_AddRustGameplayTags));
+	default:
+		return IsUObjectType(Type)
+			       ? FindLibraryFunction(GET_FUNCTION_NAME_CHECKED(URustFunctionLibrary, K2_AddRustObject))
+			       : nullptr;
+	}
+}
+
+static UFunction* GetValueFunction(ReflectionType Type)
+{
+	switch (Type)
+	{
+	case ReflectionType::Float:
+		return FindLibraryFunction(GET_FUNCTION_NAME_CHECKED(URustFunctionLibrary, K2_GetRustFloat));
+	case ReflectionType::Bool:
+		return FindLibraryFunction(GET_FUNCTION_NAME_CHECKED(URustFunctionLibrary, K2_GetRustBool));
+	case ReflectionType::Vector3:
+		return FindLibraryFunction(GET_FUNCTION_NAME_CHECKED(URustFunctionLibrary, K2_GetRustVector));
+	case ReflectionType::Quaternion:
+		return FindLibraryFunction(GET_FUNCTION_NAME_CHECKED(URustFunctionLibrary, K2_GetRustQuat));
+	case ReflectionType::SoftObjectPath:
+		return FindLibraryFunction(GET_FUNCTION_NAME_CHECKED(URustFunctionLibrary, K2_GetRustSoftObject));
+	case ReflectionType::GameplayTags:
+		return FindLibraryFunction(GET_FUNCTION_NAME_CHECKED(URustFunctionLibrary, K2_GetRustGameplayTags));
+	default:
+		return IsUObjectType(Type)
+			       ? FindLibraryFunction(GET_FUNCTION_NAME_CHECKED(URustFunctionLibrary, K2_GetRustObject))
+			       : nullptr;
+	}
+}
+
+UEdGraphPin* UK2Node_CallRustFunction::CreateValuePin(EEdGraphPinDirection Direction, ReflectionType Type,
+                                                      const FString& Name)
+{
+	switch (Type)
+	{
+	case ReflectionType::Float:
+		return CreatePin(Direction, UEdGraphSchema_K2::PC_Real, UEdGraphSchema_K2::PC_Float, *Name);
+	case ReflectionType::Bool:
+		return CreatePin(Direction, UEdGraphSchema_K2::PC_Boolean, *Name);
+	case ReflectionType::Vector3:
+		return CreatePin(Direction, UEdGraphSchema_K2::PC_Struct, TBaseStructure<FVector>::Get(), *Name);
+	case ReflectionType::Quaternion:
+		return CreatePin(Direction, UEdGraphSchema_K2::PC_Struct, TBaseStructure<FQuat>::Get(), *Name);
+	case ReflectionType::SoftObjectPath:
+		return CreatePin(Direction, UEdGraphSchema_K2::PC_Struct, TBaseStructure<FSoftObjectPath>::Get(), *Name);
+	case ReflectionType::GameplayTags:
+		return CreatePin(Direction, UEdGraphSchema_K2::PC_Struct, FGameplayTagContainer::StaticStruct(), *Name);
+	default:
+		// TODO: Implement composite types
+		return IsUObjectType(Type)
+			       ? CreatePin(Direction, UEdGraphSchema_K2::PC_Object, UObject::StaticClass(), *Name)
+			       : nullptr;
+	}
+}
+
+void UK2Node_CallRustFunction::AllocateDefaultPins()
+{
+	Super::AllocateDefaultPins();
+	CreatePin(EGPD_Input, UEdGraphSchema_K2::PC_Exec, UEdGraphSchema_K2::PN_Execute);
+	CreatePin(EGPD_Output, UEdGraphSchema_K2::PC_Exec, UEdGraphSchema_K2::PN_Then);
+	CreatePin(EGPD_Input, UEdGraphSchema_K2::PC_Struct, FEntity::StaticStruct(), EntityParamName);
+	CreatePin(EGPD_Output, UEdGraphSchema_K2::PC_Boolean, SuccessParamName);
+
+	const FRustFunction* Function = GetRustModule().Plugin.ReflectionData.Functions.Find(FunctionName);
+	if (Function == nullptr)
+		return;
+
+	for (int32 Idx = 0; Idx < Function->ArgumentNames.Num(); ++Idx)
+	{
+		CreateValuePin(EGPD_Input, Function->ArgumentTypes[Idx], Function->ArgumentNames[Idx]);
+	}
+	if (Function->HasReturnValue)
+	{
+		CreateValuePin(EGPD_Output, Function->ReturnType, ReturnValueParamName.ToString());
+	}
+}
+
+void UK2Node_CallRustFunction::ExpandNode(class FKismetCompilerContext& CompilerContext, UEdGraph* SourceGraph)
+{
+	Super::ExpandNode(CompilerContext, SourceGraph);
+
+	const FRustFunction* Function = GetRustModule().Plugin.ReflectionData.Functions.Find(FunctionName);
+	if (Function == nullptr)
+	{
+		CompilerContext.MessageLog.Error(
+			*FText::Format(LOCTEXT("MissingFunction", "@@ Rust function {0} doesn't exist"),
+			               FText::FromString(FunctionName)).ToString(), this);
+		BreakAllNodeLinks();
+		return;
+	}
+
+	// The arguments are collected in a chain of pure nodes, starting with an empty `FRustValues`
+	UK2Node_CallFunction* MakeValues = CompilerContext.SpawnIntermediateNode<UK2Node_CallFunction>(this, SourceGraph);
+	MakeValues->SetFromFunction(FindLibraryFunction(
+		GET_FUNCTION_NAME_CHECKED(URustFunctionLibrary, K2_MakeRustValues)));
+	MakeValues->AllocateDefaultPins();
+	UEdGraphPin* ValuesPin = MakeValues->GetReturnValuePin();
+
+	for (int32 Idx = 0; Idx < Function->ArgumentNames.Num(); ++Idx)
+	{
+		ReflectionType Type = Function->ArgumentTypes[Idx];
+		UFunction* AddValue = AddValueFunction(Type);
+		UEdGraphPin* ArgumentPin = FindPin(*Function->ArgumentNames[Idx], EGPD_Input);
+		if (AddValue == nullptr || ArgumentPin == nullptr)
+		{
+			CompilerContext.MessageLog.Error(
+				*FText::Format(LOCTEXT("UnsupportedArgument", "@@ Argument {0} has a type that is not supported"),
+				               FText::FromString(Function->ArgumentNames[Idx])).ToString(), this);
+			BreakAllNodeLinks();
+			return;
+		}
+
+		UK2Node_CallFunction* AddNode = CompilerContext.SpawnIntermediateNode<UK2Node_CallFunction>(this, SourceGraph);
+		AddNode->SetFromFunction(AddValue);
+		AddNode->AllocateDefaultPins();
+		ValuesPin->MakeLinkTo(AddNode->FindPinChecked(ValuesParamName, EGPD_Input));
+		CompilerContext.MovePinLinksToIntermediate(*ArgumentPin, *AddNode->FindPinChecked(ValueParamName, EGPD_Input));
+		if (UEdGraphPin* TypePin = AddNode->FindPin(TypeParamName, EGPD_Input))
+		{
+			TypePin->DefaultValue = FString::FromInt((int32)Type);
+		}
+		ValuesPin = AddNode->GetReturnValuePin();
+	}
+
+	UK2Node_CallFunction* CallNode = CompilerContext.SpawnIntermediateNode<UK2Node_CallFunction>(this, SourceGraph);
+	CallNode->SetFromFunction(FindLibraryFunction(
+		GET_FUNCTION_NAME_CHECKED(URustFunctionLibrary, K2_CallRustFunction)));
+	CallNode->AllocateDefaultPins();
+	CallNode->FindPinChecked(CallNameParamName, EGPD_Input)->DefaultValue = FunctionName;
+	ValuesPin->MakeLinkTo(CallNode->FindPinChecked(CallArgumentsParamName, EGPD_Input));
+
+	CompilerContext.MovePinLinksToIntermediate(*GetExecPin(), *CallNode->GetExecPin());
+	CompilerContext.MovePinLinksToIntermediate(*FindPinChecked(UEdGraphSchema_K2::PN_Then, EGPD_Output),
+	                                           *CallNode->GetThenPin());
+	CompilerContext.MovePinLinksToIntermediate(*FindPinChecked(EntityParamName, EGPD_Input),
+	                                           *CallNode->FindPinChecked(EntityParamName, EGPD_Input));
+	CompilerContext.MovePinLinksToIntermediate(*FindPinChecked(SuccessParamName, EGPD_Output),
+	                                           *CallNode->GetReturnValuePin());
+
+	UEdGraphPin* ReturnValuePin = FindPin(ReturnValueParamName, EGPD_Output);
+	if (Function->HasReturnValue && ReturnValuePin != nullptr)
+	{
+		UK2Node_CallFunction* GetNode = CompilerContext.SpawnIntermediateNode<UK2Node_CallFunction>(this, SourceGraph);
+		GetNode->SetFromFunction(GetValueFunction(Function->ReturnType));
+		GetNode->AllocateDefaultPins();
+		CallNode->FindPinChecked(CallResultParamName, EGPD_Output)->MakeLinkTo(
+			GetNode->FindPinChecked(ValuesParamName, EGPD_Input));
+		GetNode->FindPinChecked(IndexParamName, EGPD_Input)->DefaultValue = TEXT("0");
+		CompilerContext.MovePinLinksToIntermediate(*ReturnValuePin, *GetNode->GetReturnValuePin());
+	}
+
+	BreakAllNodeLinks();
+}
+
+void UK2Node_CallRustFunction::GetMenuActions(FBlueprintActionDatabaseRegistrar& ActionRegistrar) const
+{
+	const auto ActionKey = GetClass();
+	if (!ActionRegistrar.IsOpenForRegistration(ActionKey))
+		return;
+
+	// TODO: The actions are cached by the editor, functions that are added after a hot reload only show up after
+	//       a restart.
+	for (const auto& Elem : GetRustModule().Plugin.ReflectionData.Functions)
+	{
+		const auto NodeSpawner = UBlueprintNodeSpawner::Create(ActionKey);
+		check(NodeSpawner != nullptr);
+
+		NodeSpawner->DefaultMenuSignature.MenuName = FText::Format(
+			LOCTEXT("MenuName", "Call {0} (Rust)"), FText::FromString(Elem.Key));
+		NodeSpawner->CustomizeNodeDelegate = UBlueprintNodeSpawner::FCustomizeNodeDelegate::CreateLambda(
+			[Name = Elem.Key](UEdGraphNode* NewNode, bool bIsTemplateNode)
+			{
+				CastChecked<UK2Node_CallRustFunction>(NewNode)->FunctionName = Name;
+			});
+		ActionRegistrar.AddBlueprintAction(ActionKey, NodeSpawner);
+	}
+}
+
+FText UK2Node_CallRustFunction::GetMenuCategory() const
+{
+	return LOCTEXT("MenuCategory", "Rust|Functions");
+}
+
+FText UK2Node_CallRustFunction::GetNodeTitle(ENodeTitleType::Type TitleType) const
+{
+	return FText::Format(LOCTEXT("NodeTitle", "Call {0} (Rust)"), FText::FromString(FunctionName));
+}
+
+#undef LOCTEXT_NAMESPACE
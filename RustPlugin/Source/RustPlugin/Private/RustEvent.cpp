@@ -0,0 +1,193 @@
+#include "RustEvent.h"
+
+#include "RustPlugin.h"
+#include "RustUtils.h"
+
+static FRustProperty* FindField(FRustEvent& Event, const FString& Field, ERustPropertyTag Tag)
+{
+	FRustProperty* Property = Event.Fields.Find(Field);
+	if (Property == nullptr || Property->Tag != Tag)
+	{
+		UE_LOG(LogTemp, Warning, TEXT("Rust event %s has no field %s of the requested type"), *Event.Name, *Field);
+		return nullptr;
+	}
+	return Property;
+}
+
+static const FRustProperty* FindField(const FRustEvent& Event, const FString& Field, ERustPropertyTag Tag)
+{
+	return FindField(const_cast<FRustEvent&>(Event), Field, Tag);
+}
+
+bool FRustEvent::Create(const FString& EventName, FRustEvent& Out)
+{
+	for (auto& Elem : GetRustModule().Plugin.ReflectionData.Types)
+	{
+		if (!Elem.Value.IsEvent || Elem.Value.Name != EventName)
+		{
+			continue;
+		}
+		Out.Name = EventName;
+		Out.Id = Elem.Key;
+		Out.Fields.Reset();
+		for (auto& Field : Elem.Value.FieldNameToType)
+		{
+			Out.Fields.Add(Field.Key, FRustProperty::Default(Field.Value));
+		}
+		return true;
+	}
+	return false;
+}
+
+FRustEvent FRustEvent::FromReflectValues(Uuid EventId, const ReflectValue* Values, uintptr_t Len)
+{
+	FRustEvent Event;
+	Event.Id = ToFGuid(EventId);
+	if (auto Reflection = GetRustModule().Plugin.ReflectionData.Types.Find(Event.Id))
+	{
+		Event.Name = Reflection->Name;
+		for (uintptr_t Idx = 0; Idx < Len; ++Idx)
+		{
+			if (auto FieldName = Reflection->IndexToFieldName.Find(Idx))
+			{
+				Event.Fields.Add(*FieldName, FRustProperty::FromReflectValue(Values[Idx]));
+			}
+		}
+	}
+	return Event;
+}
+
+FRustEvent URustEventLibrary::MakeRustEvent(FString EventName)
+{
+	FRustEvent Event;
+	if (!FRustEvent::Create(EventName, Event))
+	{
+		UE_LOG(LogTemp, Warning, TEXT("There is no Rust event named %s"), *EventName);
+	}
+	return Event;
+}
+
+bool URustEventLibrary::IsRustEvent(const FRustEvent& Event, FString EventName)
+{
+	return Event.Name == EventName;
+}
+
+bool URustEventLibrary::SendRustEvent(const FRustEvent& Event)
+{
+	auto& Module = GetRustModule();
+	if (!Module.Plugin.IsLoaded())
+		return false;
+
+	auto Reflection = Module.Plugin.ReflectionData.Types.Find(Event.Id);
+	if (Reflection == nullptr || !Reflection->IsEvent)
+		return false;
+
+	TArray<TUniquePtr<FTCHARToUTF8>> Utf8Strings;
+	TArray<ReflectValue> Values;
+	for (uint32 Idx = 0; Idx < (uint32)Reflection->IndexToFieldName.Num(); ++Idx)
+	{
+		const FString& FieldName = Reflection->IndexToFieldName[Idx];
+		const FRustProperty* Property = Event.Fields.Find(FieldName);
+		if (Property == nullptr)
+			return false;
+		Values.Add(Property->ToReflectValue(Reflection->FieldNameToType[FieldName], Utf8Strings));
+	}
+	return Module.Plugin.Rust.send_event(ToUuid(Event.Id), Values.GetData(), Values.Num()) == 1;
+}
+
+void URustEventLibrary::SetRustEventFloat(FRustEvent& Event, FString Field, float Value)
+{
+	if (FRustProperty* Property = FindField(Event, Field, ERustPropertyTag::Float))
+		Property->Float = Value;
+}
+
+void URustEventLibrary::SetRustEventBool(FRustEvent& Event, FString Field, bool Value)
+{
+	if (FRustProperty* Property = FindField(Event, Field, ERustPropertyTag::Bool))
+		Property->Bool = Value;
+}
+
+void URustEventLibrary::SetRustEventVector(FRustEvent& Event, FString Field, FVector Value)
+{
+	if (FRustProperty* Property = FindField(Event, Field, ERustPropertyTag::Vector))
+		Property->Vector = Value;
+}
+
+void URustEventLibrary::SetRustEventRotator(FRustEvent& Event, FString Field, FRotator Value)
+{
+	if (FRustProperty* Property = FindField(Event, Field, ERustPropertyTag::Quat))
+		Property->Rotation = Value;
+}
+
+void URustEventLibrary::SetRustEventObject(FRustEvent& Event, FString Field, UObject* Value)
+{
+	FRustProperty* Property = Event.Fields.Find(Field);
+	UObjectType ObjectType;
+	if (Property != nullptr && Property->GetUObjectType(ObjectType))
+	{
+		Property->SetUObject(ObjectType, Value);
+	}
+	else
+	{
+		UE_LOG(LogTemp, Warning, TEXT("Rust event %s has no object field %s"), *Event.Name, *Field);
+	}
+}
+
+void URustEventLibrary::SetRustEventSoftObject(FRustEvent& Event, FString Field, FSoftObjectPath Value)
+{
+	if (FRustProperty* Property = FindField(Event, Field, ERustPropertyTag::SoftObject))
+		Property->SoftObject = Value;
+}
+
+void URustEventLibrary::SetRustEventGameplayTags(FRustEvent& Event, FString Field, FGameplayTagContainer Value)
+{
+	if (FRustProperty* Property = FindField(Event, Field, ERustPropertyTag::GameplayTags))
+		Property->GameplayTags = Value;
+}
+
+float URustEventLibrary::GetRustEventFloat(const FRustEvent& Event, FString Field)
+{
+	const FRustProperty* Property = FindField(Event, Field, ERustPropertyTag::Float);
+	return Property != nullptr ? Property->Float : 0.0f;
+}
+
+bool URustEventLibrary::GetRustEventBool(const FRustEvent& Event, FString Field)
+{
+	const FRustProperty* Property = FindField(Event, Field, ERustPropertyTag::Bool);
+	return Property != nullptr ? Property->Bool : false;
+}
+
+FVector URustEventLibrary::GetRustEventVector(const FRustEvent& Event, FString Field)
+{
+	const FRustProperty* Property = FindField(Event, Field, ERustPropertyTag::Vector);
+	return Property != nullptr ? Property->Vector : FVector::ZeroVector;
+}
+
+FRotator URustEventLibrary::GetRustEventRotator(const FRustEvent& Event, FString Field)
+{
+	const FRustProperty* Property = FindField(Event, Field, ERustPropertyTag::Quat);
+	return Property != nullptr ? Property->Rotation : FRotator::ZeroRotator;
+}
+
+UObject* URustEventLibrary::GetRustEventObject(const FRustEvent& Event, FString Field)
+{
+	const FRustProperty* Property = Event.Fields.Find(Field);
+	UObjectType ObjectType;
+	if (Property != nullptr && Property->GetUObjectType(ObjectType))
+	{
+		return Property->GetUObject(ObjectType);
+	}
+	return nullptr;
+}
+
+FSoftObjectPath URustEventLibrary::GetRustEventSoftObject(const FRustEvent& Event, FString Field)
+{
+	const FRustProperty* Property = FindField(Event, Field, ERustPropertyTag::SoftObject);
+	return Property != nullptr ? Property->SoftObject : FSoftObjectPath();
+}
+
+FGameplayTagContainer URustEventLibrary::GetRustEventGameplayTags(const FRustEvent& Event, FString Field)
+{
+	const FRustProperty* Property = FindField(Event, Field, ERustPropertyTag::GameplayTags);
+	return Property != nullptr ? Property->GameplayTags : FGameplayTagContainer();
+}
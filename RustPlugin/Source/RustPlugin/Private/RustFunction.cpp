@@ -0,0 +1,166 @@
+#include "RustFunction.h"
+
+#include "RustPlugin.h"
+#include "RustUtils.h"
+
+static FRustValues AddValue(const FRustValues& Values, const FRustProperty& Property)
+{
+	FRustValues Out = Values;
+	Out.Values.Add(Property);
+	return Out;
+}
+
+static const FRustProperty* GetValue(const FRustValues& Values, int32 Index, ERustPropertyTag Tag)
+{
+	if (!Values.Values.IsValidIndex(Index) || Values.Values[Index].Tag != Tag)
+	{
+		return nullptr;
+	}
+	return &Values.Values[Index];
+}
+
+FRustValues URustFunctionLibrary::K2_MakeRustValues()
+{
+	return FRustValues();
+}
+
+FRustValues URustFunctionLibrary::K2_AddRustFloat(const FRustValues& Values, float Value)
+{
+	FRustProperty Property = FRustProperty::Default(ReflectionType::Float);
+	Property.Float = Value;
+	return AddValue(Values, Property);
+}
+
+FRustValues URustFunctionLibrary::K2_AddRustBool(const FRustValues& Values, bool Value)
+{
+	FRustProperty Property = FRustProperty::Default(ReflectionType::Bool);
+	Property.Bool = Value;
+	return AddValue(Values, Property);
+}
+
+FRustValues URustFunctionLibrary::K2_AddRustVector(const FRustValues& Values, FVector Value)
+{
+	FRustProperty Property = FRustProperty::Default(ReflectionType::Vector3);
+	Property.Vector = Value;
+	return AddValue(Values, Property);
+}
+
+FRustValues URustFunctionLibrary::K2_AddRustQuat(const FRustValues& Values, FQuat Value)
+{
+	FRustProperty Property = FRustProperty::Default(ReflectionType::Quaternion);
+	Property.Rotation = Value.Rotator();
+	return AddValue(Values, Property);
+}
+
+FRustValues URustFunctionLibrary::K2_AddRustSoftObject(const FRustValues& Values, FSoftObjectPath Value)
+{
+	FRustProperty Property = FRustProperty::Default(ReflectionType::SoftObjectPath);
+	Property.SoftObject = Value;
+	return AddValue(Values, Property);
+}
+
+FRustValues URustFunctionLibrary::K2_AddRustGameplayTags(const FRustValues& Values, FGameplayTagContainer Value)
+{
+	FRustProperty Property = FRustProperty::Default(ReflectionType::GameplayTags);
+	Property.GameplayTags = Value;
+	return AddValue(Values, Property);
+}
+
+FRustValues URustFunctionLibrary::K2_AddRustObject(const FRustValues& Values, UObject* Value, int32 Type)
+{
+	FRustProperty Property = FRustProperty::Default((ReflectionType)Type);
+	UObjectType ObjectType;
+	if (Property.GetUObjectType(ObjectType))
+	{
+		Property.SetUObject(ObjectType, Value);
+	}
+	return AddValue(Values, Property);
+}
+
+bool URustFunctionLibrary::K2_CallRustFunction(FString Name, FEntity EntityId, const FRustValues& Arguments,
+                                               FRustValues& Result)
+{
+	Result.Values.Reset();
+
+	auto& Module = GetRustModule();
+	if (!Module.Plugin.IsLoaded())
+		return false;
+
+	const FRustFunction* Function = Module.Plugin.ReflectionData.Functions.Find(Name);
+	if (Function == nullptr || Function->ArgumentTypes.Num() != Arguments.Values.Num())
+	{
+		UE_LOG(LogTemp, Warning, TEXT("Unable to call the Rust function %s"), *Name);
+		return false;
+	}
+
+	TArray<TUniquePtr<FTCHARToUTF8>> Utf8Strings;
+	TArray<ReflectValue> Values;
+	for (int32 Idx = 0; Idx < Arguments.Values.Num(); ++Idx)
+	{
+		Values.Add(Arguments.Values[Idx].ToReflectValue(Function->ArgumentTypes[Idx], Utf8Strings));
+	}
+
+	FTCHARToUTF8 NameUtf8(*Name);
+	Utf8Str NameStr;
+	NameStr.ptr = NameUtf8.Get();
+	NameStr.len = NameUtf8.Length();
+
+	uint32_t HasReturnValue = 0;
+	ReflectValue ReturnValue = {};
+	if (Module.Plugin.Rust.function_fns.call_function(NameStr, EntityId.ToRustEntity(), Values.GetData(),
+	                                                  Values.Num(), &HasReturnValue, &ReturnValue) != 1)
+	{
+		return false;
+	}
+	if (HasReturnValue == 1)
+	{
+		Result.Values.Add(FRustProperty::FromReflectValue(ReturnValue));
+	}
+	return true;
+}
+
+float URustFunctionLibrary::K2_GetRustFloat(const FRustValues& Values, int32 Index)
+{
+	const FRustProperty* Property = GetValue(Values, Index, ERustPropertyTag::Float);
+	return Property ? Property->Float : 0.0f;
+}
+
+bool URustFunctionLibrary::K2_GetRustBool(const FRustValues& Values, int32 Index)
+{
+	const FRustProperty* Property = GetValue(Values, Index, ERustPropertyTag::Bool);
+	return Property ? Property->Bool : false;
+}
+
+FVector URustFunctionLibrary::K2_GetRustVector(const FRustValues& Values, int32 Index)
+{
+	const FRustProperty* Property = GetValue(Values, Index, ERustPropertyTag::Vector);
+	return Property ? Property->Vector : FVector::ZeroVector;
+}
+
+FQuat URustFunctionLibrary::K2_GetRustQuat(const FRustValues& Values, int32 Index)
+{
+	const FRustProperty* Property = GetValue(Values, Index, ERustPropertyTag::Quat);
+	return Property ? Property->Rotation.Quaternion() : FQuat::Identity;
+}
+
+FSoftObjectPath URustFunctionLibrary::K2_GetRustSoftObject(const FRustValues& Values, int32 Index)
+{
+	const FRustProperty* Property = GetValue(Values, Index, ERustPropertyTag::SoftObject);
+	return Property ? Property->SoftObject : FSoftObjectPath();
+}
+
+FGameplayTagContainer URustFunctionLibrary::K2_GetRustGameplayTags(const FRustValues& Values, int32 Index)
+{
+	const FRustProperty* Property = GetValue(Values, Index, ERustPropertyTag::GameplayTags);
+	return Property ? Property->GameplayTags : FGameplayTagContainer();
+}
+
+UObject* URustFunctionLibrary::K2_GetRustObject(const FRustValues& Values, int32 Index)
+{
+	UObjectType ObjectType;
+	if (!Values.Values.IsValidIndex(Index) || !Values.Values[Index].GetUObjectType(ObjectType))
+	{
+		return nullptr;
+	}
+	return Values.Values[Index].GetUObject(ObjectType);
+}
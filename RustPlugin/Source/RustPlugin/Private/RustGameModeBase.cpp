@@ -10,8 +10,11 @@
 #include "Engine/InputDelegateBinding.h"
 #include "GameFramework/PlayerInput.h"
 #include "Kismet/GameplayStatics.h"
+#include "GameFramework/WorldSettings.h"
 #include "Widgets/Notifications/SNotificationList.h"
 #include "Framework/Notifications/NotificationManager.h"
+#include "Engine/Level.h"
+#include "Engine/LevelStreaming.h"
 
 #include "Editor.h"
 #include "RustUtils.h"
@@ -29,6 +32,8 @@ ARustGameModeBase::ARustGameModeBase()
 
 	PrimaryActorTick.bStartWithTickEnabled = true;
 	PrimaryActorTick.bCanEverTick = true;
+	// Rust keeps track of real time and drives pause menus while the game is paused
+	PrimaryActorTick.bTickEvenWhenPaused = true;
 
 	bBlockInput = false;
 	AutoReceiveInput = EAutoReceiveInput::Player0;
@@ -48,31 +53,54 @@ void ARustGameModeBase::OnActorSpawnedHandler(AActor* actor)
 	GetRustModule().Plugin.Rust.unreal_event(&Type, (void*)&Event);
 }
 
-void ARustGameModeBase::OnActorBeginOverlap(AActor* OverlappedActor, AActor* OtherActor)
+void ARustGameModeBase::OnComponentBeginOverlap(UPrimitiveComponent* OverlappedComponent, AActor* OtherActor,
+                                                UPrimitiveComponent* OtherComp, int32 OtherBodyIndex,
+                                                bool bFromSweep, const FHitResult& SweepResult)
 {
 	EventType Type = EventType::ActorBeginOverlap;
-	ActorBeginOverlap Event;
-	Event.overlapped_actor = (AActorOpaque*)OverlappedActor;
+	ActorBeginOverlap Event = {};
+	Event.overlapped_actor = (AActorOpaque*)OverlappedComponent->GetOwner();
 	Event.other = (AActorOpaque*)OtherActor;
+	Event.overlapped_component = (UPrimtiveOpaque*)OverlappedComponent;
+	Event.other_component = (UPrimtiveOpaque*)OtherComp;
+	Event.other_body_index = OtherBodyIndex;
+	Event.from_sweep = bFromSweep;
+	if (bFromSweep)
+	{
+		Event.sweep_result = ToHitResult(SweepResult);
+	}
 	GetRustModule().Plugin.Rust.unreal_event(&Type, (void*)&Event);
 }
 
-void ARustGameModeBase::OnActorEndOverlap(AActor* OverlappedActor, AActor* OtherActor)
+void ARustGameModeBase::OnComponentEndOverlap(UPrimitiveComponent* OverlappedComponent, AActor* OtherActor,
+                                              UPrimitiveComponent* OtherComp, int32 OtherBodyIndex)
 {
 	EventType Type = EventType::ActorEndOverlap;
 	ActorEndOverlap Event;
-	Event.overlapped_actor = (AActorOpaque*)OverlappedActor;
+	Event.overlapped_actor = (AActorOpaque*)OverlappedComponent->GetOwner();
 	Event.other = (AActorOpaque*)OtherActor;
+	Event.overlapped_component = (UPrimtiveOpaque*)OverlappedComponent;
+	Event.other_component = (UPrimtiveOpaque*)OtherComp;
+	Event.other_body_index = OtherBodyIndex;
 	GetRustModule().Plugin.Rust.unreal_event(&Type, (void*)&Event);
 }
 
-void ARustGameModeBase::OnActorHit(AActor* SelfActor, AActor* OtherActor, FVector NormalImpulse, const FHitResult& Hit)
+void ARustGameModeBase::OnComponentHit(UPrimitiveComponent* HitComponent, AActor* OtherActor,
+                                       UPrimitiveComponent* OtherComp, FVector NormalImpulse, const FHitResult& Hit)
 {
 	EventType Type = EventType::ActorOnHit;
 	ActorHitEvent Event;
-	Event.self_actor = (AActorOpaque*)SelfActor;
+	Event.self_actor = (AActorOpaque*)HitComponent->GetOwner();
 	Event.other = (AActorOpaque*)OtherActor;
+	Event.self_component = (UPrimtiveOpaque*)HitComponent;
+	Event.other_component = (UPrimtiveOpaque*)OtherComp;
 	Event.normal_impulse = ToVector3(NormalImpulse);
+	Event.hit = ToHitResult(Hit);
+	FTCHARToUTF8 BoneName(Hit.BoneName.IsNone() ? TEXT("") : *Hit.BoneName.ToString());
+	Event.bone_name.ptr = BoneName.Get();
+	Event.bone_name.len = BoneName.Length();
+	FVector OtherVelocity = OtherComp != nullptr ? OtherComp->GetComponentVelocity() : FVector::ZeroVector;
+	Event.relative_velocity = ToVector3(HitComponent->GetComponentVelocity() - OtherVelocity);
 	GetRustModule().Plugin.Rust.unreal_event(&Type, (void*)&Event);
 }
 
@@ -84,6 +112,14 @@ void ARustGameModeBase::OnActorDestroyed(AActor* Actor)
 	GetRustModule().Plugin.Rust.unreal_event(&Type, (void*)&Event);
 }
 
+void ARustGameModeBase::OnAssetLoaded(uint64 Handle, UObject* Object)
+{
+	AssetLoadedEvent Event;
+	Event.handle = Handle;
+	Event.object = (UObjectOpague*)Object;
+	PendingAssetLoadedEvents.Add(Event);
+}
+
 void ARustGameModeBase::PostLogin(APlayerController* NewPlayer)
 {
 	Super::PostLogin(NewPlayer);
@@ -98,16 +134,139 @@ void ARustGameModeBase::Tick(float Dt)
 	{
 		StartPlay();
 	}
-	if (Module.Plugin.IsLoaded() && Module.Plugin.Rust.tick(Dt) == ResultCode::Panic)
+	if (Module.Plugin.IsLoaded())
+	{
+		for (AssetLoadedEvent& Event : PendingAssetLoadedEvents)
+		{
+			EventType Type = EventType::AssetLoaded;
+			Module.Plugin.Rust.unreal_event(&Type, (void*)&Event);
+		}
+	}
+	PendingAssetLoadedEvents.Empty();
+	if (Module.Plugin.IsLoaded())
+	{
+		UpdateStreamingLevels(true);
+	}
+	UWorld* World = GetWorld();
+	TickTime Time;
+	Time.delta = Dt;
+	Time.real_delta = World->DeltaRealTimeSeconds;
+	Time.time_dilation = World->GetWorldSettings()->GetEffectiveTimeDilation();
+	Time.paused = World->IsPaused() ? 1 : 0;
+	if (Module.Plugin.IsLoaded() && Module.Plugin.Rust.tick(Time) == ResultCode::Panic)
+	{
+		Module.Exit();
+	}
+}
+
+void FRustTickFunction::ExecuteTick(float DeltaTime, ELevelTick TickType, ENamedThreads::Type CurrentThread,
+                                    const FGraphEventRef& MyCompletionGraphEvent)
+{
+	FRustPluginModule& Module = GetRustModule();
+	if (Module.Plugin.IsLoaded() && !Module.Plugin.NeedsInit && Module.Plugin.Rust.tick_group(Group) ==
+		ResultCode::Panic)
 	{
 		Module.Exit();
 	}
 }
 
+FString FRustTickFunction::DiagnosticMessage()
+{
+	return FString::Printf(TEXT("FRustTickFunction[%d]"), static_cast<uint32>(Group));
+}
+
+void ARustGameModeBase::RegisterTickGroup(FRustTickFunction& TickFunction, TickGroup Group,
+                                          ETickingGroup UnrealGroup)
+{
+	TickFunction.Group = Group;
+	TickFunction.TickGroup = UnrealGroup;
+	TickFunction.bCanEverTick = true;
+	TickFunction.bTickEvenWhenPaused = true;
+	TickFunction.bRunOnAnyThread = false;
+	TickFunction.RegisterTickFunction(GetLevel());
+}
+
+void ARustGameModeBase::SendLevelEvent(EventType Type, ULevel* Level, const FString& Name)
+{
+	LevelStreamingEvent Event;
+	Event.level = (ULevelOpaque*)Level;
+	FTCHARToUTF8 Utf8(*Name);
+	Event.name.ptr = Utf8.Get();
+	Event.name.len = Utf8.Length();
+	GetRustModule().Plugin.Rust.unreal_event(&Type, (void*)&Event);
+}
+
+void ARustGameModeBase::UpdateStreamingLevels(bool bRegisterActors)
+{
+	for (ULevelStreaming* Streaming : GetWorld()->GetStreamingLevels())
+	{
+		if (Streaming == nullptr)
+			continue;
+		FRustStreamingLevelState& State = StreamingLevelStates.FindOrAdd(Streaming);
+		ULevel* Level = Streaming->GetLoadedLevel();
+		const bool bLoaded = Level != nullptr;
+		const bool bVisible = bLoaded && Streaming->IsLevelVisible();
+		const FString Name = ToLevelName(Streaming->GetWorldAssetPackageName());
+
+		// A level is always hidden before it is unloaded and loaded before it is shown
+		if (State.bVisible && !bVisible)
+		{
+			SendLevelEvent(EventType::LevelHidden, State.Level, Name);
+		}
+		if (State.bLoaded && !bLoaded)
+		{
+			SendLevelEvent(EventType::LevelUnloaded, nullptr, Name);
+		}
+		if (!State.bLoaded && bLoaded)
+		{
+			SendLevelEvent(EventType::LevelLoaded, Level, Name);
+		}
+		if (!State.bVisible && bVisible)
+		{
+			SendLevelEvent(EventType::LevelShown, Level, Name);
+			if (bRegisterActors)
+			{
+				// Actors of streamed levels are loaded instead of spawned, so the spawn handler never sees them
+				for (AActor* Actor : Level->Actors)
+				{
+					if (!IsValid(Actor))
+						continue;
+					Actor->OnDestroyed.AddUniqueDynamic(this, &ARustGameModeBase::OnActorDestroyed);
+					OnActorSpawnedHandler(Actor);
+				}
+			}
+		}
+		State.bLoaded = bLoaded;
+		State.bVisible = bVisible;
+		State.Level = Level;
+	}
+}
+
+void ARustGameModeBase::EndPlay(const EEndPlayReason::Type EndPlayReason)
+{
+	FRustPluginModule& Module = GetRustModule();
+	if (Module.Plugin.IsLoaded() && Module.Plugin.Rust.end_play(ToEndPlayReason(EndPlayReason)) ==
+		ResultCode::Panic)
+	{
+		Module.Exit();
+	}
+	// Rust dropped its world, nothing that refers to it may reach Rust anymore
+	GetWorld()->RemoveOnActorSpawnedHandler(ActorSpawnedHandle);
+	PendingAssetLoadedEvents.Empty();
+	AssetHandles.Empty();
+	StreamingLevelStates.Empty();
+
+	DuringPhysicsTick.UnRegisterTickFunction();
+	PostPhysicsTick.UnRegisterTickFunction();
+	LateCameraTick.UnRegisterTickFunction();
+	PostUpdateWorkTick.UnRegisterTickFunction();
+	Super::EndPlay(EndPlayReason);
+}
+
 void ARustGameModeBase::StartPlay()
 {
 	Super::StartPlay();
-	GetWorld()->AddOnActorSpawnedHandler(
+	ActorSpawnedHandle = GetWorld()->AddOnActorSpawnedHandler(
 		FOnActorSpawned::FDelegate::CreateUObject(this, &ARustGameModeBase::OnActorSpawnedHandler));
 
 	APlayerController* PC = UGameplayStatics::GetPlayerController(this, 0);
@@ -117,6 +276,13 @@ void ARustGameModeBase::StartPlay()
 		InputComponent->BindAxis(Mapping.AxisName);
 	}
 
+	RegisterTickGroup(DuringPhysicsTick, TickGroup::DuringPhysics, TG_DuringPhysics);
+	RegisterTickGroup(PostPhysicsTick, TickGroup::PostPhysics, TG_PostPhysics);
+	RegisterTickGroup(LateCameraTick, TickGroup::LateCamera, TG_PostPhysics);
+	// Unreal updates the player cameras after TG_PostPhysics, so this is the last chance to move them
+	LateCameraTick.AddPrerequisite(this, PostPhysicsTick);
+	RegisterTickGroup(PostUpdateWorkTick, TickGroup::PostUpdateWork, TG_PostUpdateWork);
+
 	FRustPluginModule& Module = GetRustModule();
 	if (Module.Plugin.IsLoaded() && Module.Plugin.Rust.begin_play() == ResultCode::Panic)
 	{
@@ -132,4 +298,10 @@ void ARustGameModeBase::StartPlay()
 		Actor->OnDestroyed.AddUniqueDynamic(this, &ARustGameModeBase::OnActorDestroyed);
 		OnActorSpawnedHandler(Actor);
 	}
+	// Rust starts with a new world, so it needs to hear about the levels that are already loaded
+	StreamingLevelStates.Empty();
+	if (Module.Plugin.IsLoaded())
+	{
+		UpdateStreamingLevels(false);
+	}
 }
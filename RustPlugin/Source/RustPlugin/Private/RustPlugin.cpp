@@ -159,6 +159,7 @@ void FPlugin::RetrieveReflectionData()
 		FRustReflection Reflection;
 		Reflection.Name = ToFString(TypeNameStr);
 		Reflection.IsEditorComponent = Rust.reflection_fns.is_editor_component(Id) == 1;
+		Reflection.IsEvent = Rust.reflection_fns.is_event(Id) == 1;
 
 		for (uint32_t Idx = 0; Idx < NumberOfFields; Idx++)
 		{
@@ -174,6 +175,37 @@ void FPlugin::RetrieveReflectionData()
 
 		ReflectionData.Types.Add(ToFGuid(Id), Reflection);
 	}
+
+	ReflectionData.Functions.Reset();
+
+	uint32_t NumberOfFunctions = 0;
+	Rust.function_fns.number_of_functions(&NumberOfFunctions);
+	for (uint32_t Idx = 0; Idx < NumberOfFunctions; Idx++)
+	{
+		Utf8Str FunctionNameStr;
+		check(Rust.function_fns.get_function_name(Idx, &FunctionNameStr));
+
+		FRustFunction Function;
+		Function.Name = ToFString(FunctionNameStr);
+
+		uint32_t NumberOfArguments = 0;
+		check(Rust.function_fns.get_function_number_of_arguments(Idx, &NumberOfArguments));
+		for (uint32_t ArgumentIdx = 0; ArgumentIdx < NumberOfArguments; ArgumentIdx++)
+		{
+			Utf8Str ArgumentName;
+			ReflectionType Type = ReflectionType::Bool;
+			check(Rust.function_fns.get_function_argument(Idx, ArgumentIdx, &ArgumentName, &Type));
+			Function.ArgumentNames.Add(ToFString(ArgumentName));
+			Function.ArgumentTypes.Add(Type);
+		}
+
+		uint32_t HasReturnValue = 0;
+		Function.ReturnType = ReflectionType::Bool;
+		check(Rust.function_fns.get_function_return_type(Idx, &HasReturnValue, &Function.ReturnType));
+		Function.HasReturnValue = HasReturnValue == 1;
+
+		ReflectionData.Functions.Add(Function.Name, Function);
+	}
 }
 
 void FRustPluginModule::StartupModule()
@@ -13,6 +13,13 @@
 #include "Widgets/Input/SVectorInputBox.h"
 #include "Widgets/Layout/SBox.h"
 #include "Widgets/Input//SButton.h"
+#include "Animation/AnimationAsset.h"
+#include "Engine/DataAsset.h"
+#include "Engine/StaticMesh.h"
+#include "Engine/Texture.h"
+#include "Materials/MaterialInterface.h"
+#include "Particles/ParticleSystem.h"
+#include "Sound/SoundBase.h"
 
 #define LOCTEXT_NAMESPACE "RustProperty"
 
@@ -45,6 +52,270 @@ void FRustProperty::Initialize(TSharedPtr<IPropertyHandle> Handle, ReflectionTyp
 	{
 		HandleTag->SetValue(ERustPropertyTag::Sound);
 	}
+	if (Type == ReflectionType::UStaticMesh)
+	{
+		HandleTag->SetValue(ERustPropertyTag::StaticMesh);
+	}
+	if (Type == ReflectionType::UMaterial)
+	{
+		HandleTag->SetValue(ERustPropertyTag::Material);
+	}
+	if (Type == ReflectionType::UTexture)
+	{
+		HandleTag->SetValue(ERustPropertyTag::Texture);
+	}
+	if (Type == ReflectionType::UParticleSystem)
+	{
+		HandleTag->SetValue(ERustPropertyTag::ParticleSystem);
+	}
+	if (Type == ReflectionType::UDataAsset)
+	{
+		HandleTag->SetValue(ERustPropertyTag::DataAsset);
+	}
+	if (Type == ReflectionType::UAnimationAsset)
+	{
+		HandleTag->SetValue(ERustPropertyTag::AnimationAsset);
+	}
+	if (Type == ReflectionType::SoftObjectPath)
+	{
+		HandleTag->SetValue(ERustPropertyTag::SoftObject);
+	}
+	if (Type == ReflectionType::GameplayTags)
+	{
+		HandleTag->SetValue(ERustPropertyTag::GameplayTags);
+	}
+}
+
+UObject* FRustProperty::GetUObject(UObjectType Type) const
+{
+	switch (Type)
+	{
+	case UObjectType::UClass:
+		return Tag == ERustPropertyTag::Class ? Class.Get() : nullptr;
+	case UObjectType::USound:
+		return Tag == ERustPropertyTag::Sound ? Sound.Get() : nullptr;
+	case UObjectType::UStaticMesh:
+		return Tag == ERustPropertyTag::StaticMesh ? StaticMesh.Get() : nullptr;
+	case UObjectType::UMaterial:
+		return Tag == ERustPropertyTag::Material ? Material.Get() : nullptr;
+	case UObjectType::UTexture:
+		return Tag == ERustPropertyTag::Texture ? Texture.Get() : nullptr;
+	case UObjectType::UParticleSystem:
+		return Tag == ERustPropertyTag::ParticleSystem ? ParticleSystem.Get() : nullptr;
+	case UObjectType::UDataAsset:
+		return Tag == ERustPropertyTag::DataAsset ? DataAsset.Get() : nullptr;
+	case UObjectType::UAnimationAsset:
+		return Tag == ERustPropertyTag::AnimationAsset ? AnimationAsset.Get() : nullptr;
+	default:
+		return nullptr;
+	}
+}
+
+void FRustProperty::SetUObject(UObjectType Type, UObject* Object)
+{
+	switch (Type)
+	{
+	case UObjectType::UClass:
+		Tag = ERustPropertyTag::Class;
+		Class = Cast<UClass>(Object);
+		break;
+	case UObjectType::USound:
+		Tag = ERustPropertyTag::Sound;
+		Sound = Cast<USoundBase>(Object);
+		break;
+	case UObjectType::UStaticMesh:
+		Tag = ERustPropertyTag::StaticMesh;
+		StaticMesh = Cast<UStaticMesh>(Object);
+		break;
+	case UObjectType::UMaterial:
+		Tag = ERustPropertyTag::Material;
+		Material = Cast<UMaterialInterface>(Object);
+		break;
+	case UObjectType::UTexture:
+		Tag = ERustPropertyTag::Texture;
+		Texture = Cast<UTexture>(Object);
+		break;
+	case UObjectType::UParticleSystem:
+		Tag = ERustPropertyTag::ParticleSystem;
+		ParticleSystem = Cast<UParticleSystem>(Object);
+		break;
+	case UObjectType::UDataAsset:
+		Tag = ERustPropertyTag::DataAsset;
+		DataAsset = Cast<UDataAsset>(Object);
+		break;
+	case UObjectType::UAnimationAsset:
+		Tag = ERustPropertyTag::AnimationAsset;
+		AnimationAsset = Cast<UAnimationAsset>(Object);
+		break;
+	}
+}
+
+bool FRustProperty::GetUObjectType(UObjectType& Out) const
+{
+	switch (Tag)
+	{
+	case ERustPropertyTag::Class:
+		Out = UObjectType::UClass;
+		return true;
+	case ERustPropertyTag::Sound:
+		Out = UObjectType::USound;
+		return true;
+	case ERustPropertyTag::StaticMesh:
+		Out = UObjectType::UStaticMesh;
+		return true;
+	case ERustPropertyTag::Material:
+		Out = UObjectType::UMaterial;
+		return true;
+	case ERustPropertyTag::Texture:
+		Out = UObjectType::UTexture;
+		return true;
+	case ERustPropertyTag::ParticleSystem:
+		Out = UObjectType::UParticleSystem;
+		return true;
+	case ERustPropertyTag::DataAsset:
+		Out = UObjectType::UDataAsset;
+		return true;
+	case ERustPropertyTag::AnimationAsset:
+		Out = UObjectType::UAnimationAsset;
+		return true;
+	default:
+		return false;
+	}
+}
+
+FRustProperty FRustProperty::Default(ReflectionType Type)
+{
+	FRustProperty Property = {};
+	switch (Type)
+	{
+	case ReflectionType::Float:
+		Property.Tag = ERustPropertyTag::Float;
+		break;
+	case ReflectionType::Vector3:
+		Property.Tag = ERustPropertyTag::Vector;
+		break;
+	case ReflectionType::Bool:
+		Property.Tag = ERustPropertyTag::Bool;
+		break;
+	case ReflectionType::Quaternion:
+		Property.Tag = ERustPropertyTag::Quat;
+		break;
+	case ReflectionType::UClass:
+		Property.SetUObject(UObjectType::UClass, nullptr);
+		break;
+	case ReflectionType::USound:
+		Property.SetUObject(UObjectType::USound, nullptr);
+		break;
+	case ReflectionType::UStaticMesh:
+		Property.SetUObject(UObjectType::UStaticMesh, nullptr);
+		break;
+	case ReflectionType::UMaterial:
+		Property.SetUObject(UObjectType::UMaterial, nullptr);
+		break;
+	case ReflectionType::UTexture:
+		Property.SetUObject(UObjectType::UTexture, nullptr);
+		break;
+	case ReflectionType::UParticleSystem:
+		Property.SetUObject(UObjectType::UParticleSystem, nullptr);
+		break;
+	case ReflectionType::UDataAsset:
+		Property.SetUObject(UObjectType::UDataAsset, nullptr);
+		break;
+	case ReflectionType::UAnimationAsset:
+		Property.SetUObject(UObjectType::UAnimationAsset, nullptr);
+		break;
+	case ReflectionType::SoftObjectPath:
+		Property.Tag = ERustPropertyTag::SoftObject;
+		break;
+	case ReflectionType::GameplayTags:
+		Property.Tag = ERustPropertyTag::GameplayTags;
+		break;
+	default:
+		break;
+	}
+	return Property;
+}
+
+FRustProperty FRustProperty::FromReflectValue(const ReflectValue& Value)
+{
+	FRustProperty Property = Default(Value.ty);
+	switch (Value.ty)
+	{
+	case ReflectionType::Float:
+		Property.Float = Value.data.float32;
+		break;
+	case ReflectionType::Vector3:
+		Property.Vector = ToFVector(Value.data.vector3);
+		break;
+	case ReflectionType::Bool:
+		Property.Bool = Value.data.boolean != 0;
+		break;
+	case ReflectionType::Quaternion:
+		Property.Rotation = ToFQuat(Value.data.quaternion).Rotator();
+		break;
+	case ReflectionType::SoftObjectPath:
+		Property.SoftObject = FSoftObjectPath(ToFString(Value.data.soft_object_path.path));
+		break;
+	case ReflectionType::GameplayTags:
+		Property.GameplayTags = ToFGameplayTagContainer(Value.data.gameplay_tags);
+		break;
+	default:
+		{
+			UObjectType ObjectType;
+			if (Property.GetUObjectType(ObjectType))
+			{
+				Property.SetUObject(ObjectType, (UObject*)Value.data.uobject);
+			}
+		}
+		break;
+	}
+	return Property;
+}
+
+ReflectValue FRustProperty::ToReflectValue(ReflectionType Type, TArray<TUniquePtr<FTCHARToUTF8>>& Utf8Strings) const
+{
+	ReflectValue Value = {};
+	Value.ty = Type;
+	switch (Type)
+	{
+	case ReflectionType::Float:
+		Value.data.float32 = Float;
+		break;
+	case ReflectionType::Vector3:
+		Value.data.vector3 = ToVector3(Vector);
+		break;
+	case ReflectionType::Bool:
+		Value.data.boolean = Bool ? 1 : 0;
+		break;
+	case ReflectionType::Quaternion:
+		Value.data.quaternion = ToQuaternion(Rotation.Quaternion());
+		break;
+	case ReflectionType::SoftObjectPath:
+		{
+			// Unreal doesn't know the asset type of an unloaded soft reference, Rust only uses the path
+			FTCHARToUTF8* Path = Utf8Strings.Add_GetRef(MakeUnique<FTCHARToUTF8>(*SoftObject.ToString())).Get();
+			Value.data.soft_object_path.path.ptr = Path->Get();
+			Value.data.soft_object_path.path.len = Path->Length();
+		}
+		break;
+	case ReflectionType::GameplayTags:
+		{
+			FTCHARToUTF8* Tags = Utf8Strings.Add_GetRef(MakeUnique<FTCHARToUTF8>(*ToTagString(GameplayTags))).Get();
+			Value.data.gameplay_tags.ptr = Tags->Get();
+			Value.data.gameplay_tags.len = Tags->Length();
+		}
+		break;
+	default:
+		{
+			UObjectType ObjectType;
+			if (GetUObjectType(ObjectType))
+			{
+				Value.data.uobject = (UObjectOpague*)GetUObject(ObjectType);
+			}
+		}
+		break;
+	}
+	return Value;
 }
 
 void FDynamicRustComponent::Reload(TSharedPtr<IPropertyHandle> Handle, FGuid Guid)
@@ -207,6 +478,62 @@ void FDynamicRustComponent::Render(TSharedRef<IPropertyHandle> MapHandle, IDetai
 				ComponentGroup.AddPropertyRow(SoundProperty.ToSharedRef()).DisplayName(
 					FText::FromString(FieldPropertyName));
 			}
+			if (Tag == ERustPropertyTag::StaticMesh)
+			{
+				auto StaticMeshProperty = RustPropertyEntry->GetChildHandle(
+					GET_MEMBER_NAME_CHECKED(FRustProperty, StaticMesh));
+				ComponentGroup.AddPropertyRow(StaticMeshProperty.ToSharedRef()).DisplayName(
+					FText::FromString(FieldPropertyName));
+			}
+			if (Tag == ERustPropertyTag::Material)
+			{
+				auto MaterialProperty = RustPropertyEntry->GetChildHandle(
+					GET_MEMBER_NAME_CHECKED(FRustProperty, Material));
+				ComponentGroup.AddPropertyRow(MaterialProperty.ToSharedRef()).DisplayName(
+					FText::FromString(FieldPropertyName));
+			}
+			if (Tag == ERustPropertyTag::Texture)
+			{
+				auto TextureProperty = RustPropertyEntry->GetChildHandle(
+					GET_MEMBER_NAME_CHECKED(FRustProperty, Texture));
+				ComponentGroup.AddPropertyRow(TextureProperty.ToSharedRef()).DisplayName(
+					FText::FromString(FieldPropertyName));
+			}
+			if (Tag == ERustPropertyTag::ParticleSystem)
+			{
+				auto ParticleSystemProperty = RustPropertyEntry->GetChildHandle(
+					GET_MEMBER_NAME_CHECKED(FRustProperty, ParticleSystem));
+				ComponentGroup.AddPropertyRow(ParticleSystemProperty.ToSharedRef()).DisplayName(
+					FText::FromString(FieldPropertyName));
+			}
+			if (Tag == ERustPropertyTag::DataAsset)
+			{
+				auto DataAssetProperty = RustPropertyEntry->GetChildHandle(
+					GET_MEMBER_NAME_CHECKED(FRustProperty, DataAsset));
+				ComponentGroup.AddPropertyRow(DataAssetProperty.ToSharedRef()).DisplayName(
+					FText::FromString(FieldPropertyName));
+			}
+			if (Tag == ERustPropertyTag::AnimationAsset)
+			{
+				auto AnimationAssetProperty = RustPropertyEntry->GetChildHandle(
+					GET_MEMBER_NAME_CHECKED(FRustProperty, AnimationAsset));
+				ComponentGroup.AddPropertyRow(AnimationAssetProperty.ToSharedRef()).DisplayName(
+					FText::FromString(FieldPropertyName));
+			}
+			if (Tag == ERustPropertyTag::SoftObject)
+			{
+				auto SoftObjectProperty = RustPropertyEntry->GetChildHandle(
+					GET_MEMBER_NAME_CHECKED(FRustProperty, SoftObject));
+				ComponentGroup.AddPropertyRow(SoftObjectProperty.ToSharedRef()).DisplayName(
+					FText::FromString(FieldPropertyName));
+			}
+			if (Tag == ERustPropertyTag::GameplayTags)
+			{
+				auto GameplayTagsProperty = RustPropertyEntry->GetChildHandle(
+					GET_MEMBER_NAME_CHECKED(FRustProperty, GameplayTags));
+				ComponentGroup.AddPropertyRow(GameplayTagsProperty.ToSharedRef()).DisplayName(
+					FText::FromString(FieldPropertyName));
+			}
 		}
 	}
 }
@@ -0,0 +1,37 @@
+#include "RustState.h"
+
+#include "RustPlugin.h"
+#include "RustUtils.h"
+
+bool URustStateLibrary::SetRustState(FString Machine, FString State)
+{
+	auto& Module = GetRustModule();
+	if (!Module.Plugin.IsLoaded())
+		return false;
+
+	FTCHARToUTF8 MachineUtf8(*Machine);
+	FTCHARToUTF8 StateUtf8(*State);
+	Utf8Str MachineStr;
+	MachineStr.ptr = MachineUtf8.Get();
+	MachineStr.len = MachineUtf8.Length();
+	Utf8Str StateStr;
+	StateStr.ptr = StateUtf8.Get();
+	StateStr.len = StateUtf8.Length();
+	return Module.Plugin.Rust.state_fns.set_state(MachineStr, StateStr) == 1;
+}
+
+FString URustStateLibrary::GetRustState(FString Machine)
+{
+	auto& Module = GetRustModule();
+	if (!Module.Plugin.IsLoaded())
+		return FString();
+
+	FTCHARToUTF8 MachineUtf8(*Machine);
+	Utf8Str MachineStr;
+	MachineStr.ptr = MachineUtf8.Get();
+	MachineStr.len = MachineUtf8.Length();
+	Utf8Str Out;
+	if (Module.Plugin.Rust.state_fns.get_state(MachineStr, &Out) == 0)
+		return FString();
+	return ToFString(Out);
+}
@@ -3,11 +3,31 @@
 #include "RustPlugin.h"
 #include "EngineUtils.h"
 #include "RustActor.h"
+#include "Animation/AnimationAsset.h"
+#include "Engine/DataAsset.h"
+#include "Engine/StaticMesh.h"
+#include "Engine/Texture.h"
+#include "Materials/MaterialInterface.h"
+#include "Particles/ParticleSystem.h"
+#include "Sound/SoundBase.h"
+#include "PhysicalMaterials/PhysicalMaterial.h"
+#include "GameplayTagContainer.h"
+#include "Misc/PackageName.h"
 
 UnrealBindings CreateBindings()
 {
 	SoundFns sound_fns;
 	sound_fns.play_sound_at_location = PlaySoundAtLocation;
+
+	AssetFns asset_fns;
+	asset_fns.load_asset = &LoadAsset;
+	asset_fns.load_asset_async = &LoadAssetAsync;
+	asset_fns.unload_asset = &UnloadAsset;
+
+	ObjectFns object_fns;
+	object_fns.call_function = &CallUFunction;
+	object_fns.get_property = &GetUProperty;
+	object_fns.set_property = &SetUProperty;
 	
 	EditorComponentFns editor_component_fns;
 	editor_component_fns.get_editor_component_bool = &GetEditorComponentBool;
@@ -16,6 +36,8 @@ UnrealBindings CreateBindings()
 	editor_component_fns.get_editor_component_vector = &GetEditorComponentVector;
 	editor_component_fns.get_editor_component_uobject = &GetEditorComponentUObject;
 	editor_component_fns.get_editor_components = &GetEditorComponentUuids;
+	editor_component_fns.get_editor_component_soft_object_path = &GetEditorComponentSoftObjectPath;
+	editor_component_fns.get_editor_component_gameplay_tags = &GetEditorComponentGameplayTags;
 
 	PhysicsFns physics_fns = {};
 	 physics_fns.add_force = &AddForce;
@@ -39,15 +61,59 @@ UnrealBindings CreateBindings()
 	actor_fns.get_class = &GetClass;
 	actor_fns.set_owner = &SetOwner;
 	actor_fns.get_actor_name = &GetActorName;
+	actor_fns.get_actor_path = &GetActorPath;
 	actor_fns.is_moveable = &IsMoveable;
 	actor_fns.register_actor_on_overlap = &RegisterActorOnOverlap;
 	actor_fns.register_actor_on_hit = &RegisterActorOnHit;
+	actor_fns.unregister_actor_on_overlap = &UnregisterActorOnOverlap;
+	actor_fns.unregister_actor_on_hit = &UnregisterActorOnHit;
 	actor_fns.get_root_component = &GetRootComponent;
 	actor_fns.destroy_actor = &DestroyActor;
+	actor_fns.apply_damage = &ApplyDamage;
+	actor_fns.get_owned_gameplay_tags = &GetOwnedGameplayTags;
 	
+	VisualLogFns visual_log_fns;
+	visual_log_fns.is_recording = &VisualLogIsRecording;
+	visual_log_fns.text = &VisualLogText;
+	visual_log_fns.segment = &VisualLogSegment;
+	visual_log_fns.arrow = &VisualLogArrow;
+	visual_log_fns.location = &VisualLogLocation;
+	visual_log_fns.capsule = &VisualLogCapsule;
+	visual_log_fns.aligned_box = &VisualLogBox;
+	visual_log_fns.oriented_box = &VisualLogOrientedBox;
+	visual_log_fns.cone = &VisualLogCone;
+	visual_log_fns.polyline = &VisualLogPolyline;
+	visual_log_fns.histogram = &VisualLogHistogram;
+	visual_log_fns.status = &VisualLogStatus;
+
+	TimeFns time_fns;
+	time_fns.set_global_time_dilation = &SetGlobalTimeDilation;
+	time_fns.set_game_paused = &SetGamePaused;
+	time_fns.set_custom_time_dilation = &SetCustomTimeDilation;
+
+	LevelFns level_fns;
+	level_fns.get_actor_level = &GetActorLevel;
+	level_fns.get_level_name = &GetLevelName;
+	level_fns.is_persistent_level = &IsPersistentLevel;
+	level_fns.load_stream_level = &LoadStreamLevel;
+	level_fns.unload_stream_level = &UnloadStreamLevel;
+	level_fns.set_stream_level_visible = &SetStreamLevelVisible;
+	level_fns.open_level = &OpenLevel;
+
+	SaveGameFns save_game_fns;
+	save_game_fns.save_data_to_slot = &SaveDataToSlot;
+	save_game_fns.load_data_from_slot = &LoadDataFromSlot;
+	save_game_fns.does_save_game_exist = &DoesSaveGameExist;
+	save_game_fns.delete_save_game_in_slot = &DeleteSaveGameInSlot;
+
 	UnrealBindings b = {};
 	b.actor_fns = actor_fns;
 	b.sound_fns = sound_fns;
+	b.asset_fns = asset_fns;
+	b.object_fns = object_fns;
+	b.time_fns = time_fns;
+	b.level_fns = level_fns;
+	b.save_game_fns = save_game_fns;
 	b.physics_fns = physics_fns;
 	b.editor_component_fns = editor_component_fns;
 	b.log = &Log;
@@ -56,9 +122,9 @@ UnrealBindings CreateBindings()
 	b.get_axis_value = &GetAxisValue;
 	b.spawn_actor = &SpawnActor;
 	b.get_mouse_delta = &GetMouseDelta;
-	b.visual_log_segment = &VisualLogSegment;
-	b.visual_log_capsule = &VisualLogCapsule;
-	b.visual_log_location = &VisualLogLocation;
+	b.visual_log_fns = visual_log_fns;
+	b.debug_draw = &DebugDraw;
+	b.send_blueprint_event = &SendBlueprintEvent;
 	return b;
 }
 
@@ -126,6 +192,27 @@ FColor ToFColor(Color c)
 	return FColor(c.r, c.g, c.b, c.a);
 }
 
+ELogVerbosity::Type ToELogVerbosity(LogVerbosity Verbosity)
+{
+	switch (Verbosity)
+	{
+	case LogVerbosity::Error:
+		return ELogVerbosity::Error;
+	case LogVerbosity::Warning:
+		return ELogVerbosity::Warning;
+	case LogVerbosity::Display:
+		return ELogVerbosity::Display;
+	case LogVerbosity::Log:
+		return ELogVerbosity::Log;
+	case LogVerbosity::Verbose:
+		return ELogVerbosity::Verbose;
+	case LogVerbosity::VeryVerbose:
+		return ELogVerbosity::VeryVerbose;
+	default:
+		return ELogVerbosity::Log;
+	}
+}
+
 FCollisionShape ToFCollisionShape(CollisionShape Shape)
 {
 	if (Shape.ty == CollisionShapeType::Box)
@@ -153,6 +240,121 @@ FCollisionShape ToFCollisionShape(CollisionShape Shape)
 	abort();
 }
 
+UClass* ToUClass(UObjectType Type)
+{
+	switch (Type)
+	{
+	case UObjectType::UClass:
+		return UClass::StaticClass();
+	case UObjectType::USound:
+		return USoundBase::StaticClass();
+	case UObjectType::UStaticMesh:
+		return UStaticMesh::StaticClass();
+	case UObjectType::UMaterial:
+		return UMaterialInterface::StaticClass();
+	case UObjectType::UTexture:
+		return UTexture::StaticClass();
+	case UObjectType::UParticleSystem:
+		return UParticleSystem::StaticClass();
+	case UObjectType::UDataAsset:
+		return UDataAsset::StaticClass();
+	case UObjectType::UAnimationAsset:
+		return UAnimationAsset::StaticClass();
+	default:
+		return UObject::StaticClass();
+	}
+}
+
+HitResult ToHitResult(const FHitResult& Hit)
+{
+	HitResult Result;
+	Result.actor = (AActorOpaque*)Hit.GetActor();
+	Result.primtive = (UPrimtiveOpaque*)Hit.GetComponent();
+	Result.distance = Hit.Distance;
+	Result.location = ToVector3(Hit.Location);
+	Result.normal = ToVector3(Hit.Normal);
+	Result.impact_location = ToVector3(Hit.ImpactPoint);
+	Result.impact_normal = ToVector3(Hit.ImpactNormal);
+	Result.pentration_depth = Hit.PenetrationDepth;
+	Result.start_penetrating = Hit.bStartPenetrating;
+	// Queries need `bReturnPhysicalMaterial`, otherwise this is always null
+	UPhysicalMaterial* PhysicalMaterial = Hit.PhysMaterial.Get();
+	Result.physical_material = (UObjectOpague*)PhysicalMaterial;
+	Result.surface_type = (uint32_t)UPhysicalMaterial::DetermineSurfaceType(PhysicalMaterial);
+	return Result;
+}
+
+bool ToUObjectType(const UClass* Class, UObjectType& Out)
+{
+	const UObjectType Types[] = {
+		UObjectType::UClass,
+		UObjectType::USound,
+		UObjectType::UStaticMesh,
+		UObjectType::UMaterial,
+		UObjectType::UTexture,
+		UObjectType::UParticleSystem,
+		UObjectType::UDataAsset,
+		UObjectType::UAnimationAsset,
+	};
+	for (UObjectType Type : Types)
+	{
+		if (Class->IsChildOf(ToUClass(Type)))
+		{
+			Out = Type;
+			return true;
+		}
+	}
+	return false;
+}
+
+ReflectionType ToReflectionType(UObjectType Type)
+{
+	switch (Type)
+	{
+	case UObjectType::UClass:
+		return ReflectionType::UClass;
+	case UObjectType::USound:
+		return ReflectionType::USound;
+	case UObjectType::UStaticMesh:
+		return ReflectionType::UStaticMesh;
+	case UObjectType::UMaterial:
+		return ReflectionType::UMaterial;
+	case UObjectType::UTexture:
+		return ReflectionType::UTexture;
+	case UObjectType::UParticleSystem:
+		return ReflectionType::UParticleSystem;
+	case UObjectType::UDataAsset:
+		return ReflectionType::UDataAsset;
+	case UObjectType::UAnimationAsset:
+		return ReflectionType::UAnimationAsset;
+	default:
+		return ReflectionType::Composite;
+	}
+}
+
+EndPlayReason ToEndPlayReason(EEndPlayReason::Type Reason)
+{
+	switch (Reason)
+	{
+	case EEndPlayReason::Destroyed:
+		return EndPlayReason::Destroyed;
+	case EEndPlayReason::LevelTransition:
+		return EndPlayReason::LevelTransition;
+	case EEndPlayReason::EndPlayInEditor:
+		return EndPlayReason::EndPlayInEditor;
+	case EEndPlayReason::RemovedFromWorld:
+		return EndPlayReason::RemovedFromWorld;
+	case EEndPlayReason::Quit:
+	default:
+		return EndPlayReason::Quit;
+	}
+}
+
+FString ToLevelName(const FString& PackageName)
+{
+	return UWorld::RemovePIEPrefix(FPackageName::GetShortName(PackageName));
+}
+
 FString ToFString(Utf8Str Str)
 {
 	if(Str.len == 0)
@@ -161,6 +363,32 @@ FString ToFString(Utf8Str Str)
 	return FString(Str.len, UTF8_TO_TCHAR(Str.ptr));
 }
 
+FGameplayTagContainer ToFGameplayTagContainer(Utf8Str Tags)
+{
+	FGameplayTagContainer Container;
+	TArray<FString> Names;
+	ToFString(Tags).ParseIntoArray(Names, TEXT(","));
+	for (const FString& Name : Names)
+	{
+		FGameplayTag Tag = FGameplayTag::RequestGameplayTag(FName(*Name.TrimStartAndEnd()), false);
+		if (Tag.IsValid())
+		{
+			Container.AddTag(Tag);
+		}
+	}
+	return Container;
+}
+
+FString ToTagString(const FGameplayTagContainer& Tags)
+{
+	TArray<FString> Names;
+	for (const FGameplayTag& Tag : Tags)
+	{
+		Names.Add(Tag.ToString());
+	}
+	return FString::Join(Names, TEXT(","));
+}
+
 FRustProperty* GetRustProperty(const AActorOpaque* actor, Uuid uuid, Utf8Str field)
 {
 	AActor* Actor = ToAActor(actor);
@@ -21,6 +21,10 @@ void SRustDropdownList::Construct(const FArguments& InArgs)
 
 	for (auto& Elem : GetRustModule().Plugin.ReflectionData.Types)
 	{
+		if (Elem.Value.IsEvent)
+		{
+			continue;
+		}
 		if (InArgs._OnlyShowEditorComponents)
 		{
 			if (!Elem.Value.IsEditorComponent)
@@ -27,12 +27,51 @@ enum class CollisionShapeType : uint32_t {
   Sphere,
 };
 
+enum class DebugDrawType : uint32_t {
+  Line,
+  Sphere,
+  Capsule,
+  Box,
+  Arrow,
+  Text,
+  CoordinateFrame,
+};
+
+/// Mirrors `EEndPlayReason`
+enum class EndPlayReason : uint32_t {
+  /// The game mode was destroyed explicitly
+  Destroyed = 0,
+  /// The world is being unloaded for a level transition
+  LevelTransition = 1,
+  /// Play in editor was stopped
+  EndPlayInEditor = 2,
+  /// The level of the game mode was removed from the world
+  RemovedFromWorld = 3,
+  /// The application is shutting down
+  Quit = 4,
+};
+
 enum class EventType : uint32_t {
   ActorSpawned = 0,
   ActorBeginOverlap = 1,
   ActorEndOverlap = 2,
   ActorOnHit = 3,
   ActorDestroy = 4,
+  AssetLoaded = 5,
+  LevelLoaded = 6,
+  LevelUnloaded = 7,
+  LevelShown = 8,
+  LevelHidden = 9,
+};
+
+/// Converted to `ELogVerbosity` with `ToELogVerbosity`, the values differ from unreal's
+enum class LogVerbosity : uint8_t {
+  Error = 0,
+  Warning = 1,
+  Display = 2,
+  Log = 3,
+  Verbose = 4,
+  VeryVerbose = 5,
 };
 
 enum class ReflectionType : uint32_t {
@@ -42,6 +81,14 @@ enum class ReflectionType : uint32_t {
   Quaternion,
   UClass,
   USound,
+  UStaticMesh,
+  UMaterial,
+  UTexture,
+  UParticleSystem,
+  UDataAsset,
+  UAnimationAsset,
+  SoftObjectPath,
+  GameplayTags,
   Composite,
 };
 
@@ -50,12 +97,49 @@ enum class ResultCode : uint8_t {
   Panic = 1,
 };
 
+/// The unreal tick groups that run Rust stages after `tick`. `tick` itself runs in
+/// `TG_PrePhysics`, from the tick of the game mode.
+enum class TickGroup : uint32_t {
+  /// `TG_DuringPhysics`, runs while the physics simulation is in flight
+  DuringPhysics = 0,
+  /// `TG_PostPhysics`, runs after the physics simulation finished
+  PostPhysics = 1,
+  /// Also `TG_PostPhysics`, but after `PostPhysics` and right before the player camera managers
+  /// are updated
+  LateCamera = 2,
+  /// `TG_PostUpdateWork`, runs after the camera update
+  PostUpdateWork = 3,
+};
+
 enum class UObjectType : uint32_t {
   UClass,
+  USound,
+  UStaticMesh,
+  UMaterial,
+  UTexture,
+  UParticleSystem,
+  UDataAsset,
+  UAnimationAsset,
 };
 
 using AActorOpaque = void;
 
+struct Utf8Str {
+  const char *ptr;
+  uintptr_t len;
+};
+
+struct LogRecord {
+  /// The unreal log category, which is derived from the `log` target
+  Utf8Str category;
+  LogVerbosity verbosity;
+  Utf8Str message;
+  /// Empty if unknown
+  Utf8Str file;
+  /// 0 if unknown
+  uint32_t line;
+};
+
 struct Vector3 {
   float x;
   float y;
@@ -69,18 +153,6 @@ struct Quaternion {
   float w;
 };
 
-struct Color {
-  uint8_t r;
-  uint8_t g;
-  uint8_t b;
-  uint8_t a;
-};
-
-struct Utf8Str {
-  const char *ptr;
-  uintptr_t len;
-};
-
 struct Uuid {
   uint32_t a;
   uint32_t b;
@@ -90,6 +162,36 @@ struct Uuid {
 
 using UObjectOpague = void;
 
+struct SoftObjectPathValue {
+  UObjectType ty;
+  Utf8Str path;
+};
+
+union ReflectValueData {
+  float float32;
+  Vector3 vector3;
+  uint32_t boolean;
+  Quaternion quaternion;
+  UObjectOpague *uobject;
+  SoftObjectPathValue soft_object_path;
+  /// Tag names separated by `,`
+  Utf8Str gameplay_tags;
+};
+
+/// A single reflected value that is passed by value across the ffi boundary, eg the fields of an
+/// event. `ty` decides which field of `data` is valid. Strings are borrowed from the sender and
+/// are only valid for the duration of the call.
+struct ReflectValue {
+  ReflectionType ty;
+  ReflectValueData data;
+};
+
+struct RustAlloc {
+  uint8_t *ptr;
+  uintptr_t size;
+  uintptr_t align;
+};
+
 struct Entity {
   uint64_t id;
 };
@@ -101,12 +203,34 @@ struct ActorComponentPtr {
 
 using UClassOpague = void;
 
-struct RustAlloc {
-  uint8_t *ptr;
-  uintptr_t size;
-  uintptr_t align;
+struct Color {
+  uint8_t r;
+  uint8_t g;
+  uint8_t b;
+  uint8_t a;
+};
+
+/// A single debug draw call. Fields that the `ty` doesn't use are ignored.
+struct DebugDrawCommand {
+  DebugDrawType ty;
+  /// Start of lines and arrows, center of every other shape
+  Vector3 position;
+  /// End of lines and arrows, half extent of boxes
+  Vector3 end;
+  Quaternion rotation;
+  /// Radius of spheres and capsules, head size of arrows and axis length of coordinate frames
+  float size;
+  float half_height;
+  float thickness;
+  Color color;
+  /// In seconds, anything <= 0 is only drawn for the current frame
+  float duration;
+  uint32_t depth_test;
+  Utf8Str text;
 };
 
+using ULevelOpaque = void;
+
 using UPrimtiveOpaque = void;
 
 struct LineTraceParams {
@@ -124,6 +248,10 @@ struct HitResult {
   Vector3 impact_location;
   float pentration_depth;
   uint32_t start_penetrating;
+  /// Null if the surface has no physical material
+  UObjectOpague *physical_material;
+  /// Mirrors `EPhysicalSurface`
+  uint32_t surface_type;
 };
 
 struct CollisionBox {
@@ -164,6 +292,21 @@ struct SoundSettings {
   float pitch;
 };
 
+/// Shared data of every visual log entry
+struct VisualLogEntry {
+  Utf8Str category;
+  LogVerbosity verbosity;
+  const AActorOpaque *owner;
+  Color color;
+  /// Text that is displayed next to the shape, can be empty
+  Utf8Str label;
+};
+
+struct VisualLogStatusEntry {
+  Utf8Str key;
+  Utf8Str value;
+};
+
 using GetSpatialDataFn = void(*)(const AActorOpaque *actor, Vector3 *position, Quaternion *rotation, Vector3 *scale);
 
 using SetSpatialDataFn = void(*)(AActorOpaque *actor, Vector3 position, Quaternion rotation, Vector3 scale);
@@ -176,6 +319,10 @@ using RegisterActorOnOverlapFn = void(*)(AActorOpaque *actor);
 
 using RegisterActorOnHitFn = void(*)(AActorOpaque *actor);
 
+using UnregisterActorOnOverlapFn = void(*)(AActorOpaque *actor);
+
+using UnregisterActorOnHitFn = void(*)(AActorOpaque *actor);
+
 using GetRootComponentFn = void(*)(const AActorOpaque *actor, ActorComponentPtr *data);
 
 using GetRegisteredClassesFn = void(*)(UClassOpague **classes, uintptr_t *len);
@@ -186,12 +333,24 @@ using SetViewTargetFn = void(*)(const AActorOpaque *actor);
 
 using GetActorNameFn = void(*)(const AActorOpaque *actor, RustAlloc *data);
 
+/// Writes the object path of the actor without the play in editor prefix. Actors that are placed
+/// in a level keep their path between sessions.
+using GetActorPathFn = void(*)(const AActorOpaque *actor, RustAlloc *data);
+
 using SetOwnerFn = void(*)(AActorOpaque *actor, const AActorOpaque *new_owner);
 
 using IsMoveableFn = uint32_t(*)(const AActorOpaque *actor);
 
 using DestroyActorFn = void(*)(const AActorOpaque *actor);
 
+/// Calls `UGameplayStatics::ApplyDamage` and returns the damage that the actor actually took.
+/// `instigator` can be null.
+using ApplyDamageFn = float(*)(AActorOpaque *actor, float damage, AActorOpaque *instigator);
+
+/// Writes the tags of actors that implement `IGameplayTagAssetInterface` separated by `,` into
+/// `data`. Returns 0 if the actor doesn't implement the interface.
+using GetOwnedGameplayTagsFn = uint32_t(*)(const AActorOpaque *actor, RustAlloc *data);
+
 struct ActorFns {
   GetSpatialDataFn get_spatial_data;
   SetSpatialDataFn set_spatial_data;
@@ -199,14 +358,19 @@ struct ActorFns {
   GetActorComponentsFn get_actor_components;
   RegisterActorOnOverlapFn register_actor_on_overlap;
   RegisterActorOnHitFn register_actor_on_hit;
+  UnregisterActorOnOverlapFn unregister_actor_on_overlap;
+  UnregisterActorOnHitFn unregister_actor_on_hit;
   GetRootComponentFn get_root_component;
   GetRegisteredClassesFn get_registered_classes;
   GetClassFn get_class;
   SetViewTargetFn set_view_target;
   GetActorNameFn get_actor_name;
+  GetActorPathFn get_actor_path;
   SetOwnerFn set_owner;
   IsMoveableFn is_moveable;
   DestroyActorFn destroy_actor;
+  ApplyDamageFn apply_damage;
+  GetOwnedGameplayTagsFn get_owned_gameplay_tags;
 };
 
 using GetVelocityFn = Vector3(*)(const UPrimtiveOpaque *primitive);
@@ -227,7 +391,8 @@ using SweepFn = uint32_t(*)(Vector3 start, Vector3 end, Quaternion rotation, Lin
 
 using SweepMultiFn = uint32_t(*)(Vector3 start, Vector3 end, Quaternion rotation, LineTraceParams params, CollisionShape collision_shape, uintptr_t max_results, HitResult *results);
 
-using OverlapMultiFn = uint32_t(*)(CollisionShape collision_shape, Vector3 position, Quaternion rotation, LineTraceParams params, uintptr_t max_results, OverlapResult **result);
+/// Writes up to `max_results` overlaps into `results` and returns the number of overlaps written.
+using OverlapMultiFn = uint32_t(*)(CollisionShape collision_shape, Vector3 position, Quaternion rotation, LineTraceParams params, uintptr_t max_results, OverlapResult *results);
 
 using GetCollisionShapeFn = uint32_t(*)(const UPrimtiveOpaque *primitive, CollisionShape *shape);
 
@@ -245,7 +410,7 @@ struct PhysicsFns {
   GetCollisionShapeFn get_collision_shape;
 };
 
-using LogFn = void(*)(const char*, int32_t);
+using LogFn = void(*)(const LogRecord *record);
 
 using IterateActorsFn = void(*)(AActorOpaque **array, uint64_t *len);
 
@@ -257,11 +422,49 @@ using SpawnActorFn = AActorOpaque*(*)(ActorClass actor_class, Vector3 position,
 
 using GetMouseDeltaFn = void(*)(float *x, float *y);
 
-using VisualLogSegmentFn = void(*)(const AActorOpaque *owner, Vector3 start, Vector3 end, Color color);
+using VisualLogIsRecordingFn = uint32_t(*)();
+
+using VisualLogTextFn = void(*)(const VisualLogEntry *entry);
+
+using VisualLogSegmentFn = void(*)(const VisualLogEntry *entry, Vector3 start, Vector3 end);
+
+using VisualLogArrowFn = void(*)(const VisualLogEntry *entry, Vector3 start, Vector3 end);
 
-using VisualLogCapsuleFn = void(*)(Utf8Str category, const AActorOpaque *owner, Vector3 position, Quaternion rotation, float half_height, float radius, Color color);
+using VisualLogLocationFn = void(*)(const VisualLogEntry *entry, Vector3 position, float radius);
 
-using VisualLogLocationFn = void(*)(Utf8Str category, const AActorOpaque *owner, Vector3 position, float radius, Color color);
+using VisualLogCapsuleFn = void(*)(const VisualLogEntry *entry, Vector3 position, Quaternion rotation, float half_height, float radius);
+
+using VisualLogBoxFn = void(*)(const VisualLogEntry *entry, Vector3 min, Vector3 max);
+
+using VisualLogOrientedBoxFn = void(*)(const VisualLogEntry *entry, Vector3 position, Quaternion rotation, Vector3 half_extent);
+
+using VisualLogConeFn = void(*)(const VisualLogEntry *entry, Vector3 origin, Vector3 direction, float length, float angle);
+
+using VisualLogPolylineFn = void(*)(const VisualLogEntry *entry, const Vector3 *points, uintptr_t len);
+
+using VisualLogHistogramFn = void(*)(const VisualLogEntry *entry, Utf8Str graph_name, Utf8Str data_name, float x, float y);
+
+using VisualLogStatusFn = void(*)(const VisualLogEntry *entry, const VisualLogStatusEntry *status, uintptr_t len);
+
+struct VisualLogFns {
+  VisualLogIsRecordingFn is_recording;
+  VisualLogTextFn text;
+  VisualLogSegmentFn segment;
+  VisualLogArrowFn arrow;
+  VisualLogLocationFn location;
+  VisualLogCapsuleFn capsule;
+  VisualLogBoxFn aligned_box;
+  VisualLogOrientedBoxFn oriented_box;
+  VisualLogConeFn cone;
+  VisualLogPolylineFn polyline;
+  VisualLogHistogramFn histogram;
+  VisualLogStatusFn status;
+};
+
+using DebugDrawFn = void(*)(const DebugDrawCommand *commands, uintptr_t len);
+
+/// Sends a user defined event from Rust to the blueprint event dispatchers.
+using SendBlueprintEventFn = void(*)(Uuid uuid, const ReflectValue *fields, uintptr_t len);
 
 using GetEditorComponentUuidsFn = uint32_t(*)(const AActorOpaque *actor, Uuid *data, uintptr_t *len);
 
@@ -275,6 +478,11 @@ using GetEditorComponentFloatFn = uint32_t(*)(const AActorOpaque *actor, Uuid uu
 
 using GetEditorComponentUObjectFn = uint32_t(*)(const AActorOpaque *actor, Uuid uuid, Utf8Str field, UObjectType ty, UObjectOpague **out);
 
+using GetEditorComponentSoftObjectPathFn = uint32_t(*)(const AActorOpaque *actor, Uuid uuid, Utf8Str field, RustAlloc *out);
+
+/// Writes the tag names separated by `,` into `out`
+using GetEditorComponentGameplayTagsFn = uint32_t(*)(const AActorOpaque *actor, Uuid uuid, Utf8Str field, RustAlloc *out);
+
 struct EditorComponentFns {
   GetEditorComponentUuidsFn get_editor_components;
   GetEditorComponentQuatFn get_editor_component_quat;
@@ -282,6 +490,8 @@ struct EditorComponentFns {
   GetEditorComponentBoolFn get_editor_component_bool;
   GetEditorComponentFloatFn get_editor_component_float;
   GetEditorComponentUObjectFn get_editor_component_uobject;
+  GetEditorComponentSoftObjectPathFn get_editor_component_soft_object_path;
+  GetEditorComponentGameplayTagsFn get_editor_component_gameplay_tags;
 };
 
 using PlaySoundAtLocationFn = void(*)(const USoundBaseOpague *sound, Vector3 location, Quaternion rotation, const SoundSettings *settings);
@@ -290,6 +500,99 @@ struct SoundFns {
   PlaySoundAtLocationFn play_sound_at_location;
 };
 
+using LoadAssetFn = UObjectOpague*(*)(Utf8Str path, UObjectType ty);
+
+using LoadAssetAsyncFn = uint32_t(*)(Utf8Str path, UObjectType ty, uint64_t handle);
+
+using UnloadAssetFn = void(*)(uint64_t handle);
+
+struct AssetFns {
+  LoadAssetFn load_asset;
+  LoadAssetAsyncFn load_asset_async;
+  UnloadAssetFn unload_asset;
+};
+
+/// Calls a `UFunction` on any `UObject`, including actors. Only the return value is written back,
+/// out parameters are ignored. `has_return_value` is only 0 for functions without a return value,
+/// a return value that can't be read fails the call. Strings in `out` stay valid until the next
+/// call.
+using CallUFunctionFn = uint32_t(*)(UObjectOpague *object, Utf8Str name, const ReflectValue *arguments, uintptr_t len, uint32_t *has_return_value, ReflectValue *out);
+
+/// Strings in `out` stay valid until the next call.
+using GetUPropertyFn = uint32_t(*)(UObjectOpague *object, Utf8Str name, ReflectValue *out);
+
+using SetUPropertyFn = uint32_t(*)(UObjectOpague *object, Utf8Str name, const ReflectValue *value);
+
+struct ObjectFns {
+  CallUFunctionFn call_function;
+  GetUPropertyFn get_property;
+  SetUPropertyFn set_property;
+};
+
+using SetGlobalTimeDilationFn = void(*)(float dilation);
+
+/// Returns 0 if the game could not be paused, eg because a pause delegate refused it
+using SetGamePausedFn = uint32_t(*)(uint32_t paused);
+
+/// Sets `AActor::CustomTimeDilation`, which scales the tick of the actor and its components
+using SetCustomTimeDilationFn = void(*)(AActorOpaque *actor, float dilation);
+
+struct TimeFns {
+  SetGlobalTimeDilationFn set_global_time_dilation;
+  SetGamePausedFn set_game_paused;
+  SetCustomTimeDilationFn set_custom_time_dilation;
+};
+
+/// Returns the level that the actor is placed in, which is the persistent level for actors that
+/// were spawned at runtime
+using GetActorLevelFn = ULevelOpaque*(*)(const AActorOpaque *actor);
+
+/// Writes the map name of the level, without the play in editor prefix
+using GetLevelNameFn = void(*)(const ULevelOpaque *level, RustAlloc *out);
+
+using IsPersistentLevelFn = uint32_t(*)(const ULevelOpaque *level);
+
+/// Starts streaming in a sublevel. With `block` the world loads it in one go when it updates
+/// streaming in its next tick. Returns 0 if the world has no streaming level with that name.
+using LoadStreamLevelFn = uint32_t(*)(Utf8Str name, uint32_t make_visible, uint32_t block);
+
+/// Starts streaming out a sublevel. Returns 0 if the world has no streaming level with that name.
+using UnloadStreamLevelFn = uint32_t(*)(Utf8Str name);
+
+/// Shows or hides a sublevel that is loaded. Returns 0 if the world has no streaming level with
+/// that name.
+using SetStreamLevelVisibleFn = uint32_t(*)(Utf8Str name, uint32_t visible);
+
+/// Travels to another map. This ends play in the current world.
+using OpenLevelFn = void(*)(Utf8Str name);
+
+struct LevelFns {
+  GetActorLevelFn get_actor_level;
+  GetLevelNameFn get_level_name;
+  IsPersistentLevelFn is_persistent_level;
+  LoadStreamLevelFn load_stream_level;
+  UnloadStreamLevelFn unload_stream_level;
+  SetStreamLevelVisibleFn set_stream_level_visible;
+  OpenLevelFn open_level;
+};
+
+/// Writes `data` to a save slot through unreal's save game system. Returns 0 on failure.
+using SaveDataToSlotFn = uint32_t(*)(Utf8Str slot, int32_t user_index, const uint8_t *data, uintptr_t len);
+
+/// Returns 0 if the slot doesn't exist or could not be read
+using LoadDataFromSlotFn = uint32_t(*)(Utf8Str slot, int32_t user_index, RustAlloc *out);
+
+using DoesSaveGameExistFn = uint32_t(*)(Utf8Str slot, int32_t user_index);
+
+using DeleteSaveGameInSlotFn = uint32_t(*)(Utf8Str slot, int32_t user_index);
+
+struct SaveGameFns {
+  SaveDataToSlotFn save_data_to_slot;
+  LoadDataFromSlotFn load_data_from_slot;
+  DoesSaveGameExistFn does_save_game_exist;
+  DeleteSaveGameInSlotFn delete_save_game_in_slot;
+};
+
 struct UnrealBindings {
   ActorFns actor_fns;
   PhysicsFns physics_fns;
@@ -299,23 +602,51 @@ struct UnrealBindings {
   GetAxisValueFn get_axis_value;
   SpawnActorFn spawn_actor;
   GetMouseDeltaFn get_mouse_delta;
-  VisualLogSegmentFn visual_log_segment;
-  VisualLogCapsuleFn visual_log_capsule;
-  VisualLogLocationFn visual_log_location;
+  VisualLogFns visual_log_fns;
+  DebugDrawFn debug_draw;
+  SendBlueprintEventFn send_blueprint_event;
   EditorComponentFns editor_component_fns;
   SoundFns sound_fns;
+  AssetFns asset_fns;
+  ObjectFns object_fns;
+  TimeFns time_fns;
+  LevelFns level_fns;
+  SaveGameFns save_game_fns;
 };
 
 using RetrieveUuids = void(*)(Uuid *ptr, uintptr_t *len);
 
-using TickFn = ResultCode(*)(float dt);
+/// The clocks of the current frame, passed to `tick`. The game ticks even while it is paused, so
+/// that Rust can keep track of real time and drive pause menus.
+struct TickTime {
+  /// Scaled by the global time dilation
+  float delta;
+  /// Wall clock delta, not affected by time dilation or pause
+  float real_delta;
+  float time_dilation;
+  uint32_t paused;
+};
+
+using TickFn = ResultCode(*)(TickTime time);
+
+/// Runs the stages of a tick group. Called once per frame for every group, after `tick`.
+using TickGroupFn = ResultCode(*)(TickGroup group);
 
 using BeginPlayFn = ResultCode(*)();
 
+using EndPlayFn = ResultCode(*)(EndPlayReason reason);
+
 using UnrealEventFn = void(*)(const EventType *ty, const void *data);
 
+/// Sends a user defined event, that was registered in Rust, from unreal into the ECS. Returns 0 if
+/// the event is unknown or the fields don't match. Events sent while Rust is running, eg from a
+/// blueprint that handles an event from Rust, are queued and always return 1.
+using SendEventFn = uint32_t(*)(Uuid uuid, const ReflectValue *fields, uintptr_t len);
+
 using IsEditorComponentFn = uint32_t(*)(Uuid uuid);
 
+using IsEventFn = uint32_t(*)(Uuid uuid);
+
 using NumberOfFieldsFn = uint32_t(*)(Uuid uuid, uint32_t *out);
 
 using HasComponentFn = uint32_t(*)(Entity entity, Uuid uuid);
@@ -336,6 +667,7 @@ using GetFieldQuatValueFn = uint32_t(*)(Uuid uuid, Entity entity, uint32_t field
 
 struct ReflectionFns {
   IsEditorComponentFn is_editor_component;
+  IsEventFn is_event;
   NumberOfFieldsFn number_of_fields;
   HasComponentFn has_component;
   GetTypeNameFn get_type_name;
@@ -347,19 +679,61 @@ struct ReflectionFns {
   GetFieldQuatValueFn get_field_quat_value;
 };
 
+using NumberOfFunctionsFn = uint32_t(*)(uint32_t *out);
+
+using GetFunctionNameFn = uint32_t(*)(uint32_t idx, Utf8Str *name);
+
+using GetFunctionNumberOfArgumentsFn = uint32_t(*)(uint32_t idx, uint32_t *out);
+
+using GetFunctionArgumentFn = uint32_t(*)(uint32_t idx, uint32_t argument_idx, Utf8Str *name, ReflectionType *ty);
+
+/// Writes 0 to `has_return_value` if the function doesn't return anything.
+using GetFunctionReturnTypeFn = uint32_t(*)(uint32_t idx, uint32_t *has_return_value, ReflectionType *ty);
+
+/// Calls the function with the given name. Strings in `out` stay valid until the next call.
+using CallFunctionFn = uint32_t(*)(Utf8Str name, Entity entity, const ReflectValue *arguments, uintptr_t len, uint32_t *has_return_value, ReflectValue *out);
+
+struct FunctionFns {
+  NumberOfFunctionsFn number_of_functions;
+  GetFunctionNameFn get_function_name;
+  GetFunctionNumberOfArgumentsFn get_function_number_of_arguments;
+  GetFunctionArgumentFn get_function_argument;
+  GetFunctionReturnTypeFn get_function_return_type;
+  CallFunctionFn call_function;
+};
+
 using AllocateFn = uint32_t(*)(uintptr_t size, uintptr_t align, RustAlloc *ptr);
 
 struct AllocateFns {
   AllocateFn allocate;
 };
 
+/// Queues a transition of the state machine `machine` to the state `state`. Returns 0 if the
+/// machine or state is unknown, if the machine is already in that state, or if Rust is still
+/// running, eg when a system called the blueprint that switches the state.
+using SetStateFn = uint32_t(*)(Utf8Str machine, Utf8Str state);
+
+/// Writes the name of the current state to `out`. The string is static. Returns 0 if the machine is
+/// unknown or if Rust is still running.
+using GetStateFn = uint32_t(*)(Utf8Str machine, Utf8Str *out);
+
+struct StateFns {
+  SetStateFn set_state;
+  GetStateFn get_state;
+};
+
 struct RustBindings {
   RetrieveUuids retrieve_uuids;
   TickFn tick;
+  TickGroupFn tick_group;
   BeginPlayFn begin_play;
+  EndPlayFn end_play;
   UnrealEventFn unreal_event;
+  SendEventFn send_event;
   ReflectionFns reflection_fns;
+  FunctionFns function_fns;
   AllocateFns allocate_fns;
+  StateFns state_fns;
 };
 
 using EntryUnrealBindingsFn = uint32_t(*)(UnrealBindings bindings, RustBindings *rust_bindings);
@@ -371,28 +745,58 @@ struct ActorSpawnedEvent {
 struct ActorBeginOverlap {
   AActorOpaque *overlapped_actor;
   AActorOpaque *other;
+  UPrimtiveOpaque *overlapped_component;
+  UPrimtiveOpaque *other_component;
+  int32_t other_body_index;
+  uint32_t from_sweep;
+  /// Only valid if `from_sweep` is 1
+  HitResult sweep_result;
 };
 
 struct ActorEndOverlap {
   AActorOpaque *overlapped_actor;
   AActorOpaque *other;
+  UPrimtiveOpaque *overlapped_component;
+  UPrimtiveOpaque *other_component;
+  int32_t other_body_index;
 };
 
 struct ActorHitEvent {
   AActorOpaque *self_actor;
   AActorOpaque *other;
+  UPrimtiveOpaque *self_component;
+  UPrimtiveOpaque *other_component;
   Vector3 normal_impulse;
+  HitResult hit;
+  /// Empty if no bone was hit. Only valid during the event.
+  Utf8Str bone_name;
+  /// Velocity of the self component relative to the other component
+  Vector3 relative_velocity;
 };
 
 struct ActorDestroyEvent {
   AActorOpaque *actor;
 };
 
+struct AssetLoadedEvent {
+  uint64_t handle;
+  /// Null if the asset could not be loaded
+  UObjectOpague *object;
+};
+
+/// Sent for every streaming level whose state changed since the last tick
+struct LevelStreamingEvent {
+  /// Null if the level is not loaded anymore
+  ULevelOpaque *level;
+  /// Only valid during the event
+  Utf8Str name;
+};
+
 extern "C" {
 
 extern void TickActor(AActorOpaque *actor, float dt);
 
-extern void Log(const char *s, int32_t len);
+extern void Log(const LogRecord *record);
 
 extern void IterateActors(AActorOpaque **array, uint64_t *len);
 
@@ -407,21 +811,7 @@ extern AActorOpaque *SpawnActor(ActorClass actor_class,
 
 extern void GetMouseDelta(float *x, float *y);
 
-extern void VisualLogSegment(const AActorOpaque *owner, Vector3 start, Vector3 end, Color color);
-
-extern void VisualLogCapsule(Utf8Str category,
-                             const AActorOpaque *owner,
-                             Vector3 position,
-                             Quaternion rotation,
-                             float half_height,
-                             float radius,
-                             Color color);
-
-extern void VisualLogLocation(Utf8Str category,
-                              const AActorOpaque *owner,
-                              Vector3 position,
-                              float radius,
-                              Color color);
+extern void SendBlueprintEvent(Uuid uuid, const ReflectValue *fields, uintptr_t len);
 
 extern uint32_t GetEditorComponentUuids(const AActorOpaque *actor, Uuid *data, uintptr_t *len);
 
@@ -451,10 +841,26 @@ extern uint32_t GetEditorComponentUObject(const AActorOpaque *actor,
                                           UObjectType ty,
                                           UObjectOpague **out);
 
+extern uint32_t GetEditorComponentSoftObjectPath(const AActorOpaque *actor,
+                                                 Uuid uuid,
+                                                 Utf8Str field,
+                                                 RustAlloc *out);
+
+extern uint32_t GetEditorComponentGameplayTags(const AActorOpaque *actor,
+                                               Uuid uuid,
+                                               Utf8Str field,
+                                               RustAlloc *out);
+
 extern void RegisterActorOnHit(AActorOpaque *actor);
 
 extern void RegisterActorOnOverlap(AActorOpaque *actor);
 
+extern float ApplyDamage(AActorOpaque *actor, float damage, AActorOpaque *instigator);
+
+extern void UnregisterActorOnHit(AActorOpaque *actor);
+
+extern void UnregisterActorOnOverlap(AActorOpaque *actor);
+
 extern void SetOwner(AActorOpaque *actor, const AActorOpaque *new_owner);
 
 extern void SetSpatialData(AActorOpaque *actor,
@@ -481,10 +887,47 @@ extern uint32_t IsMoveable(const AActorOpaque *actor);
 
 extern void GetActorName(const AActorOpaque *actor, RustAlloc *data);
 
+extern void GetActorPath(const AActorOpaque *actor, RustAlloc *data);
+
+extern uint32_t GetOwnedGameplayTags(const AActorOpaque *actor, RustAlloc *data);
+
 extern void DestroyActor(const AActorOpaque *actor);
 
 extern void SetViewTarget(const AActorOpaque *actor);
 
+extern UObjectOpague *LoadAsset(Utf8Str path, UObjectType ty);
+
+extern uint32_t LoadAssetAsync(Utf8Str path, UObjectType ty, uint64_t handle);
+
+extern void UnloadAsset(uint64_t handle);
+
+extern void DebugDraw(const DebugDrawCommand *commands, uintptr_t len);
+
+extern ULevelOpaque *GetActorLevel(const AActorOpaque *actor);
+
+extern void GetLevelName(const ULevelOpaque *level, RustAlloc *out);
+
+extern uint32_t IsPersistentLevel(const ULevelOpaque *level);
+
+extern uint32_t LoadStreamLevel(Utf8Str name, uint32_t make_visible, uint32_t block);
+
+extern uint32_t UnloadStreamLevel(Utf8Str name);
+
+extern uint32_t SetStreamLevelVisible(Utf8Str name, uint32_t visible);
+
+extern void OpenLevel(Utf8Str name);
+
+extern uint32_t CallUFunction(UObjectOpague *object,
+                              Utf8Str name,
+                              const ReflectValue *arguments,
+                              uintptr_t len,
+                              uint32_t *has_return_value,
+                              ReflectValue *out);
+
+extern uint32_t GetUProperty(UObjectOpague *object, Utf8Str name, ReflectValue *out);
+
+extern uint32_t SetUProperty(UObjectOpague *object, Utf8Str name, const ReflectValue *value);
+
 extern Vector3 GetVelocity(const UPrimtiveOpaque *primitive);
 
 extern void SetVelocity(UPrimtiveOpaque *primitive, Vector3 velocity);
@@ -519,13 +962,71 @@ extern uint32_t OverlapMulti(CollisionShape collision_shape,
                              Quaternion rotation,
                              LineTraceParams params,
                              uintptr_t max_results,
-                             OverlapResult **result);
+                             OverlapResult *results);
 
 extern uint32_t GetCollisionShape(const UPrimtiveOpaque *primitive, CollisionShape *shape);
 
+extern uint32_t SaveDataToSlot(Utf8Str slot,
+                               int32_t user_index,
+                               const uint8_t *data,
+                               uintptr_t len);
+
+extern uint32_t LoadDataFromSlot(Utf8Str slot, int32_t user_index, RustAlloc *out);
+
+extern uint32_t DoesSaveGameExist(Utf8Str slot, int32_t user_index);
+
+extern uint32_t DeleteSaveGameInSlot(Utf8Str slot, int32_t user_index);
+
 extern void PlaySoundAtLocation(const USoundBaseOpague *sound,
                                 Vector3 location,
                                 Quaternion rotation,
                                 const SoundSettings *settings);
 
+extern void SetGlobalTimeDilation(float dilation);
+
+extern uint32_t SetGamePaused(uint32_t paused);
+
+extern void SetCustomTimeDilation(AActorOpaque *actor, float dilation);
+
+extern uint32_t VisualLogIsRecording();
+
+extern void VisualLogText(const VisualLogEntry *entry);
+
+extern void VisualLogSegment(const VisualLogEntry *entry, Vector3 start, Vector3 end);
+
+extern void VisualLogArrow(const VisualLogEntry *entry, Vector3 start, Vector3 end);
+
+extern void VisualLogLocation(const VisualLogEntry *entry, Vector3 position, float radius);
+
+extern void VisualLogCapsule(const VisualLogEntry *entry,
+                             Vector3 position,
+                             Quaternion rotation,
+                             float half_height,
+                             float radius);
+
+extern void VisualLogBox(const VisualLogEntry *entry, Vector3 min, Vector3 max);
+
+extern void VisualLogOrientedBox(const VisualLogEntry *entry,
+                                 Vector3 position,
+                                 Quaternion rotation,
+                                 Vector3 half_extent);
+
+extern void VisualLogCone(const VisualLogEntry *entry,
+                          Vector3 origin,
+                          Vector3 direction,
+                          float length,
+                          float angle);
+
+extern void VisualLogPolyline(const VisualLogEntry *entry, const Vector3 *points, uintptr_t len);
+
+extern void VisualLogHistogram(const VisualLogEntry *entry,
+                               Utf8Str graph_name,
+                               Utf8Str data_name,
+                               float x,
+                               float y);
+
+extern void VisualLogStatus(const VisualLogEntry *entry,
+                            const VisualLogStatusEntry *status,
+                            uintptr_t len);
+
 } // extern "C"
@@ -0,0 +1,25 @@
+#pragma once
+
+#include "K2Node.h"
+#include "Bindings.h"
+
+#include "K2Node_CallRustFunction.generated.h"
+
+// Calls a function that was registered in Rust with `Module::add_unreal_function`. There is one node per function,
+// the pins are created from the reflection data.
+UCLASS()
+class UK2Node_CallRustFunction : public UK2Node
+{
+	GENERATED_BODY()
+
+	virtual void AllocateDefaultPins() override;
+	virtual void ExpandNode(class FKismetCompilerContext& CompilerContext, UEdGraph* SourceGraph) override;
+	virtual void GetMenuActions(FBlueprintActionDatabaseRegistrar& ActionRegistrar) const override;
+	virtual FText GetMenuCategory() const override;
+	virtual FText GetNodeTitle(ENodeTitleType::Type TitleType) const override;
+
+	UPROPERTY()
+	FString FunctionName;
+
+	UEdGraphPin* CreateValuePin(EEdGraphPinDirection Direction, ReflectionType Type, const FString& Name);
+};
@@ -0,0 +1,76 @@
+#pragma once
+
+#include "CoreMinimal.h"
+#include "Bindings.h"
+#include "RustProperty.h"
+#include "Kismet/BlueprintFunctionLibrary.h"
+#include "RustEvent.generated.h"
+
+// An event that was registered in Rust with `Module::add_unreal_event`. The fields are stored by name, the order is
+// taken from the reflection data when the event is sent to Rust.
+USTRUCT(BlueprintType)
+struct RUSTPLUGIN_API FRustEvent
+{
+	GENERATED_BODY()
+
+	UPROPERTY(BlueprintReadOnly, Category=Rust)
+	FString Name;
+
+	UPROPERTY()
+	FGuid Id;
+
+	UPROPERTY()
+	TMap<FString, FRustProperty> Fields;
+
+	// Creates an event with default values for every field. Returns false if there is no event with that name.
+	static bool Create(const FString& EventName, FRustEvent& Out);
+	static FRustEvent FromReflectValues(Uuid EventId, const ReflectValue* Values, uintptr_t Len);
+};
+
+DECLARE_DYNAMIC_MULTICAST_DELEGATE_OneParam(FOnRustEvent, const FRustEvent&, Event);
+
+UCLASS()
+class URustEventLibrary : public UBlueprintFunctionLibrary
+{
+	GENERATED_BODY()
+public:
+	UFUNCTION(BlueprintPure, Category="Rust|Events")
+	static FRustEvent MakeRustEvent(FString EventName);
+
+	UFUNCTION(BlueprintPure, Category="Rust|Events")
+	static bool IsRustEvent(const FRustEvent& Event, FString EventName);
+
+	// Sends the event into the ECS. Returns false if Rust doesn't know the event.
+	UFUNCTION(BlueprintCallable, Category="Rust|Events")
+	static bool SendRustEvent(const FRustEvent& Event);
+
+	UFUNCTION(BlueprintCallable, Category="Rust|Events")
+	static void SetRustEventFloat(UPARAM(ref) FRustEvent& Event, FString Field, float Value);
+	UFUNCTION(BlueprintCallable, Category="Rust|Events")
+	static void SetRustEventBool(UPARAM(ref) FRustEvent& Event, FString Field, bool Value);
+	UFUNCTION(BlueprintCallable, Category="Rust|Events")
+	static void SetRustEventVector(UPARAM(ref) FRustEvent& Event, FString Field, FVector Value);
+	UFUNCTION(BlueprintCallable, Category="Rust|Events")
+	static void SetRustEventRotator(UPARAM(ref) FRustEvent& Event, FString Field, FRotator Value);
+	UFUNCTION(BlueprintCallable, Category="Rust|Events")
+	static void SetRustEventObject(UPARAM(ref) FRustEvent& Event, FString Field, UObject* Value);
+	UFUNCTION(BlueprintCallable, Category="Rust|Events")
+	static void SetRustEventSoftObject(UPARAM(ref) FRustEvent& Event, FString Field, FSoftObjectPath Value);
+	UFUNCTION(BlueprintCallable, Category="Rust|Events")
+	static void SetRustEventGameplayTags(UPARAM(ref) FRustEvent& Event, FString Field, FGameplayTagContainer Value);
+
+	UFUNCTION(BlueprintPure, Category="Rust|Events")
+	static float GetRustEventFloat(const FRustEvent& Event, FString Field);
+	UFUNCTION(BlueprintPure, Category="Rust|Events")
+	static bool GetRustEventBool(const FRustEvent& Event, FString Field);
+	UFUNCTION(BlueprintPure, Category="Rust|Events")
+	static FVector GetRustEventVector(const FRustEvent& Event, FString Field);
+	UFUNCTION(BlueprintPure, Category="Rust|Events")
+	static FRotator GetRustEventRotator(const FRustEvent& Event, FString Field);
+	UFUNCTION(BlueprintPure, Category="Rust|Events")
+	static UObject* GetRustEventObject(const FRustEvent& Event, FString Field);
+	UFUNCTION(BlueprintPure, Category="Rust|Events")
+	static FSoftObjectPath GetRustEventSoftObject(const FRustEvent& Event, FString Field);
+	UFUNCTION(BlueprintPure, Category="Rust|Events")
+	static FGameplayTagContainer GetRustEventGameplayTags(const FRustEvent& Event, FString Field);
+};
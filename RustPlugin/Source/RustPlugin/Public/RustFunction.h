@@ -0,0 +1,63 @@
+#pragma once
+
+#include "CoreMinimal.h"
+#include "EntityComponent.h"
+#include "RustProperty.h"
+#include "Kismet/BlueprintFunctionLibrary.h"
+#include "RustFunction.generated.h"
+
+// Arguments or the return value of a Rust function. The values are stored in the order of the arguments.
+USTRUCT(BlueprintType)
+struct RUSTPLUGIN_API FRustValues
+{
+	GENERATED_BODY()
+
+	UPROPERTY()
+	TArray<FRustProperty> Values;
+};
+
+// Used by `UK2Node_CallRustFunction`. The functions are not meant to be called directly.
+UCLASS()
+class URustFunctionLibrary : public UBlueprintFunctionLibrary
+{
+	GENERATED_BODY()
+public:
+	UFUNCTION(BlueprintPure, meta=(BlueprintInternalUseOnly = "true"))
+	static FRustValues K2_MakeRustValues();
+
+	UFUNCTION(BlueprintPure, meta=(BlueprintInternalUseOnly = "true"))
+	static FRustValues K2_AddRustFloat(const FRustValues& Values, float Value);
+	UFUNCTION(BlueprintPure, meta=(BlueprintInternalUseOnly = "true"))
+	static FRustValues K2_AddRustBool(const FRustValues& Values, bool Value);
+	UFUNCTION(BlueprintPure, meta=(BlueprintInternalUseOnly = "true"))
+	static FRustValues K2_AddRustVector(const FRustValues& Values, FVector Value);
+	UFUNCTION(BlueprintPure, meta=(BlueprintInternalUseOnly = "true"))
+	static FRustValues K2_AddRustQuat(const FRustValues& Values, FQuat Value);
+	UFUNCTION(BlueprintPure, meta=(BlueprintInternalUseOnly = "true"))
+	static FRustValues K2_AddRustSoftObject(const FRustValues& Values, FSoftObjectPath Value);
+	UFUNCTION(BlueprintPure, meta=(BlueprintInternalUseOnly = "true"))
+	static FRustValues K2_AddRustGameplayTags(const FRustValues& Values, FGameplayTagContainer Value);
+	// `Type` is the `ReflectionType` of the argument
+	UFUNCTION(BlueprintPure, meta=(BlueprintInternalUseOnly = "true"))
+	static FRustValues K2_AddRustObject(const FRustValues& Values, UObject* Value, int32 Type);
+
+	// Returns false if there is no function with that name or if the arguments don't match
+	UFUNCTION(BlueprintCallable, meta=(BlueprintInternalUseOnly = "true"))
+	static bool K2_CallRustFunction(FString Name, FEntity EntityId, const FRustValues& Arguments,
+	                                FRustValues& Result);
+
+	UFUNCTION(BlueprintPure, meta=(BlueprintInternalUseOnly = "true"))
+	static float K2_GetRustFloat(const FRustValues& Values, int32 Index);
+	UFUNCTION(BlueprintPure, meta=(BlueprintInternalUseOnly = "true"))
+	static bool K2_GetRustBool(const FRustValues& Values, int32 Index);
+	UFUNCTION(BlueprintPure, meta=(BlueprintInternalUseOnly = "true"))
+	static FVector K2_GetRustVector(const FRustValues& Values, int32 Index);
+	UFUNCTION(BlueprintPure, meta=(BlueprintInternalUseOnly = "true"))
+	static FQuat K2_GetRustQuat(const FRustValues& Values, int32 Index);
+	UFUNCTION(BlueprintPure, meta=(BlueprintInternalUseOnly = "true"))
+	static FSoftObjectPath K2_GetRustSoftObject(const FRustValues& Values, int32 Index);
+	UFUNCTION(BlueprintPure, meta=(BlueprintInternalUseOnly = "true"))
+	static FGameplayTagContainer K2_GetRustGameplayTags(const FRustValues& Values, int32 Index);
+	UFUNCTION(BlueprintPure, meta=(BlueprintInternalUseOnly = "true"))
+	static UObject* K2_GetRustObject(const FRustValues& Values, int32 Index);
+};
@@ -5,16 +5,52 @@
 #include "CoreMinimal.h"
 #include "GameFramework/GameModeBase.h"
 #include "Containers/Map.h"
+#include "Bindings.h"
+#include "Engine/StreamableManager.h"
+#include "RustEvent.h"
 #include "RustGameModeBase.generated.h"
 
 class FRustPluginModule;
 class UPlayerInput;
+class ULevelStreaming;
 
 struct FInputMap
 {
 	TMap<uint32, FName> AxisMapping;
 	TMap<int32, FName> ActionMapping;
 };
+// The state of a streaming level that Rust was last told about
+struct FRustStreamingLevelState
+{
+	bool bLoaded = false;
+	bool bVisible = false;
+	// Only identifies the level in Rust, this is never dereferenced
+	ULevel* Level = nullptr;
+};
+
+// Runs the stages of a Rust tick group. The game mode itself ticks in TG_PrePhysics and calls `tick`,
+// every later group has its own tick function.
+USTRUCT()
+struct FRustTickFunction : public FTickFunction
+{
+	GENERATED_BODY()
+
+	TickGroup Group = TickGroup::DuringPhysics;
+
+	virtual void ExecuteTick(float DeltaTime, ELevelTick TickType, ENamedThreads::Type CurrentThread,
+	                         const FGraphEventRef& MyCompletionGraphEvent) override;
+	virtual FString DiagnosticMessage() override;
+};
+
+template <>
+struct TStructOpsTypeTraits<FRustTickFunction> : public TStructOpsTypeTraitsBase2<FRustTickFunction>
+{
+	enum
+	{
+		WithCopy = false
+	};
+};
+
 /**
  *
  */
@@ -26,23 +62,54 @@ class RUSTPLUGIN_API ARustGameModeBase : public AGameModeBase
 	~ARustGameModeBase();
 	virtual void StartPlay();
 	virtual void Tick(float Dt);
+	virtual void EndPlay(const EEndPlayReason::Type EndPlayReason) override;
 	UPlayerInput *PlayerInput;
 	int32 Handle;
+	FDelegateHandle ActorSpawnedHandle;
 	virtual void PostLogin(APlayerController *NewPlayer);
 	void OnActorSpawnedHandler(AActor *actor);
 
 public:
 	UPROPERTY(EditAnywhere, Category = Game)
 	TArray<TSubclassOf<AActor>> RegisteredClasses;
+
+	// Called for every event that Rust sends with `send_to_blueprint`
+	UPROPERTY(BlueprintAssignable, Category = Rust)
+	FOnRustEvent OnRustEvent;
+
+	// Assets that were requested from Rust. Keeping the handle alive keeps the asset loaded.
+	TMap<uint64, TSharedPtr<FStreamableHandle>> AssetHandles;
+	// Finished loads are forwarded to Rust at the start of the next tick, so that we never call
+	// back into Rust while it is still inside `load_asset_async`.
+	TArray<AssetLoadedEvent> PendingAssetLoadedEvents;
+	void OnAssetLoaded(uint64 Handle, UObject* Object);
+
+	FRustTickFunction DuringPhysicsTick;
+	FRustTickFunction PostPhysicsTick;
+	FRustTickFunction LateCameraTick;
+	FRustTickFunction PostUpdateWorkTick;
+	void RegisterTickGroup(FRustTickFunction& TickFunction, TickGroup Group, ETickingGroup UnrealGroup);
+
+	// Unreal has no delegate for every streaming level, so we compare their state with the last tick
+	TMap<TWeakObjectPtr<ULevelStreaming>, FRustStreamingLevelState> StreamingLevelStates;
+	// Sends the streaming events to Rust. Actors of levels that became visible are registered unless they
+	// already have been, which is the case for the levels that are visible at the start of play.
+	void UpdateStreamingLevels(bool bRegisterActors);
+	void SendLevelEvent(EventType Type, ULevel* Level, const FString& Name);
 	
+	// We bind to the primitive components instead of the actor, so that Rust knows which components overlapped
 	UFUNCTION()
-	void OnActorBeginOverlap(AActor* OverlappedActor, AActor* OtherActor);
-	
+	void OnComponentBeginOverlap(UPrimitiveComponent* OverlappedComponent, AActor* OtherActor,
+	                             UPrimitiveComponent* OtherComp, int32 OtherBodyIndex, bool bFromSweep,
+	                             const FHitResult& SweepResult);
+
 	UFUNCTION()
-	void OnActorEndOverlap(AActor* OverlappedActor, AActor* OtherActor);
-	
+	void OnComponentEndOverlap(UPrimitiveComponent* OverlappedComponent, AActor* OtherActor,
+	                           UPrimitiveComponent* OtherComp, int32 OtherBodyIndex);
+
 	UFUNCTION()
-    void OnActorHit(AActor* SelfActor, AActor* OtherActor, FVector NormalImpulse, const FHitResult& Hit);
+	void OnComponentHit(UPrimitiveComponent* HitComponent, AActor* OtherActor, UPrimitiveComponent* OtherComp,
+	                    FVector NormalImpulse, const FHitResult& Hit);
 	
 	UFUNCTION()
     void OnActorDestroyed(AActor* Actor);
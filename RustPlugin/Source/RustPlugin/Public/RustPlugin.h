@@ -20,13 +20,26 @@ struct FRustReflection
 	FString Name;
 	uint32 NumberOfFields;
 	bool IsEditorComponent;
+	// Events are registered in the same registry as components, but they can't be added to an entity
+	bool IsEvent;
 	TMap<uint32, FString> IndexToFieldName;
 	TMap<FString, ReflectionType> FieldNameToType;
 };
 
+// A function that was registered in Rust with `Module::add_unreal_function`
+struct FRustFunction
+{
+	FString Name;
+	TArray<FString> ArgumentNames;
+	TArray<ReflectionType> ArgumentTypes;
+	bool HasReturnValue;
+	ReflectionType ReturnType;
+};
+
 struct FReflectionData
 {
 	TMap<FGuid, FRustReflection> Types;
+	TMap<FString, FRustFunction> Functions;
 };
 
 struct FPlugin {
@@ -5,11 +5,18 @@
 #include "CoreMinimal.h"
 #include "Bindings.h"
 #include "DetailCategoryBuilder.h"
+#include "GameplayTagContainer.h"
 #include "Templates/SubclassOf.h"
 #include "UObject/Object.h"
 #include "RustProperty.generated.h"
 
 class USoundBase;
+class UStaticMesh;
+class UMaterialInterface;
+class UTexture;
+class UParticleSystem;
+class UDataAsset;
+class UAnimationAsset;
 DECLARE_DELEGATE_RetVal(FReply, FOnComponentRemoved);
 
 UENUM()
@@ -20,7 +27,15 @@ enum ERustPropertyTag
 	Vector,
 	Quat,
 	Class,
-	Sound
+	Sound,
+	StaticMesh,
+	Material,
+	Texture,
+	ParticleSystem,
+	DataAsset,
+	AnimationAsset,
+	SoftObject,
+	GameplayTags
 };
 
 // TODO: This is a disgusting hack. We store all the possible variants in this struct so that we can access them
@@ -58,7 +73,44 @@ struct FRustProperty
 	
 	UPROPERTY(EditAnywhere, Category=Rust)
 	TObjectPtr<USoundBase> Sound;
+
+	UPROPERTY(EditAnywhere, Category=Rust)
+	TObjectPtr<UStaticMesh> StaticMesh;
+
+	UPROPERTY(EditAnywhere, Category=Rust)
+	TObjectPtr<UMaterialInterface> Material;
+
+	UPROPERTY(EditAnywhere, Category=Rust)
+	TObjectPtr<UTexture> Texture;
+
+	UPROPERTY(EditAnywhere, Category=Rust)
+	TObjectPtr<UParticleSystem> ParticleSystem;
+
+	UPROPERTY(EditAnywhere, Category=Rust)
+	TObjectPtr<UDataAsset> DataAsset;
+
+	UPROPERTY(EditAnywhere, Category=Rust)
+	TObjectPtr<UAnimationAsset> AnimationAsset;
+
+	UPROPERTY(EditAnywhere, Category=Rust)
+	FSoftObjectPath SoftObject;
+
+	UPROPERTY(EditAnywhere, Category=Rust)
+	FGameplayTagContainer GameplayTags;
+
 	static void Initialize(TSharedPtr<IPropertyHandle> Handle, ReflectionType Type);
+	// Returns the asset that is stored in this property if it matches the requested type
+	UObject* GetUObject(UObjectType Type) const;
+	// Sets the tag and stores the asset in the matching field. Objects of the wrong class are stored as nullptr.
+	void SetUObject(UObjectType Type, UObject* Object);
+	// Returns false if the tag doesn't store an asset
+	bool GetUObjectType(UObjectType& Out) const;
+
+	// A property with the tag that matches the type and a default value
+	static FRustProperty Default(ReflectionType Type);
+	static FRustProperty FromReflectValue(const ReflectValue& Value);
+	// Strings are written into `Utf8Strings`, which needs to outlive the returned value
+	ReflectValue ToReflectValue(ReflectionType Type, TArray<TUniquePtr<FTCHARToUTF8>>& Utf8Strings) const;
 };
 
 
@@ -0,0 +1,22 @@
+#pragma once
+
+#include "CoreMinimal.h"
+#include "Kismet/BlueprintFunctionLibrary.h"
+#include "RustState.generated.h"
+
+// Switches state machines that were registered in Rust with `Module::add_unreal_state`
+UCLASS()
+class URustStateLibrary : public UBlueprintFunctionLibrary
+{
+	GENERATED_BODY()
+public:
+	// Queues a transition, which happens the next time the state machine is updated. Returns false if the machine or
+	// the state is unknown, if the machine is already in that state, or if it is called while Rust is running, eg from
+	// a function that a Rust system called. Switch the state from a Rust event instead in that case.
+	UFUNCTION(BlueprintCallable, Category="Rust|State")
+	static bool SetRustState(FString Machine, FString State);
+
+	// Returns an empty string if the machine is unknown or if it is called while Rust is running
+	UFUNCTION(BlueprintPure, Category="Rust|State")
+	static FString GetRustState(FString Machine);
+};
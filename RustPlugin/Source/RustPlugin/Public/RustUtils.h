@@ -6,6 +6,7 @@
 
 class AActor;
 class FRustPluginModule;
+struct FGameplayTagContainer;
 
 extern struct FLogCategoryRustVisualLog : public FLogCategory<ELogVerbosity::Log, ELogVerbosity::All>
 {
@@ -21,6 +22,8 @@ Vector3 ToVector3(FVector v);
 FVector ToFVector(Vector3 v);
 FColor ToFColor(Color c);
 
+ELogVerbosity::Type ToELogVerbosity(LogVerbosity Verbosity);
+
 // W, X, Y, Z
 FQuat ToFQuat(Quaternion q);
 
@@ -34,7 +37,21 @@ UnrealBindings CreateBindings();
 FRustPluginModule& GetRustModule();
 
 FCollisionShape ToFCollisionShape(CollisionShape Shape);
+HitResult ToHitResult(const FHitResult& Hit);
+
+// Returns the base class that is used when loading an asset of the given type
+UClass* ToUClass(UObjectType Type);
+// Returns false if instances of the class can't be passed to Rust
+bool ToUObjectType(const UClass* Class, UObjectType& Out);
+ReflectionType ToReflectionType(UObjectType Type);
+EndPlayReason ToEndPlayReason(EEndPlayReason::Type Reason);
+// The short map name of a level package, without the play in editor prefix
+FString ToLevelName(const FString& PackageName);
 
 
 FString ToFString(Utf8Str Str);
+// Parses tag names separated by `,`. Tags that are not registered in the tag table are skipped.
+FGameplayTagContainer ToFGameplayTagContainer(Utf8Str Tags);
+// Joins the tag names with `,`
+FString ToTagString(const FGameplayTagContainer& Tags);
 struct FRustProperty* GetRustProperty(const AActorOpaque* actor, Uuid uuid, Utf8Str field);
@@ -25,7 +25,7 @@ public class RustPlugin : ModuleRules
 		PublicDependencyModuleNames.AddRange(
 			new string[]
 			{
-				"Core", "GraphEditor",
+				"Core", "GraphEditor", "GameplayTags",
 				// ... add other public dependencies that you statically link with here ...
 			}
 			);
@@ -0,0 +1,623 @@
+use std::collections::HashMap;
+
+use bevy_ecs::{event::ManualEventReader, prelude::*};
+use unreal_api::api::{LineTraceParams, OverlapParams, UnrealApi};
+use unreal_api::Component;
+use unreal_api::{
+    core::{CorePlugin, CoreStage, TransformComponent},
+    math::{Quat, Vec3},
+    module::{GameThread, Module},
+    physics::CollisionShape,
+    plugin::{plugin_name, Plugin},
+    register_components,
+};
+
+/// Identifies what kind of damage was dealt, so that [`Resistances`] can scale it. Custom damage
+/// types can be declared as constants next to the built in ones.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DamageType(pub &'static str);
+
+impl DamageType {
+    pub const GENERIC: Self = Self("Generic");
+    pub const EXPLOSION: Self = Self("Explosion");
+    pub const FIRE: Self = Self("Fire");
+    pub const FALL: Self = Self("Fall");
+}
+
+impl Default for DamageType {
+    fn default() -> Self {
+        Self::GENERIC
+    }
+}
+
+#[derive(Debug, Component)]
+#[uuid = "893d25a4-09bd-4086-8ea1-132e04d28dd4"]
+#[reflect(editor)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self {
+            current: 100.0,
+            max: 100.0,
+        }
+    }
+}
+
+impl Health {
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.0
+    }
+
+    /// Returns the remaining health in the range `[0, 1]`
+    pub fn fraction(&self) -> f32 {
+        if self.max > 0.0 {
+            (self.current / self.max).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Heals up to [`Health::max`]. Dead entities can not be healed.
+    pub fn heal(&mut self, amount: f32) {
+        if !self.is_dead() {
+            self.current = f32::min(self.current + amount, self.max);
+        }
+    }
+}
+
+#[derive(Default, Debug, Component)]
+#[uuid = "5ca9f588-b8f7-44bd-a0a6-3583e275a919"]
+#[reflect(editor)]
+pub struct Armor {
+    /// Flat amount that is subtracted from every hit before resistances are applied
+    pub amount: f32,
+}
+
+/// Scales incoming damage per [`DamageType`]. A multiplier of `0.5` halves the damage, damage
+/// types without an entry are not scaled.
+#[derive(Default, Debug, Component)]
+#[uuid = "ff0722d5-ca2c-429c-ad3f-82babee875c9"]
+pub struct Resistances {
+    #[reflect(skip)]
+    multipliers: HashMap<DamageType, f32>,
+}
+
+impl Resistances {
+    pub fn with(mut self, damage_type: DamageType, multiplier: f32) -> Self {
+        self.multipliers.insert(damage_type, multiplier);
+        self
+    }
+
+    pub fn set(&mut self, damage_type: DamageType, multiplier: f32) {
+        self.multipliers.insert(damage_type, multiplier);
+    }
+
+    pub fn multiplier(&self, damage_type: DamageType) -> f32 {
+        self.multipliers.get(&damage_type).copied().unwrap_or(1.0)
+    }
+}
+
+/// Damage the target directly. Send this from gameplay code, eg when a projectile hits.
+#[derive(Debug, Copy, Clone)]
+pub struct PointDamageEvent {
+    pub target: Entity,
+    pub instigator: Option<Entity>,
+    pub amount: f32,
+    pub damage_type: DamageType,
+    /// Where the damage was applied in world space
+    pub location: Vec3,
+}
+
+/// Damages every entity with [`Health`] within `radius` of `origin`. The damage falls off linearly
+/// from `base_damage` at the origin to `min_damage` at the edge of the radius.
+#[derive(Debug, Copy, Clone)]
+pub struct RadialDamageEvent {
+    pub origin: Vec3,
+    pub radius: f32,
+    pub base_damage: f32,
+    pub min_damage: f32,
+    pub damage_type: DamageType,
+    /// Doesn't block the line of sight, eg the barrel that exploded
+    pub instigator: Option<Entity>,
+    /// Entities that are behind geometry will not be damaged
+    pub require_line_of_sight: bool,
+}
+
+/// Sent after damage has been applied to an entity, with armor and resistances already taken into
+/// account.
+#[derive(Debug, Copy, Clone)]
+pub struct DamageEvent {
+    pub target: Entity,
+    pub instigator: Option<Entity>,
+    pub amount: f32,
+    pub damage_type: DamageType,
+    pub location: Vec3,
+}
+
+/// Sent once when the [`Health`] of an entity drops to zero
+#[derive(Debug, Copy, Clone)]
+pub struct DeathEvent {
+    pub entity: Entity,
+    pub instigator: Option<Entity>,
+    pub damage_type: DamageType,
+}
+
+/// Settings of the [`DamagePlugin`], change them with `Module::configure`
+#[derive(Debug)]
+pub struct DamageSettings {
+    /// Also calls `UGameplayStatics::ApplyDamage` on the actor, so that blueprints that listen to
+    /// `OnTakeAnyDamage` keep working.
+    pub forward_to_unreal: bool,
+    /// The most overlaps that radial damage looks at, entities beyond that are not damaged
+    pub max_overlaps: usize,
+}
+
+impl Default for DamageSettings {
+    fn default() -> Self {
+        Self {
+            forward_to_unreal: false,
+            max_overlaps: 64,
+        }
+    }
+}
+
+/// The world queries that radial damage needs. This is implemented for [`UnrealApi`], and can be
+/// implemented by a mock to run the damage logic without the engine.
+pub trait DamageQueries {
+    /// Returns up to `max_overlaps` entities that overlap with the sphere. Entities may show up more
+    /// than once.
+    fn overlap_sphere(&self, origin: Vec3, radius: f32, max_overlaps: usize) -> Vec<Entity>;
+    /// Returns true if nothing other than `source` or `target` blocks the line between `from` and
+    /// `to`
+    fn has_line_of_sight(
+        &self,
+        from: Vec3,
+        to: Vec3,
+        source: Option<Entity>,
+        target: Entity,
+    ) -> bool;
+}
+
+impl DamageQueries for UnrealApi {
+    fn overlap_sphere(&self, origin: Vec3, radius: f32, max_overlaps: usize) -> Vec<Entity> {
+        self.overlap_multi(
+            origin,
+            Quat::IDENTITY,
+            CollisionShape::Sphere { radius },
+            max_overlaps,
+            OverlapParams::default(),
+        )
+        .into_iter()
+        .map(|hit| hit.entity)
+        .collect()
+    }
+
+    fn has_line_of_sight(
+        &self,
+        from: Vec3,
+        to: Vec3,
+        source: Option<Entity>,
+        target: Entity,
+    ) -> bool {
+        let mut params = LineTraceParams::default().add_ignored_entity(target);
+        if let Some(source) = source {
+            params = params.add_ignored_entity(source);
+        }
+        self.line_trace(from, to, params).is_none()
+    }
+}
+
+/// Linear falloff from `base_damage` at the center to `min_damage` at `radius`. Returns zero
+/// outside of the radius.
+pub fn radial_falloff(base_damage: f32, min_damage: f32, radius: f32, distance: f32) -> f32 {
+    if radius <= 0.0 || distance > radius {
+        return 0.0;
+    }
+    let t = distance / radius;
+    base_damage + (min_damage - base_damage) * t
+}
+
+/// Applies armor and then resistances to the incoming damage. Never returns negative damage.
+pub fn mitigate_damage(
+    amount: f32,
+    damage_type: DamageType,
+    armor: Option<&Armor>,
+    resistances: Option<&Resistances>,
+) -> f32 {
+    let amount = amount - armor.map_or(0.0, |armor| armor.amount);
+    let amount = amount * resistances.map_or(1.0, |r| r.multiplier(damage_type));
+    f32::max(amount, 0.0)
+}
+
+/// Turns a radial damage event into point damage for every entity inside the radius. `position`
+/// returns the world position of an entity.
+pub fn resolve_radial_damage(
+    event: &RadialDamageEvent,
+    queries: &impl DamageQueries,
+    max_overlaps: usize,
+    position: impl Fn(Entity) -> Option<Vec3>,
+) -> Vec<PointDamageEvent> {
+    let mut targets = queries.overlap_sphere(event.origin, event.radius, max_overlaps);
+    // An entity shows up once for every primitive component that overlaps
+    targets.sort();
+    targets.dedup();
+
+    targets
+        .into_iter()
+        .filter_map(|target| {
+            let location = position(target)?;
+            if event.require_line_of_sight
+                && !queries.has_line_of_sight(event.origin, location, event.instigator, target)
+            {
+                return None;
+            }
+            let distance = Vec3::distance(event.origin, location);
+            let amount =
+                radial_falloff(event.base_damage, event.min_damage, event.radius, distance);
+            (amount > 0.0).then_some(PointDamageEvent {
+                target,
+                instigator: event.instigator,
+                amount,
+                damage_type: event.damage_type,
+                location,
+            })
+        })
+        .collect()
+}
+
+/// Applies point damage and radial damage to the [`Health`] of the targets, after armor and
+/// resistances. Sends a [`DamageEvent`] for every hit that dealt damage and a [`DeathEvent`] when
+/// the health of a target drops to zero. Dead targets are skipped. Needs the [`DamageSettings`]
+/// resource.
+pub fn apply_damage(
+    world: &mut World,
+    queries: &impl DamageQueries,
+    point_damage: impl IntoIterator<Item = PointDamageEvent>,
+    radial_damage: impl IntoIterator<Item = RadialDamageEvent>,
+) {
+    let max_overlaps = world.resource::<DamageSettings>().max_overlaps;
+    let position = |entity| {
+        world
+            .get::<TransformComponent>(entity)
+            .map(|transform| transform.position)
+    };
+    let mut pending: Vec<PointDamageEvent> = point_damage.into_iter().collect();
+    for event in radial_damage {
+        pending.extend(resolve_radial_damage(
+            &event,
+            queries,
+            max_overlaps,
+            position,
+        ));
+    }
+
+    for event in pending {
+        let Some(mut target) = world.get_entity_mut(event.target) else {
+            continue;
+        };
+        let amount = mitigate_damage(
+            event.amount,
+            event.damage_type,
+            target.get::<Armor>(),
+            target.get::<Resistances>(),
+        );
+        let Some(mut health) = target.get_mut::<Health>() else {
+            continue;
+        };
+        if health.is_dead() || amount <= 0.0 {
+            continue;
+        }
+        health.current = f32::max(health.current - amount, 0.0);
+        let died = health.is_dead();
+
+        world.send_event(DamageEvent {
+            target: event.target,
+            instigator: event.instigator,
+            amount,
+            damage_type: event.damage_type,
+            location: event.location,
+        });
+        if died {
+            world.send_event(DeathEvent {
+                entity: event.target,
+                instigator: event.instigator,
+                damage_type: event.damage_type,
+            });
+        }
+    }
+}
+
+#[derive(Default)]
+struct DamageReaders {
+    point: ManualEventReader<PointDamageEvent>,
+    radial: ManualEventReader<RadialDamageEvent>,
+}
+
+fn apply_damage_system(world: &mut World) {
+    let (point, radial) = world.resource_scope(|world, mut readers: Mut<DamageReaders>| {
+        let point: Vec<PointDamageEvent> = readers
+            .point
+            .iter(world.resource::<Events<PointDamageEvent>>())
+            .copied()
+            .collect();
+        let radial: Vec<RadialDamageEvent> = readers
+            .radial
+            .iter(world.resource::<Events<RadialDamageEvent>>())
+            .copied()
+            .collect();
+        (point, radial)
+    });
+    world.resource_scope(|world, api: Mut<UnrealApi>| {
+        apply_damage(world, &*api, point, radial);
+    });
+}
+
+fn forward_damage_to_unreal(
+    _game_thread: NonSend<GameThread>,
+    api: Res<UnrealApi>,
+    settings: Res<DamageSettings>,
+    mut damage_events: EventReader<DamageEvent>,
+) {
+    for event in damage_events.iter() {
+        if !settings.forward_to_unreal {
+            continue;
+        }
+        if let Some(actor) = api.entity_to_actor.get(&event.target) {
+            let instigator = event
+                .instigator
+                .and_then(|entity| api.entity_to_actor.get(&entity))
+                .copied();
+            actor.apply_damage(event.amount, instigator);
+        }
+    }
+}
+
+pub struct DamagePlugin;
+
+impl Plugin for DamagePlugin {
+    fn dependencies(&self) -> Vec<&'static str> {
+        vec![plugin_name::<CorePlugin>()]
+    }
+
+    fn build(&self, module: &mut Module) {
+        register_components! {
+            Health,
+            Armor,
+            Resistances,
+            => module
+        };
+
+        module
+            .init_resource::<DamageSettings>()
+            .init_resource::<DamageReaders>()
+            .add_event::<PointDamageEvent>()
+            .add_event::<RadialDamageEvent>()
+            .add_event::<DamageEvent>()
+            .add_event::<DeathEvent>()
+            .add_system_set_to_stage(
+                CoreStage::PostUpdate,
+                SystemSet::new()
+                    // Radial damage calls into unreal, exclusive systems run on the game thread
+                    .with_system(apply_damage_system.exclusive_system().at_start())
+                    .with_system(forward_damage_to_unreal),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct MockQueries {
+        overlaps: Vec<Entity>,
+        /// Entities that are behind a wall
+        hidden: Vec<Entity>,
+    }
+
+    impl DamageQueries for MockQueries {
+        fn overlap_sphere(&self, _origin: Vec3, _radius: f32, max_overlaps: usize) -> Vec<Entity> {
+            self.overlaps.iter().copied().take(max_overlaps).collect()
+        }
+
+        fn has_line_of_sight(
+            &self,
+            _from: Vec3,
+            _to: Vec3,
+            _source: Option<Entity>,
+            target: Entity,
+        ) -> bool {
+            !self.hidden.contains(&target)
+        }
+    }
+
+    fn explosion(require_line_of_sight: bool) -> RadialDamageEvent {
+        RadialDamageEvent {
+            origin: Vec3::ZERO,
+            radius: 100.0,
+            base_damage: 50.0,
+            min_damage: 10.0,
+            damage_type: DamageType::EXPLOSION,
+            instigator: None,
+            require_line_of_sight,
+        }
+    }
+
+    #[test]
+    fn falloff_is_linear_inside_the_radius() {
+        assert_eq!(radial_falloff(50.0, 10.0, 100.0, 0.0), 50.0);
+        assert_eq!(radial_falloff(50.0, 10.0, 100.0, 50.0), 30.0);
+        assert_eq!(radial_falloff(50.0, 10.0, 100.0, 100.0), 10.0);
+        assert_eq!(radial_falloff(50.0, 10.0, 100.0, 100.1), 0.0);
+        assert_eq!(radial_falloff(50.0, 10.0, 0.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn armor_is_subtracted_before_resistances() {
+        let armor = Armor { amount: 10.0 };
+        let resistances = Resistances::default().with(DamageType::FIRE, 0.5);
+
+        assert_eq!(mitigate_damage(30.0, DamageType::FIRE, None, None), 30.0);
+        assert_eq!(
+            mitigate_damage(30.0, DamageType::FIRE, Some(&armor), Some(&resistances)),
+            10.0
+        );
+        assert_eq!(
+            mitigate_damage(30.0, DamageType::GENERIC, Some(&armor), Some(&resistances)),
+            20.0
+        );
+        assert_eq!(
+            mitigate_damage(5.0, DamageType::GENERIC, Some(&armor), None),
+            0.0
+        );
+    }
+
+    #[test]
+    fn radial_damage_hits_every_entity_once() {
+        let near = Entity::from_raw(0);
+        let far = Entity::from_raw(1);
+        let outside = Entity::from_raw(2);
+        let queries = MockQueries {
+            overlaps: vec![far, near, outside, near],
+            ..Default::default()
+        };
+        let position = |entity| {
+            Some(match entity {
+                e if e == near => Vec3::new(0.0, 0.0, 0.0),
+                e if e == far => Vec3::new(50.0, 0.0, 0.0),
+                _ => Vec3::new(200.0, 0.0, 0.0),
+            })
+        };
+
+        let damage = resolve_radial_damage(&explosion(false), &queries, 64, position);
+        let amounts: Vec<_> = damage
+            .iter()
+            .map(|event| (event.target, event.amount))
+            .collect();
+        assert_eq!(amounts, vec![(near, 50.0), (far, 30.0)]);
+        assert!(damage
+            .iter()
+            .all(|event| event.damage_type == DamageType::EXPLOSION));
+    }
+
+    #[test]
+    fn radial_damage_checks_line_of_sight() {
+        let visible = Entity::from_raw(0);
+        let hidden = Entity::from_raw(1);
+        let queries = MockQueries {
+            overlaps: vec![visible, hidden],
+            hidden: vec![hidden],
+        };
+        let position = |_| Some(Vec3::ZERO);
+
+        let targets = |event| {
+            resolve_radial_damage(&event, &queries, 64, position)
+                .into_iter()
+                .map(|event| event.target)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(targets(explosion(true)), vec![visible]);
+        assert_eq!(targets(explosion(false)), vec![visible, hidden]);
+    }
+
+    #[test]
+    fn radial_damage_skips_entities_without_position() {
+        let queries = MockQueries {
+            overlaps: vec![Entity::from_raw(0)],
+            ..Default::default()
+        };
+        assert!(resolve_radial_damage(&explosion(false), &queries, 64, |_| None).is_empty());
+    }
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.insert_resource(DamageSettings::default());
+        world.init_resource::<Events<DamageEvent>>();
+        world.init_resource::<Events<DeathEvent>>();
+        world
+    }
+
+    fn hit(target: Entity, amount: f32) -> PointDamageEvent {
+        PointDamageEvent {
+            target,
+            instigator: None,
+            amount,
+            damage_type: DamageType::GENERIC,
+            location: Vec3::ZERO,
+        }
+    }
+
+    fn sent<E: bevy_ecs::event::Event + Copy>(world: &World) -> Vec<E> {
+        world
+            .resource::<Events<E>>()
+            .iter_current_update_events()
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn health_is_clamped_at_zero_and_death_is_sent_once() {
+        let mut world = world();
+        let target = world
+            .spawn()
+            .insert(Health {
+                current: 30.0,
+                max: 100.0,
+            })
+            .id();
+
+        let queries = MockQueries::default();
+        apply_damage(
+            &mut world,
+            &queries,
+            [hit(target, 50.0), hit(target, 50.0)],
+            [],
+        );
+        assert_eq!(world.get::<Health>(target).unwrap().current, 0.0);
+        let damage = sent::<DamageEvent>(&world);
+        assert_eq!(damage.len(), 1);
+        assert_eq!(damage[0].amount, 50.0);
+        assert_eq!(sent::<DeathEvent>(&world).len(), 1);
+
+        // Already dead
+        apply_damage(&mut world, &queries, [hit(target, 50.0)], []);
+        assert_eq!(sent::<DamageEvent>(&world).len(), 1);
+        assert_eq!(sent::<DeathEvent>(&world).len(), 1);
+    }
+
+    #[test]
+    fn radial_damage_is_mitigated_and_capped() {
+        let mut world = world();
+        world.resource_mut::<DamageSettings>().max_overlaps = 2;
+        let armored = world
+            .spawn()
+            .insert_bundle((
+                Health::default(),
+                Armor { amount: 20.0 },
+                TransformComponent::default(),
+            ))
+            .id();
+        let plain = world
+            .spawn()
+            .insert_bundle((Health::default(), TransformComponent::default()))
+            .id();
+        let beyond_cap = world
+            .spawn()
+            .insert_bundle((Health::default(), TransformComponent::default()))
+            .id();
+        let queries = MockQueries {
+            overlaps: vec![armored, plain, beyond_cap],
+            ..Default::default()
+        };
+
+        apply_damage(&mut world, &queries, [], [explosion(false)]);
+        assert_eq!(world.get::<Health>(armored).unwrap().current, 70.0);
+        assert_eq!(world.get::<Health>(plain).unwrap().current, 50.0);
+        assert_eq!(world.get::<Health>(beyond_cap).unwrap().current, 100.0);
+        assert!(sent::<DeathEvent>(&world).is_empty());
+    }
+}
@@ -2,15 +2,17 @@ use bevy_ecs::{prelude::*, query::WorldQuery};
 use unreal_api::api::{SweepHit, SweepParams, UnrealApi};
 use unreal_api::Component;
 use unreal_api::{
-    core::{ActorComponent, CoreStage, Frame, TransformComponent},
+    core::{ActorComponent, CorePlugin, CoreStage, TransformComponent},
     ffi,
+    fixed_update::FixedTime,
     input::Input,
     log::LogCategory,
     math::{Quat, Vec3, Vec3Swizzles},
-    module::Module,
-    physics::PhysicsComponent,
-    plugin::Plugin,
+    module::{GameThread, Module},
+    physics::{PhysicsComponent, SurfaceTable, SurfaceType},
+    plugin::{plugin_name, Plugin},
     register_components,
+    time::run_if_not_paused,
 };
 fn project_onto_plane(dir: Vec3, normal: Vec3) -> Vec3 {
     dir - normal * Vec3::dot(dir, normal)
@@ -50,6 +52,7 @@ pub struct MovementComponent {
 
 #[derive(Default, Debug, Component)]
 #[uuid = "ac41cdd4-3311-45ef-815c-9a31adbe4098"]
+#[reflect(default)]
 pub struct CharacterControllerComponent {
     pub horizontal_velocity: Vec3,
     pub vertical_velocity: Vec3,
@@ -57,6 +60,12 @@ pub struct CharacterControllerComponent {
     #[reflect(skip)]
     pub movement_state: MovementState,
     pub visual_rotation: Quat,
+    /// The surface type of the floor while walking
+    #[reflect(skip)]
+    pub floor_surface: Option<SurfaceType>,
+    /// A jump that was pressed in a tick without a fixed step
+    #[reflect(skip)]
+    jump_requested: bool,
 }
 
 #[derive(Debug, Component)]
@@ -64,6 +73,8 @@ pub struct CharacterControllerComponent {
 #[reflect(editor)]
 pub struct CharacterConfigComponent {
     pub max_movement_speed: f32,
+    /// How fast the walking speed changes, scaled by the friction of the floor surface
+    pub walk_acceleration: f32,
     pub gravity_dir: Vec3,
     pub gravity_strength: f32,
     pub max_walkable_slope: f32,
@@ -84,6 +95,7 @@ impl Default for CharacterConfigComponent {
     fn default() -> Self {
         Self {
             max_movement_speed: 500.0,
+            walk_acceleration: 4000.0,
             gravity_dir: -Vec3::Z,
             gravity_strength: 981.0,
             max_walkable_slope: 50.0,
@@ -110,6 +122,8 @@ pub enum MovementHit {
 pub struct FloorHit {
     pub entity: Entity,
     pub impact_location: Vec3,
+    /// Can be looked up in the `SurfaceTable` for footsteps and friction
+    pub surface_type: SurfaceType,
 }
 
 pub struct StepUpResult {
@@ -118,18 +132,28 @@ pub struct StepUpResult {
 
 fn do_walking(
     movement: &mut MovementQueryItem,
-    input: &Input,
+    target_velocity: Vec3,
+    jump: bool,
     dt: f32,
     query: &Query<&PhysicsComponent>,
+    surfaces: &SurfaceTable,
     api: &UnrealApi,
 ) -> Option<MovementState> {
-    if let Some(hit) = movement.find_floor(api) {
+    let floor = movement.find_floor(api);
+    movement.controller.floor_surface = floor.as_ref().map(|hit| hit.surface_type);
+    if let Some(hit) = floor {
+        // Slippery surfaces take longer to speed up and to stop
+        let friction = surfaces.get(hit.surface_type).friction;
+        let max_change = movement.config.walk_acceleration * friction * dt;
+        let change = target_velocity - movement.controller.horizontal_velocity;
+        movement.controller.horizontal_velocity += change.clamp_length_max(max_change);
+
         movement.controller.vertical_velocity = Vec3::ZERO;
         movement.transform.position.z = hit.impact_location.z
             + movement.physics.get_collision_shape().extent().z
             + movement.config.walk_offset;
 
-        if input.is_action_pressed(PlayerInput::JUMP) {
+        if jump {
             movement.controller.vertical_velocity.z += movement.config.jump_velocity;
             return Some(MovementState::Falling);
         }
@@ -144,14 +168,18 @@ fn do_walking(
 
 fn do_falling(
     movement: &mut MovementQueryItem,
-    input: &Input,
+    target_velocity: Vec3,
+    jump: bool,
     dt: f32,
     api: &UnrealApi,
 ) -> Option<MovementState> {
+    // Steering in the air doesn't depend on a surface
+    movement.controller.horizontal_velocity = target_velocity;
+    movement.controller.floor_surface = None;
     let is_downwards = movement.controller.vertical_velocity.z < 0.0;
     if movement.find_floor(api).is_some() && is_downwards {
         Some(MovementState::Walking)
-    } else if input.is_action_pressed(PlayerInput::JUMP) {
+    } else if jump {
         Some(MovementState::Gliding)
     } else {
         movement.controller.vertical_velocity +=
@@ -163,15 +191,19 @@ fn do_falling(
 
 fn do_gliding(
     movement: &mut MovementQueryItem,
-    input: &Input,
+    target_velocity: Vec3,
+    jump: bool,
     dt: f32,
     api: &UnrealApi,
 ) -> Option<MovementState> {
+    // Steering in the air doesn't depend on a surface
+    movement.controller.horizontal_velocity = target_velocity;
+    movement.controller.floor_surface = None;
     let is_downwards = movement.controller.vertical_velocity.z < 0.0;
 
     if movement.find_floor(api).is_some() && is_downwards {
         Some(MovementState::Walking)
-    } else if input.is_action_pressed(PlayerInput::JUMP) {
+    } else if jump {
         Some(MovementState::Falling)
     } else {
         movement.controller.vertical_velocity += movement.config.gravity_dir
@@ -395,6 +427,7 @@ impl<'w> MovementQueryItem<'w> {
                 Some(FloorHit {
                     entity: hit.entity,
                     impact_location: hit.impact_location,
+                    surface_type: hit.surface_type,
                 })
             } else {
                 None
@@ -404,9 +437,11 @@ impl<'w> MovementQueryItem<'w> {
 }
 
 fn character_control_system(
+    _game_thread: NonSend<GameThread>,
     input: Res<Input>,
-    frame: Res<Frame>,
+    fixed: Res<FixedTime>,
     api: Res<UnrealApi>,
+    surfaces: Res<SurfaceTable>,
     mut query: Query<MovementQuery>,
     phys: Query<&PhysicsComponent>,
 ) {
@@ -416,21 +451,33 @@ fn character_control_system(
         .unwrap_or(0.0);
     let right = input.get_axis_value(PlayerInput::MOVE_RIGHT).unwrap_or(0.0);
     let player_input = Vec3::new(forward, right, 0.0).normalize_or_zero();
+    // Input is sampled once per tick, only the first step of the tick sees the press
+    let jump_pressed = fixed.steps_this_tick() == 1 && input.is_action_pressed(PlayerInput::JUMP);
+    let dt = fixed.step();
 
     for mut movement in query.iter_mut() {
         let mut input_dir = movement.controller.camera_view * player_input;
         input_dir.z = 0.0;
-        movement.controller.horizontal_velocity =
-            input_dir.normalize_or_zero() * movement.config.max_movement_speed;
+        let target_velocity = input_dir.normalize_or_zero() * movement.config.max_movement_speed;
+
+        let jump = std::mem::take(&mut movement.controller.jump_requested) || jump_pressed;
 
         if let Some(new_position) = movement.resolve_possible_penetration(api) {
             movement.transform.position = new_position;
         }
 
         let new_state = match movement.controller.movement_state {
-            MovementState::Walking => do_walking(&mut movement, &input, frame.dt, &phys, api),
-            MovementState::Falling => do_falling(&mut movement, &input, frame.dt, api),
-            MovementState::Gliding => do_gliding(&mut movement, &input, frame.dt, api),
+            MovementState::Walking => do_walking(
+                &mut movement,
+                target_velocity,
+                jump,
+                dt,
+                &phys,
+                &surfaces,
+                api,
+            ),
+            MovementState::Falling => do_falling(&mut movement, target_velocity, jump, dt, api),
+            MovementState::Gliding => do_gliding(&mut movement, target_velocity, jump, dt, api),
         };
 
         if let Some(new_state) = new_state {
@@ -439,6 +486,19 @@ fn character_control_system(
     }
 }
 
+/// Keeps jumps that were pressed in a tick without a fixed step for the next step
+fn buffer_jump_input(
+    input: Res<Input>,
+    fixed: Res<FixedTime>,
+    mut query: Query<&mut CharacterControllerComponent>,
+) {
+    if fixed.steps_this_tick() == 0 && input.is_action_pressed(PlayerInput::JUMP) {
+        for mut controller in &mut query {
+            controller.jump_requested = true;
+        }
+    }
+}
+
 fn update_movement_component(
     mut query: Query<(&CharacterControllerComponent, &mut MovementComponent)>,
 ) {
@@ -452,6 +512,10 @@ fn update_movement_component(
 pub struct MovementPlugin;
 
 impl Plugin for MovementPlugin {
+    fn dependencies(&self) -> Vec<&'static str> {
+        vec![plugin_name::<CorePlugin>()]
+    }
+
     fn build(&self, module: &mut Module) {
         register_components! {
             MovementComponent,
@@ -459,11 +523,19 @@ impl Plugin for MovementPlugin {
             => module
         };
 
-        module.add_system_set_to_stage(
-            CoreStage::Update,
-            SystemSet::new()
-                .with_system(character_control_system)
-                .with_system(update_movement_component.after(character_control_system)),
-        );
+        module
+            .add_system_set_to_stage(
+                CoreStage::FixedUpdate,
+                SystemSet::new()
+                    .with_run_criteria(run_if_not_paused)
+                    .with_system(character_control_system)
+                    .with_system(update_movement_component.after(character_control_system)),
+            )
+            .add_system_set_to_stage(
+                CoreStage::Update,
+                SystemSet::new()
+                    .with_run_criteria(run_if_not_paused)
+                    .with_system(buffer_jump_input),
+            );
     }
 }
@@ -0,0 +1,110 @@
+use darling::FromField;
+use proc_macro2::Span;
+use quote::quote;
+use syn::*;
+
+use crate::reflect::ReflectField;
+
+pub fn event_derive(ast: &DeriveInput) -> proc_macro2::TokenStream {
+    if let Data::Struct(data) = &ast.data {
+        let literal_name = LitStr::new(&ast.ident.to_string(), Span::call_site());
+        let reflect_struct_ident = Ident::new(&format!("{}Reflect", ast.ident), Span::call_site());
+        let struct_ident = &ast.ident;
+
+        let fields: Vec<ReflectField> = data
+            .fields
+            .iter()
+            .map(|field| ReflectField::from_field(field).unwrap())
+            .collect();
+
+        let reflect_fields: Vec<&ReflectField> =
+            fields.iter().filter(|field| !field.skip).collect();
+        let skipped_idents: Vec<&Ident> = fields
+            .iter()
+            .filter(|field| field.skip)
+            .map(|field| field.ident.as_ref().unwrap())
+            .collect();
+
+        let number_of_fields = reflect_fields.len() as u32;
+        let field_indices: Vec<u32> = (0..number_of_fields).collect();
+        let value_indices: Vec<usize> = (0..reflect_fields.len()).collect();
+        let field_types: Vec<&'_ Type> = reflect_fields.iter().map(|field| &field.ty).collect();
+        let field_idents: Vec<&'_ Ident> = reflect_fields
+            .iter()
+            .map(|field| field.ident.as_ref().unwrap())
+            .collect();
+        let field_names: Vec<LitStr> = field_idents
+            .iter()
+            .map(|ident| LitStr::new(&ident.to_string(), Span::call_site()))
+            .collect();
+
+        quote! {
+            pub struct #reflect_struct_ident;
+
+            impl unreal_api::registry::ReflectDyn for #reflect_struct_ident {
+                fn name(&self) -> &'static str {
+                    #literal_name
+                }
+
+                fn number_of_fields(&self) -> u32 {
+                    #number_of_fields
+                }
+
+                fn get_field_type(&self, idx: u32) -> Option<unreal_api::registry::ReflectType> {
+                    match idx {
+                        #(
+                            #field_indices => Some(<#field_types as unreal_api::registry::ReflectStatic>::TYPE),
+                        )*
+                        _ => None
+                    }
+                }
+
+                fn get_field_name(&self, idx: u32) -> Option<&'static str> {
+                    match idx {
+                        #(
+                            #field_indices => Some(#field_names),
+                        )*
+                        _ => None
+                    }
+                }
+
+                fn get_value(&self) -> unreal_api::registry::ReflectValue {
+                    unreal_api::registry::ReflectValue::Composite
+                }
+            }
+
+            impl unreal_api::event::UnrealEvent for #struct_ident {
+                fn to_values(&self) -> Vec<unreal_api::registry::ReflectValue> {
+                    vec![
+                        #(
+                            unreal_api::registry::ReflectDyn::get_value(&self.#field_idents),
+                        )*
+                    ]
+                }
+
+                #[allow(unused_variables)]
+                fn from_values(values: &[unreal_api::registry::ReflectValue]) -> Option<Self> {
+                    Some(Self {
+                        #(
+                            #field_idents: <#field_types as unreal_api::registry::FromReflectValue>::from_value(values.get(#value_indices)?)?,
+                        )*
+                        #(
+                            #skipped_idents: Default::default(),
+                        )*
+                    })
+                }
+            }
+
+            impl unreal_api::module::InsertReflectionStruct for #struct_ident {
+                fn insert(registry: &mut unreal_api::module::ReflectionRegistry) {
+                    registry.reflect.insert(
+                        <#struct_ident as unreal_api::TypeUuid>::TYPE_UUID,
+                        Box::new(#reflect_struct_ident),
+                    );
+                }
+            }
+        }
+    } else {
+        panic!("Only structs are currently supported in `unreal_api_derive`")
+    }
+}
@@ -0,0 +1,114 @@
+use proc_macro2::Span;
+use quote::quote;
+use syn::*;
+
+pub fn unreal_function(function: &ItemFn) -> proc_macro2::TokenStream {
+    let vis = &function.vis;
+    let fn_ident = &function.sig.ident;
+    let literal_name = LitStr::new(&fn_ident.to_string(), Span::call_site());
+
+    if function.sig.inputs.len() < 2 {
+        return Error::new_spanned(
+            &function.sig,
+            "An unreal function needs to take `&mut World` and `Entity` as the first two parameters",
+        )
+        .to_compile_error();
+    }
+
+    let mut argument_names: Vec<LitStr> = Vec::new();
+    let mut argument_types: Vec<&Type> = Vec::new();
+    for input in function.sig.inputs.iter().skip(2) {
+        match input {
+            FnArg::Typed(PatType { pat, ty, .. }) => {
+                let name = match pat.as_ref() {
+                    Pat::Ident(pat_ident) => pat_ident.ident.to_string(),
+                    _ => {
+                        return Error::new_spanned(pat, "Only plain identifiers are supported")
+                            .to_compile_error()
+                    }
+                };
+                argument_names.push(LitStr::new(&name, Span::call_site()));
+                argument_types.push(ty.as_ref());
+            }
+            FnArg::Receiver(receiver) => {
+                return Error::new_spanned(receiver, "Methods can not be unreal functions")
+                    .to_compile_error()
+            }
+        }
+    }
+
+    let number_of_arguments = argument_types.len();
+    let argument_indices: Vec<usize> = (0..number_of_arguments).collect();
+
+    let argument_locals: Vec<Ident> = argument_indices
+        .iter()
+        .map(|idx| Ident::new(&format!("argument_{}", idx), Span::call_site()))
+        .collect();
+
+    let (return_type, call) = match &function.sig.output {
+        ReturnType::Default => (
+            quote!(None),
+            quote! {
+                #fn_ident(world, entity, #(#argument_locals,)*);
+                Ok(None)
+            },
+        ),
+        ReturnType::Type(_, ty) => (
+            quote!(Some(<#ty as unreal_api::registry::ReflectStatic>::TYPE)),
+            quote! {
+                let result = #fn_ident(world, entity, #(#argument_locals,)*);
+                Ok(Some(unreal_api::registry::ReflectDyn::get_value(&result)))
+            },
+        ),
+    };
+
+    quote! {
+        #function
+
+        // Lives in the type namespace, so it doesn't collide with the function itself
+        #[allow(non_camel_case_types)]
+        #[derive(Default)]
+        #vis struct #fn_ident {}
+
+        impl unreal_api::function::UnrealFunction for #fn_ident {
+            fn name(&self) -> &'static str {
+                #literal_name
+            }
+
+            fn arguments(&self) -> &'static [unreal_api::function::FunctionArgument] {
+                const ARGUMENTS: &[unreal_api::function::FunctionArgument] = &[
+                    #(
+                        unreal_api::function::FunctionArgument {
+                            name: #argument_names,
+                            ty: <#argument_types as unreal_api::registry::ReflectStatic>::TYPE,
+                        },
+                    )*
+                ];
+                ARGUMENTS
+            }
+
+            fn return_type(&self) -> Option<unreal_api::registry::ReflectType> {
+                #return_type
+            }
+
+            fn call(
+                &self,
+                world: &mut unreal_api::World,
+                entity: unreal_api::Entity,
+                arguments: &[unreal_api::registry::ReflectValue],
+            ) -> Result<Option<unreal_api::registry::ReflectValue>, unreal_api::function::FunctionCallError> {
+                if arguments.len() != #number_of_arguments {
+                    return Err(unreal_api::function::FunctionCallError::WrongNumberOfArguments {
+                        expected: #number_of_arguments,
+                        found: arguments.len(),
+                    });
+                }
+                #(
+                    let #argument_locals = <#argument_types as unreal_api::registry::FromReflectValue>::from_value(&arguments[#argument_indices])
+                        .ok_or(unreal_api::function::FunctionCallError::WrongArgumentType(#argument_indices))?;
+                )*
+                #call
+            }
+        }
+    }
+}
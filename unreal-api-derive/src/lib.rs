@@ -1,5 +1,7 @@
 use syn::DeriveInput;
 
+mod event;
+mod function;
 mod reflect;
 mod type_uuid;
 use quote::quote;
@@ -16,3 +18,26 @@ pub fn component_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStre
     }
     .into()
 }
+
+#[proc_macro_derive(UnrealEvent, attributes(uuid, reflect))]
+pub fn unreal_event_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast: DeriveInput = syn::parse(input).unwrap();
+
+    let event = event::event_derive(&ast);
+    let type_uuid = type_uuid::type_uuid_derive(&ast);
+    quote! {
+        #event
+        #type_uuid
+    }
+    .into()
+}
+
+/// Makes a function callable from blueprints, see `unreal_api::function::UnrealFunction`.
+#[proc_macro_attribute]
+pub fn unreal_function(
+    _attr: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let function: syn::ItemFn = syn::parse(input).unwrap();
+    function::unreal_function(&function).into()
+}
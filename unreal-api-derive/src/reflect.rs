@@ -1,6 +1,6 @@
 extern crate proc_macro;
 
-use darling::{FromDeriveInput, FromField};
+use darling::{util::Flag, FromDeriveInput, FromField};
 use proc_macro2::Span;
 use quote::quote;
 use syn::*;
@@ -8,21 +8,32 @@ use syn::*;
 #[derive(Debug, FromField)]
 #[darling(attributes(reflect))]
 pub struct ReflectField {
-    ident: Option<Ident>,
-    ty: Type,
+    pub(crate) ident: Option<Ident>,
+    pub(crate) ty: Type,
     #[darling(default)]
-    skip: bool,
+    pub(crate) skip: bool,
 }
 #[derive(Debug, FromDeriveInput)]
 #[darling(attributes(reflect))]
-pub struct ReflectEditor {
+pub struct ReflectAttributes {
     #[darling(default)]
     editor: bool,
+    /// The component implements `Default`, so it can be inserted without a value for every field
+    default: Flag,
+    /// The component implements `Serialize` and `Deserialize`. Needs the `serde` feature of
+    /// `unreal-api`.
+    serde: Flag,
 }
 
 pub fn reflect_derive(ast: &DeriveInput) -> proc_macro2::TokenStream {
-    let is_editor_component =
-        ReflectEditor::from_derive_input(ast).map_or(false, |reflect| reflect.editor);
+    let reflect_attrs = ReflectAttributes::from_derive_input(ast).ok();
+    let is_editor_component = reflect_attrs.as_ref().is_some_and(|reflect| reflect.editor);
+    let is_default_component = reflect_attrs
+        .as_ref()
+        .is_some_and(|reflect| reflect.default.is_present());
+    let is_serde_component = reflect_attrs
+        .as_ref()
+        .is_some_and(|reflect| reflect.serde.is_present());
 
     if let Data::Struct(data) = &ast.data {
         let literal_name = LitStr::new(&ast.ident.to_string(), Span::call_site());
@@ -77,11 +88,6 @@ pub fn reflect_derive(ast: &DeriveInput) -> proc_macro2::TokenStream {
                         _ => None
                     }
                 }
-                fn has_component(&self, world: &unreal_api::World, entity: unreal_api::Entity) -> bool {
-                    world
-                        .get_entity(entity)
-                        .and_then(|entity_ref| entity_ref.get::<#self_ty>()).is_some()
-                }
                 fn get_field_value(&self, world: &unreal_api::World, entity: unreal_api::Entity, idx: u32) -> Option<unreal_api::registry::ReflectValue> {
                     world
                         .get_entity(entity)
@@ -96,6 +102,85 @@ pub fn reflect_derive(ast: &DeriveInput) -> proc_macro2::TokenStream {
                             Some(ty)
                         })
                 }
+                fn set_field_value(
+                    &self,
+                    world: &mut unreal_api::World,
+                    entity: unreal_api::Entity,
+                    idx: u32,
+                    value: &unreal_api::registry::ReflectValue,
+                ) -> bool {
+                    use unreal_api::registry::FromReflectValue;
+                    match idx {
+                        #(
+                            #field_indices => {
+                                let Some(value) = <#field_types as FromReflectValue>::from_value(value) else {
+                                    return false;
+                                };
+                                let Some(mut component) = world.get_mut::<#self_ty>(entity) else {
+                                    return false;
+                                };
+                                component.#field_idents = value;
+                                true
+                            }
+                        )*
+                        _ => false,
+                    }
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let build_component = if is_default_component {
+            quote! {
+                let mut component = <#struct_ident as Default>::default();
+                #(
+                    if let Some(value) = values.get(#field_indices as usize).and_then(Option::as_ref) {
+                        match <#field_types as FromReflectValue>::from_value(value) {
+                            Some(value) => component.#field_idents = value,
+                            None => return false,
+                        }
+                    }
+                )*
+            }
+        } else if is_editor_component {
+            quote! {
+                let component = #struct_ident {
+                    #(
+                        #field_idents: match values
+                            .get(#field_indices as usize)
+                            .and_then(Option::as_ref)
+                            .and_then(<#field_types as FromReflectValue>::from_value)
+                        {
+                            Some(value) => value,
+                            None => return false,
+                        },
+                    )*
+                };
+            }
+        } else {
+            quote!()
+        };
+
+        let insert_method = if is_default_component || is_editor_component {
+            quote! {
+                #[allow(unused_imports, unused_variables)]
+                fn insert_component(
+                    &self,
+                    world: &mut unreal_api::World,
+                    entity: unreal_api::Entity,
+                    values: &[Option<unreal_api::registry::ReflectValue>],
+                ) -> bool {
+                    use unreal_api::registry::FromReflectValue;
+                    #build_component
+                    match world.get_entity_mut(entity) {
+                        Some(mut entity_mut) => {
+                            entity_mut.insert(component);
+                            true
+                        }
+                        None => false,
+                    }
+                }
             }
         } else {
             quote!()
@@ -113,7 +198,7 @@ pub fn reflect_derive(ast: &DeriveInput) -> proc_macro2::TokenStream {
                         use unreal_api::editor_component::GetEditorComponentValue;
                         let component = #struct_ident {
                             #(
-                                #field_idents: #field_types::get(actor, uuid, #field_names).expect(#field_names),
+                                #field_idents: <#field_types as GetEditorComponentValue>::get(actor, uuid, #field_names).expect(#field_names),
                             )*
                         };
                         commands.insert(component);
@@ -136,6 +221,38 @@ pub fn reflect_derive(ast: &DeriveInput) -> proc_macro2::TokenStream {
             quote!()
         };
 
+        let serde_methods = if is_serde_component {
+            quote! {
+                fn serialize_component(
+                    &self,
+                    world: &unreal_api::World,
+                    entity: unreal_api::Entity,
+                ) -> Option<unreal_api::registry::SerdeValue> {
+                    let component = world.get_entity(entity)?.get::<#self_ty>()?;
+                    unreal_api::serde_json::to_value(component).ok()
+                }
+                fn deserialize_component(
+                    &self,
+                    world: &mut unreal_api::World,
+                    entity: unreal_api::Entity,
+                    value: unreal_api::registry::SerdeValue,
+                ) -> bool {
+                    let Ok(component) = unreal_api::serde_json::from_value::<#self_ty>(value) else {
+                        return false;
+                    };
+                    match world.get_entity_mut(entity) {
+                        Some(mut entity_mut) => {
+                            entity_mut.insert(component);
+                            true
+                        }
+                        None => false,
+                    }
+                }
+            }
+        } else {
+            quote!()
+        };
+
         quote! {
             pub struct #reflect_struct_ident;
 
@@ -150,6 +267,23 @@ pub fn reflect_derive(ast: &DeriveInput) -> proc_macro2::TokenStream {
 
                 #field_methods
 
+                fn has_component(&self, world: &unreal_api::World, entity: unreal_api::Entity) -> bool {
+                    world
+                        .get_entity(entity)
+                        .and_then(|entity_ref| entity_ref.get::<#self_ty>()).is_some()
+                }
+
+                fn remove_component(&self, world: &mut unreal_api::World, entity: unreal_api::Entity) -> bool {
+                    world
+                        .get_entity_mut(entity)
+                        .and_then(|mut entity_mut| entity_mut.remove::<#self_ty>())
+                        .is_some()
+                }
+
+                #insert_method
+
+                #serde_methods
+
                 fn get_value(&self) -> unreal_api::registry::ReflectValue {
                     unreal_api::registry::ReflectValue::Composite
                 }
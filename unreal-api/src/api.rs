@@ -3,10 +3,10 @@ use std::collections::HashMap;
 use crate::ffi;
 use glam::{Quat, Vec3};
 
-use crate::core::ActorPtr;
+use crate::core::{ActorPtr, Primitive, UnrealPtr};
 use crate::ecs::entity::Entity;
 use crate::module::bindings;
-use crate::physics::CollisionShape;
+use crate::physics::{CollisionShape, PhysicalMaterial, SurfaceType};
 
 #[derive(Default)]
 pub struct UnrealApi {
@@ -42,6 +42,9 @@ pub struct SweepHit {
     /// Normal of the hit in world space, for the object that was swept.
     pub normal: Vec3,
     pub start_in_penentration: bool,
+    /// `None` if the surface has no physical material
+    pub physical_material: Option<PhysicalMaterial>,
+    pub surface_type: SurfaceType,
 }
 
 #[derive(Default)]
@@ -56,12 +59,35 @@ impl LineTraceParams {
     }
 }
 
+#[derive(Default)]
+pub struct OverlapParams {
+    pub ignored_entities: Vec<Entity>,
+}
+
+impl OverlapParams {
+    pub fn add_ignored_entity(mut self, entity: Entity) -> Self {
+        self.ignored_entities.push(entity);
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct OverlapHit {
+    /// The entity that overlaps with the shape
+    pub entity: Entity,
+    /// The primitive component of the entity's actor that overlaps with the shape
+    pub primitive: UnrealPtr<Primitive>,
+}
+
 #[derive(Debug)]
 pub struct LineTraceHit {
     /// The entity that was hit
     pub entity: Entity,
     pub location: Vec3,
     pub normal: Vec3,
+    /// `None` if the surface has no physical material
+    pub physical_material: Option<PhysicalMaterial>,
+    pub surface_type: SurfaceType,
 }
 
 impl UnrealApi {
@@ -112,6 +138,8 @@ impl UnrealApi {
                     penetration_depth: hit.pentration_depth,
                     start_in_penentration: hit.start_penetrating == 1,
                     impact_normal: hit.impact_normal.into(),
+                    physical_material: PhysicalMaterial::from_raw(hit.physical_material),
+                    surface_type: SurfaceType::from_ffi(hit.surface_type),
                 })
             } else {
                 None
@@ -119,6 +147,54 @@ impl UnrealApi {
         }
     }
 
+    /// Returns everything that overlaps with the shape. Actors without an entity are skipped. An
+    /// entity shows up once for every primitive component that overlaps.
+    pub fn overlap_multi(
+        &self,
+        position: Vec3,
+        rotation: Quat,
+        collision_shape: CollisionShape,
+        max_results: usize,
+        params: OverlapParams,
+    ) -> Vec<OverlapHit> {
+        let ignored_actors: Vec<_> = params
+            .ignored_entities
+            .iter()
+            .filter_map(|entity| self.entity_to_actor.get(entity))
+            .map(|actor| actor.0)
+            .collect();
+        let params = ffi::LineTraceParams {
+            ignored_actors: ignored_actors.as_ptr(),
+            ignored_actors_len: ignored_actors.len(),
+        };
+        let mut overlaps: Vec<ffi::OverlapResult> = Vec::new();
+        overlaps.resize_with(max_results, Default::default);
+        unsafe {
+            let len = (bindings().physics_fns.overlap_multi)(
+                collision_shape.into(),
+                position.into(),
+                rotation.into(),
+                params,
+                max_results,
+                overlaps.as_mut_ptr(),
+            );
+            overlaps.truncate(len as usize);
+        }
+        overlaps
+            .into_iter()
+            .filter_map(|overlap| {
+                let entity = self
+                    .actor_to_entity
+                    .get(&ActorPtr(overlap.actor))
+                    .copied()?;
+                Some(OverlapHit {
+                    entity,
+                    primitive: UnrealPtr::from_raw(overlap.primtive),
+                })
+            })
+            .collect()
+    }
+
     pub fn line_trace(
         &self,
         start: Vec3,
@@ -148,6 +224,8 @@ impl UnrealApi {
                     entity,
                     location: hit.location.into(),
                     normal: hit.normal.into(),
+                    physical_material: PhysicalMaterial::from_raw(hit.physical_material),
+                    surface_type: SurfaceType::from_ffi(hit.surface_type),
                 })
             } else {
                 None
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use bevy_ecs::prelude::*;
+use unreal_reflect::registry::{AssetRef, AssetType, SoftAssetRef};
+
+use crate::{ffi, module::bindings};
+
+/// Loads an asset synchronously. This will block the game thread until the asset is loaded, prefer
+/// [`Assets::load`] for anything that is not tiny.
+pub fn load_asset<T: AssetType>(asset: &SoftAssetRef<T>) -> Option<AssetRef<T>> {
+    if asset.is_empty() {
+        return None;
+    }
+    let ptr = unsafe {
+        (bindings().asset_fns.load_asset)(ffi::Utf8Str::from(asset.path.as_str()), T::TYPE)
+    };
+    if ptr.is_null() {
+        None
+    } else {
+        Some(AssetRef::from_raw(ptr))
+    }
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AssetHandle<T> {
+    pub id: u64,
+    _m: std::marker::PhantomData<T>,
+}
+
+impl<T> Clone for AssetHandle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for AssetHandle<T> {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AssetLoadState {
+    Loading,
+    Loaded,
+    Failed,
+}
+
+struct LoadedAsset {
+    state: AssetLoadState,
+    ptr: *mut ffi::UObjectOpague,
+}
+
+/// Sent once an asynchronous load that was started with [`Assets::load`] has finished. Use
+/// [`Assets::get`] to retrieve the asset. Loads that fail to start are reported on the next tick.
+#[derive(Debug, Copy, Clone)]
+pub struct AssetLoadedEvent {
+    pub id: u64,
+    pub success: bool,
+}
+
+impl AssetLoadedEvent {
+    pub fn is<T>(&self, handle: AssetHandle<T>) -> bool {
+        self.id == handle.id
+    }
+}
+
+/// Keeps track of all the assets that were loaded asynchronously from Rust. The assets will stay
+/// loaded until [`Assets::unload`] is called.
+#[derive(Default)]
+pub struct Assets {
+    next_id: u64,
+    assets: HashMap<u64, LoadedAsset>,
+    /// Loads that failed to start, their events are sent by [`send_failed_asset_loads`]
+    failed: Vec<u64>,
+}
+unsafe impl Send for Assets {}
+unsafe impl Sync for Assets {}
+
+impl Assets {
+    pub fn load<T: AssetType>(&mut self, asset: &SoftAssetRef<T>) -> AssetHandle<T> {
+        self.next_id += 1;
+        let handle = AssetHandle {
+            id: self.next_id,
+            _m: Default::default(),
+        };
+
+        let started = !asset.is_empty()
+            && unsafe {
+                (bindings().asset_fns.load_asset_async)(
+                    ffi::Utf8Str::from(asset.path.as_str()),
+                    T::TYPE,
+                    handle.id,
+                )
+            } == 1;
+
+        let state = if started {
+            AssetLoadState::Loading
+        } else {
+            log::warn!("Unable to load asset '{}'", asset.path);
+            self.failed.push(handle.id);
+            AssetLoadState::Failed
+        };
+        self.assets.insert(
+            handle.id,
+            LoadedAsset {
+                state,
+                ptr: std::ptr::null_mut(),
+            },
+        );
+        handle
+    }
+
+    pub fn state<T>(&self, handle: AssetHandle<T>) -> Option<AssetLoadState> {
+        self.assets.get(&handle.id).map(|asset| asset.state)
+    }
+
+    pub fn is_loaded<T>(&self, handle: AssetHandle<T>) -> bool {
+        self.state(handle) == Some(AssetLoadState::Loaded)
+    }
+
+    pub fn get<T>(&self, handle: AssetHandle<T>) -> Option<AssetRef<T>> {
+        self.assets
+            .get(&handle.id)
+            .filter(|asset| asset.state == AssetLoadState::Loaded)
+            .map(|asset| AssetRef::from_raw(asset.ptr))
+    }
+
+    /// Releases the handle. Unreal is free to garbage collect the asset once nothing else is
+    /// referencing it.
+    pub fn unload<T>(&mut self, handle: AssetHandle<T>) {
+        if self.assets.remove(&handle.id).is_some() {
+            unsafe {
+                (bindings().asset_fns.unload_asset)(handle.id);
+            }
+        }
+    }
+
+    pub(crate) fn on_loaded(&mut self, id: u64, ptr: *mut ffi::UObjectOpague) {
+        // The handle might have been unloaded before the load finished
+        if let Some(asset) = self.assets.get_mut(&id) {
+            asset.state = if ptr.is_null() {
+                AssetLoadState::Failed
+            } else {
+                AssetLoadState::Loaded
+            };
+            asset.ptr = ptr;
+        }
+    }
+}
+
+pub fn send_failed_asset_loads(
+    mut assets: ResMut<Assets>,
+    mut events: EventWriter<AssetLoadedEvent>,
+) {
+    for id in assets.failed.drain(..) {
+        events.send(AssetLoadedEvent { id, success: false });
+    }
+}
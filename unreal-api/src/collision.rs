@@ -0,0 +1,184 @@
+use bevy_ecs::prelude::*;
+use glam::Vec3;
+
+use crate::{
+    api::UnrealApi,
+    core::{
+        ActorComponent, ActorDestroyEvent, ActorPtr, OnActorBeginOverlapEvent,
+        OnActorEndOverlapEvent, Primitive, UnrealPtr,
+    },
+    module::{bindings, GameThread},
+    Component,
+};
+
+/// Actors with this component send [`OnActorBeginOverlapEvent`] and [`OnActorEndOverlapEvent`].
+/// The primitive components of the actor still need to have "Generate Overlap Events" enabled.
+/// The events are sent per pair of primitive components, two actors with several overlapping
+/// primitives send several events.
+#[derive(Default, Debug, Component)]
+#[uuid = "4d4b8750-2d18-4a2f-88cd-65c1bc9f2f70"]
+#[reflect(editor)]
+pub struct OverlapEvents;
+
+/// Actors with this component send [`ActorHitEvent`](crate::core::ActorHitEvent). The primitive
+/// components of the actor still need to have "Simulation Generates Hit Events" enabled.
+/// The events are sent per pair of primitive components, a single impact between actors with
+/// several primitives can send several events in the same tick.
+#[derive(Default, Debug, Component)]
+#[uuid = "4bd0b5c6-0099-4239-bcdd-6280fe373978"]
+#[reflect(editor)]
+pub struct HitEvents;
+
+#[derive(Debug)]
+pub struct OverlapSweep {
+    pub location: Vec3,
+    pub normal: Vec3,
+    pub impact_location: Vec3,
+    pub impact_normal: Vec3,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Overlap {
+    pub other: ActorPtr,
+    /// `None` if the other actor doesn't have an entity
+    pub other_entity: Option<Entity>,
+    pub component: UnrealPtr<Primitive>,
+    pub other_component: UnrealPtr<Primitive>,
+}
+
+/// Keeps track of everything that currently overlaps with this entity. Needs an
+/// [`OverlapEvents`] component to receive updates.
+#[derive(Default, Debug, Component)]
+#[uuid = "4e43b82a-4b9a-4d7e-a28b-32802ad31a8b"]
+pub struct CurrentOverlaps {
+    #[reflect(skip)]
+    overlaps: Vec<Overlap>,
+}
+
+impl CurrentOverlaps {
+    pub fn iter(&self) -> impl Iterator<Item = &Overlap> {
+        self.overlaps.iter()
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.overlaps
+            .iter()
+            .any(|overlap| overlap.other_entity == Some(entity))
+    }
+
+    pub fn contains_actor(&self, actor: ActorPtr) -> bool {
+        self.overlaps.iter().any(|overlap| overlap.other == actor)
+    }
+
+    pub fn len(&self) -> usize {
+        self.overlaps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overlaps.is_empty()
+    }
+}
+
+pub(crate) fn update_current_overlaps(
+    mut begin_events: EventReader<OnActorBeginOverlapEvent>,
+    mut end_events: EventReader<OnActorEndOverlapEvent>,
+    mut query: Query<&mut CurrentOverlaps>,
+) {
+    for event in begin_events.iter() {
+        if let Some(mut current) = event
+            .overlapped_entity
+            .and_then(|entity| query.get_mut(entity).ok())
+        {
+            current.overlaps.push(Overlap {
+                other: event.other,
+                other_entity: event.other_entity,
+                component: event.overlapped_component,
+                other_component: event.other_component,
+            });
+        }
+    }
+    for event in end_events.iter() {
+        if let Some(mut current) = event
+            .overlapped_entity
+            .and_then(|entity| query.get_mut(entity).ok())
+        {
+            if let Some(idx) = current.overlaps.iter().position(|overlap| {
+                overlap.other == event.other
+                    && overlap.component.ptr == event.overlapped_component.ptr
+                    && overlap.other_component.ptr == event.other_component.ptr
+            }) {
+                current.overlaps.swap_remove(idx);
+            }
+        }
+    }
+}
+
+/// Destroyed actors don't always end their overlaps, this removes them so that no dangling
+/// actors are left behind.
+pub(crate) fn prune_destroyed_overlaps(
+    mut events: EventReader<ActorDestroyEvent>,
+    mut query: Query<&mut CurrentOverlaps>,
+) {
+    for event in events.iter() {
+        for mut current in &mut query {
+            if current.contains_actor(event.actor) {
+                current
+                    .overlaps
+                    .retain(|overlap| overlap.other != event.actor);
+            }
+        }
+    }
+}
+
+/// The marker or the actor might be added after the other one
+type NeedsRegistration<T> = (With<T>, Or<(Added<T>, Added<ActorComponent>)>);
+
+pub(crate) fn register_overlap_events(
+    _game_thread: NonSend<GameThread>,
+    query: Query<&ActorComponent, NeedsRegistration<OverlapEvents>>,
+) {
+    for actor in &query {
+        unsafe {
+            (bindings().actor_fns.register_actor_on_overlap)(actor.actor.0);
+        }
+    }
+}
+
+pub(crate) fn register_hit_events(
+    _game_thread: NonSend<GameThread>,
+    query: Query<&ActorComponent, NeedsRegistration<HitEvents>>,
+) {
+    for actor in &query {
+        unsafe {
+            (bindings().actor_fns.register_actor_on_hit)(actor.actor.0);
+        }
+    }
+}
+
+pub(crate) fn unregister_overlap_events(
+    _game_thread: NonSend<GameThread>,
+    api: Res<UnrealApi>,
+    removed: RemovedComponents<OverlapEvents>,
+) {
+    for entity in removed.iter() {
+        if let Some(actor) = api.entity_to_actor.get(&entity) {
+            unsafe {
+                (bindings().actor_fns.unregister_actor_on_overlap)(actor.0);
+            }
+        }
+    }
+}
+
+pub(crate) fn unregister_hit_events(
+    _game_thread: NonSend<GameThread>,
+    api: Res<UnrealApi>,
+    removed: RemovedComponents<HitEvents>,
+) {
+    for entity in removed.iter() {
+        if let Some(actor) = api.entity_to_actor.get(&entity) {
+            unsafe {
+                (bindings().actor_fns.unregister_actor_on_hit)(actor.0);
+            }
+        }
+    }
+}
@@ -1,20 +1,38 @@
 use bevy_ecs::{prelude::*, system::Command};
 use ffi::{ActorComponentPtr, ActorComponentType, EventType, Quaternion};
-use std::ffi::c_void;
+use std::{collections::HashMap, ffi::c_void};
 
 use crate::{
     api::UnrealApi,
+    asset::{send_failed_asset_loads, AssetLoadedEvent, Assets},
+    collision::{
+        prune_destroyed_overlaps, register_hit_events, register_overlap_events,
+        unregister_hit_events, unregister_overlap_events, update_current_overlaps, CurrentOverlaps,
+        HitEvents, OverlapEvents, OverlapSweep,
+    },
+    debug_draw::DebugDraw,
+    event::{send_blueprint_events, take_blueprint_events, BlueprintEvents},
     ffi::{self, AActorOpaque},
+    fixed_update::{run_fixed_update, store_previous_transforms, FixedTime, InterpolateTransform},
+    gameplay_tags::{GameplayTagContainer, GameplayTags},
     input::Input,
+    level::{
+        despawn_level_entities, Level, LevelHiddenEvent, LevelLoadedEvent, LevelPtr,
+        LevelShownEvent, LevelUnloadedEvent, Levels,
+    },
     math::{Quat, Vec3},
-    module::{bindings, Module, UserModule},
-    physics::PhysicsComponent,
+    module::{bindings, GameThread, Module, UserModule},
+    physics::{PhysicalMaterial, PhysicsComponent, SurfaceTable, SurfaceType},
     plugin::Plugin,
     register_components,
+    time::{reset_custom_time_dilation, upload_custom_time_dilation, CustomTimeDilation},
+    timer::{tick_timers, Timer, TimerFinishedEvent, TimerFiredEvent, Timers},
+    type_registry::TypeRegistry,
 };
 
 pub struct UnrealCore {
-    module: Module,
+    pub(crate) module: Module,
+    is_playing: bool,
 }
 
 pub struct CorePlugin;
@@ -27,42 +45,131 @@ impl Plugin for CorePlugin {
             PlayerInputComponent,
             ParentComponent,
             PhysicsComponent,
+            OverlapEvents,
+            HitEvents,
+            CurrentOverlaps,
+            GameplayTags,
+            Timer,
+            CustomTimeDilation,
+            InterpolateTransform,
+            Level,
             => module
         };
+        #[cfg(feature = "save_game")]
+        {
+            use crate::save_game::{GameLoadedEvent, GameSavedEvent, SaveGameSettings, SaveId};
+            module.register_component::<SaveId>();
+            module
+                .init_resource::<SaveGameSettings>()
+                .add_event::<GameSavedEvent>()
+                .add_event::<GameLoadedEvent>();
+        }
 
         module
             .insert_resource(Frame::default())
             .insert_resource(Time::default())
             .insert_resource(Input::default())
             .insert_resource(UnrealApi::default())
-            .add_stage(CoreStage::RegisterEvent)
-            .add_stage_after(CoreStage::RegisterEvent, CoreStage::PreUpdate)
-            .add_stage_after(CoreStage::PreUpdate, CoreStage::Update)
+            .insert_resource(Assets::default())
+            .insert_resource(BlueprintEvents::default())
+            .insert_resource(DebugDraw::default())
+            .insert_resource(SurfaceTable::default())
+            .insert_resource(Timers::default())
+            .insert_resource(FixedTime::default())
+            .insert_resource(Levels::default())
+            .insert_resource(SyncedTransforms::default())
+            .add_stage_after(CoreStage::RegisterEvent, CoreStage::PreUpdate);
+        // Runs the stage again until the accumulator has no full step left
+        module.schedule.add_stage_after(
+            CoreStage::PreUpdate,
+            CoreStage::FixedUpdate,
+            SystemStage::parallel().with_run_criteria(run_fixed_update),
+        );
+        module
+            .add_stage_after(CoreStage::FixedUpdate, CoreStage::Update)
             .add_stage_after(CoreStage::Update, CoreStage::PostUpdate)
-            // TODO: Order matters here. Needs to be defined after the stages
+            .add_stage_to_tick_group(TickGroup::DuringPhysics, CoreStage::DuringPhysics)
+            .add_stage_to_tick_group(TickGroup::PostPhysics, CoreStage::PostPhysics)
+            .add_stage_to_tick_group(TickGroup::LateCamera, CoreStage::LateCamera)
+            .add_stage_to_tick_group(TickGroup::PostUpdateWork, CoreStage::PostUpdateWork)
             .add_event::<OnActorBeginOverlapEvent>()
             .add_event::<OnActorEndOverlapEvent>()
             .add_event::<ActorHitEvent>()
             .add_event::<ActorSpawnedEvent>()
             .add_event::<ActorDestroyEvent>()
+            .add_event::<AssetLoadedEvent>()
+            .add_event::<TimerFinishedEvent>()
+            .add_event::<TimerFiredEvent>()
+            .add_event::<LevelLoadedEvent>()
+            .add_event::<LevelUnloadedEvent>()
+            .add_event::<LevelShownEvent>()
+            .add_event::<LevelHiddenEvent>()
             .add_system_set_to_stage(
                 CoreStage::RegisterEvent,
                 SystemSet::new()
                     .with_system(process_actor_spawned)
-                    .with_system(process_actor_destroyed),
+                    .with_system(process_actor_destroyed)
+                    .with_system(send_failed_asset_loads)
+                    // Before spawning, so that actors of a level that was hidden and shown again
+                    // in the same tick survive
+                    .with_system(despawn_level_entities.before(process_actor_spawned)),
             )
             .add_system_set_to_stage(
                 CoreStage::PreUpdate,
                 SystemSet::new()
                     .with_system(update_input)
+                    .with_system(update_current_overlaps)
+                    .with_system(prune_destroyed_overlaps.after(update_current_overlaps))
                     .with_system(download_transform_from_unreal)
                     .with_system(download_physics_from_unreal),
             )
+            .add_system_set_to_stage(
+                CoreStage::FixedUpdate,
+                SystemSet::new()
+                    .with_system(store_previous_transforms.exclusive_system().at_start()),
+            )
             .add_system_set_to_stage(
                 CoreStage::PostUpdate,
                 SystemSet::new()
                     .with_system(upload_transform_to_unreal)
-                    .with_system(upload_physics_to_unreal),
+                    .with_system(upload_physics_to_unreal)
+                    .with_system(register_overlap_events)
+                    .with_system(register_hit_events)
+                    .with_system(unregister_overlap_events)
+                    .with_system(unregister_hit_events)
+                    .with_system(upload_custom_time_dilation)
+                    .with_system(reset_custom_time_dilation),
+            )
+            .add_system_set_to_stage(
+                CoreStage::PostPhysics,
+                SystemSet::new()
+                    // Picks up the results of the physics simulation
+                    .with_system(download_transform_from_unreal.exclusive_system().at_start())
+                    .with_system(download_physics_from_unreal.exclusive_system().at_start())
+                    .with_system(
+                        upload_moved_transforms_to_unreal
+                            .exclusive_system()
+                            .at_end(),
+                    ),
+            )
+            .add_system_set_to_stage(
+                CoreStage::LateCamera,
+                SystemSet::new().with_system(
+                    upload_moved_transforms_to_unreal
+                        .exclusive_system()
+                        .at_end(),
+                ),
+            )
+            .add_system_set_to_stage(
+                CoreStage::PostUpdateWork,
+                SystemSet::new()
+                    .with_system(
+                        upload_moved_transforms_to_unreal
+                            .exclusive_system()
+                            .at_end(),
+                    )
+                    // Runs last so that systems in every stage can still draw
+                    .with_system(flush_debug_draw.exclusive_system().at_end()),
             );
     }
 }
@@ -72,28 +179,68 @@ impl UnrealCore {
         let mut module = Module::new();
         module.add_plugin(CorePlugin);
         user_module.initialize(&mut module);
-        Self { module }
+        module.finish_plugins();
+        Self {
+            module,
+            is_playing: false,
+        }
     }
 
     pub fn begin_play(&mut self, user_module: &dyn UserModule) {
+        // Play can be restarted without an `end_play`, eg after the module was reloaded
+        if self.is_playing {
+            self.end_play(user_module, EndPlayReason::Destroyed);
+        }
         *self = Self::new(user_module);
+        self.is_playing = true;
 
         self.module.startup.run_once(&mut self.module.world);
     }
-    pub fn tick(&mut self, dt: f32) {
+
+    /// Runs the shutdown systems and the plugin cleanup, and then replaces the world with an empty
+    /// one. This drops every entity, resource, pending event and the mapping between actors and
+    /// entities, none of which are valid after the unreal world is gone.
+    pub fn end_play(&mut self, user_module: &dyn UserModule, reason: EndPlayReason) {
+        if !self.is_playing {
+            return;
+        }
+        self.module.world.insert_resource(EndPlay { reason });
+        self.module.shutdown.run_once(&mut self.module.world);
+        self.module.cleanup_plugins();
+        *self = Self::new(user_module);
+    }
+    pub fn tick(&mut self, tick: ffi::TickTime) {
+        let mut dt = 0.0;
+        if let Some(mut time) = self.module.world.get_resource_mut::<Time>() {
+            time.update(tick);
+            dt = time.delta;
+        }
         if let Some(mut frame) = self.module.world.get_resource_mut::<Frame>() {
             frame.dt = dt;
         }
-        if let Some(mut time) = self.module.world.get_resource_mut::<Time>() {
-            time.time += dt as f64;
+        if let Some(mut fixed) = self.module.world.get_resource_mut::<FixedTime>() {
+            fixed.accumulate(dt);
         }
+        tick_timers(&mut self.module.world, dt);
         self.module.schedule.run_once(&mut self.module.world);
         self.module.world.clear_trackers();
     }
+
+    /// Runs the stages of a tick group, with the clocks of the current frame.
+    ///
+    /// The change trackers are not cleared here, the main schedule and the tick groups share one
+    /// change window that [`UnrealCore::tick`] ends. Clearing them after a group would drop the
+    /// components removed in it before `RemovedComponents` readers in `PostUpdate` of the next
+    /// frame, eg `unregister_overlap_events`, have seen them.
+    pub fn tick_group(&mut self, group: TickGroup) {
+        if let Some(schedule) = self.module.tick_groups.get_mut(&group) {
+            schedule.run_once(&mut self.module.world);
+        }
+    }
 }
 
 pub unsafe extern "C" fn retrieve_uuids(ptr: *mut ffi::Uuid, len: *mut usize) {
-    if let Some(global) = crate::module::MODULE.as_mut() {
+    if let Some(global) = crate::module::global() {
         if ptr.is_null() {
             *len = global.core.module.reflection_registry.uuid_set.len();
         } else {
@@ -117,68 +264,209 @@ pub struct ActorSpawnedEvent {
     pub actor: ActorPtr,
 }
 
+/// Sent for actors that have an [`OverlapEvents`] component. There is one event for every pair
+/// of overlapping primitive components.
 pub struct OnActorBeginOverlapEvent {
     pub overlapped_actor: ActorPtr,
     pub other: ActorPtr,
+    /// `None` if the actor doesn't have an entity
+    pub overlapped_entity: Option<Entity>,
+    pub other_entity: Option<Entity>,
+    pub overlapped_component: UnrealPtr<Primitive>,
+    pub other_component: UnrealPtr<Primitive>,
+    pub other_body_index: i32,
+    /// Set if the overlap was caused by a sweep, eg when a moving actor ran into a trigger
+    pub sweep: Option<OverlapSweep>,
 }
 
 pub struct OnActorEndOverlapEvent {
     pub overlapped_actor: ActorPtr,
     pub other: ActorPtr,
+    pub overlapped_entity: Option<Entity>,
+    pub other_entity: Option<Entity>,
+    pub overlapped_component: UnrealPtr<Primitive>,
+    pub other_component: UnrealPtr<Primitive>,
+    pub other_body_index: i32,
 }
 
+/// Sent for actors that have a [`HitEvents`] component.
 pub struct ActorHitEvent {
     pub self_actor: ActorPtr,
     pub other: ActorPtr,
+    pub self_entity: Option<Entity>,
+    pub other_entity: Option<Entity>,
+    pub self_component: UnrealPtr<Primitive>,
+    pub other_component: UnrealPtr<Primitive>,
     pub normal_impulse: Vec3,
+    /// Location in world space of the contact
+    pub impact_location: Vec3,
+    /// Normal of the contact in world space, pointing away from the other component
+    pub impact_normal: Vec3,
+    pub location: Vec3,
+    pub normal: Vec3,
+    /// `None` if the surface has no physical material
+    pub physical_material: Option<PhysicalMaterial>,
+    pub surface_type: SurfaceType,
+    /// The bone of a skeletal mesh that was hit
+    pub bone_name: Option<String>,
+    /// Velocity of the self component relative to the other component
+    pub relative_velocity: Vec3,
 }
 
 pub struct ActorDestroyEvent {
     pub actor: ActorPtr,
 }
 
+fn actor_to_entity(world: &World, actor: *mut AActorOpaque) -> Option<Entity> {
+    world
+        .get_resource::<UnrealApi>()
+        .and_then(|api| api.actor_to_entity.get(&ActorPtr(actor)).copied())
+}
+
+unsafe fn level_streaming_event(data: *const c_void) -> (LevelPtr, String) {
+    let event = &*(data as *const ffi::LevelStreamingEvent);
+    let name = String::from_utf8_lossy(std::slice::from_raw_parts(
+        event.name.ptr as *const u8,
+        event.name.len,
+    ))
+    .into_owned();
+    (LevelPtr(event.level), name)
+}
+
 pub unsafe extern "C" fn unreal_event(ty: *const EventType, data: *const c_void) {
-    if let Some(global) = crate::module::MODULE.as_mut() {
-        match *ty {
-            EventType::ActorSpawned => {
-                let actor_spawned_event = data as *const ffi::ActorSpawnedEvent;
-                global.core.module.world.send_event(ActorSpawnedEvent {
-                    actor: ActorPtr((*actor_spawned_event).actor),
-                });
-            }
-            EventType::ActorBeginOverlap => {
-                let overlap = data as *const ffi::ActorBeginOverlap;
-                global
-                    .core
-                    .module
-                    .world
-                    .send_event(OnActorBeginOverlapEvent {
-                        overlapped_actor: ActorPtr((*overlap).overlapped_actor),
-                        other: ActorPtr((*overlap).other),
-                    });
-            }
-            EventType::ActorEndOverlap => {
-                let overlap = data as *const ffi::ActorEndOverlap;
-                global.core.module.world.send_event(OnActorEndOverlapEvent {
-                    overlapped_actor: ActorPtr((*overlap).overlapped_actor),
-                    other: ActorPtr((*overlap).other),
-                });
-            }
-            EventType::ActorOnHit => {
-                let hit = data as *const ffi::ActorHitEvent;
-                global.core.module.world.send_event(ActorHitEvent {
-                    self_actor: ActorPtr((*hit).self_actor),
-                    other: ActorPtr((*hit).other),
-                    normal_impulse: (*hit).normal_impulse.into(),
-                });
-            }
-            EventType::ActorDestroy => {
-                let destroy = data as *const ffi::ActorDestroyEvent;
-                global.core.module.world.send_event(ActorDestroyEvent {
-                    actor: ActorPtr((*destroy).actor),
+    let send: Box<dyn FnOnce(&mut World)> = match *ty {
+        EventType::ActorSpawned => {
+            let actor = ActorPtr((*(data as *const ffi::ActorSpawnedEvent)).actor);
+            Box::new(move |world| world.send_event(ActorSpawnedEvent { actor }))
+        }
+        EventType::ActorBeginOverlap => {
+            let overlap = &*(data as *const ffi::ActorBeginOverlap);
+            let (overlapped_actor, other) = (overlap.overlapped_actor, overlap.other);
+            let overlapped_component = UnrealPtr::from_raw(overlap.overlapped_component);
+            let other_component = UnrealPtr::from_raw(overlap.other_component);
+            let other_body_index = overlap.other_body_index;
+            let sweep = (overlap.from_sweep == 1).then(|| OverlapSweep {
+                location: overlap.sweep_result.location.into(),
+                normal: overlap.sweep_result.normal.into(),
+                impact_location: overlap.sweep_result.impact_location.into(),
+                impact_normal: overlap.sweep_result.impact_normal.into(),
+            });
+            Box::new(move |world| {
+                let event = OnActorBeginOverlapEvent {
+                    overlapped_actor: ActorPtr(overlapped_actor),
+                    other: ActorPtr(other),
+                    overlapped_entity: actor_to_entity(world, overlapped_actor),
+                    other_entity: actor_to_entity(world, other),
+                    overlapped_component,
+                    other_component,
+                    other_body_index,
+                    sweep,
+                };
+                world.send_event(event);
+            })
+        }
+        EventType::ActorEndOverlap => {
+            let overlap = &*(data as *const ffi::ActorEndOverlap);
+            let (overlapped_actor, other) = (overlap.overlapped_actor, overlap.other);
+            let overlapped_component = UnrealPtr::from_raw(overlap.overlapped_component);
+            let other_component = UnrealPtr::from_raw(overlap.other_component);
+            let other_body_index = overlap.other_body_index;
+            Box::new(move |world| {
+                let event = OnActorEndOverlapEvent {
+                    overlapped_actor: ActorPtr(overlapped_actor),
+                    other: ActorPtr(other),
+                    overlapped_entity: actor_to_entity(world, overlapped_actor),
+                    other_entity: actor_to_entity(world, other),
+                    overlapped_component,
+                    other_component,
+                    other_body_index,
+                };
+                world.send_event(event);
+            })
+        }
+        EventType::ActorOnHit => {
+            let hit = &*(data as *const ffi::ActorHitEvent);
+            let mut event = ActorHitEvent {
+                self_actor: ActorPtr(hit.self_actor),
+                other: ActorPtr(hit.other),
+                self_entity: None,
+                other_entity: None,
+                self_component: UnrealPtr::from_raw(hit.self_component),
+                other_component: UnrealPtr::from_raw(hit.other_component),
+                normal_impulse: hit.normal_impulse.into(),
+                impact_location: hit.hit.impact_location.into(),
+                impact_normal: hit.hit.impact_normal.into(),
+                location: hit.hit.location.into(),
+                normal: hit.hit.normal.into(),
+                physical_material: PhysicalMaterial::from_raw(hit.hit.physical_material),
+                surface_type: SurfaceType::from_ffi(hit.hit.surface_type),
+                bone_name: (hit.bone_name.len > 0).then(|| {
+                    let slice = std::slice::from_raw_parts(
+                        hit.bone_name.ptr as *const u8,
+                        hit.bone_name.len,
+                    );
+                    String::from_utf8_lossy(slice).into_owned()
+                }),
+                relative_velocity: hit.relative_velocity.into(),
+            };
+            Box::new(move |world| {
+                event.self_entity = actor_to_entity(world, event.self_actor.0);
+                event.other_entity = actor_to_entity(world, event.other.0);
+                world.send_event(event);
+            })
+        }
+        EventType::ActorDestroy => {
+            let actor = ActorPtr((*(data as *const ffi::ActorDestroyEvent)).actor);
+            Box::new(move |world| world.send_event(ActorDestroyEvent { actor }))
+        }
+        EventType::AssetLoaded => {
+            let loaded = &*(data as *const ffi::AssetLoadedEvent);
+            let (handle, object) = (loaded.handle, loaded.object);
+            Box::new(move |world| {
+                if let Some(mut assets) = world.get_resource_mut::<Assets>() {
+                    assets.on_loaded(handle, object);
+                }
+                world.send_event(AssetLoadedEvent {
+                    id: handle,
+                    success: !object.is_null(),
                 });
-            }
+            })
+        }
+        EventType::LevelLoaded => {
+            let (level, name) = level_streaming_event(data);
+            Box::new(move |world| {
+                world.resource_mut::<Levels>().on_loaded(&name);
+                world.send_event(LevelLoadedEvent { level, name });
+            })
+        }
+        EventType::LevelUnloaded => {
+            let (_, name) = level_streaming_event(data);
+            Box::new(move |world| {
+                world.resource_mut::<Levels>().on_unloaded(&name);
+                world.send_event(LevelUnloadedEvent { name });
+            })
+        }
+        EventType::LevelShown => {
+            let (level, name) = level_streaming_event(data);
+            Box::new(move |world| {
+                world.resource_mut::<Levels>().on_shown(&name);
+                world.send_event(LevelShownEvent { level, name });
+            })
         }
+        EventType::LevelHidden => {
+            let (level, name) = level_streaming_event(data);
+            Box::new(move |world| {
+                world.resource_mut::<Levels>().on_hidden(&name);
+                world.send_event(LevelHiddenEvent { level, name });
+            })
+        }
+    };
+    // Unreal sends events while systems call into it, eg overlaps while a transform is uploaded.
+    // Those are sent once the world is no longer borrowed.
+    if crate::module::is_module_borrowed() {
+        crate::module::defer_while_borrowed(send);
+    } else if let Some(mut global) = crate::module::global() {
+        send(&mut global.core.module.world);
     }
 }
 extern "C" fn get_field_float_value(
@@ -258,7 +546,7 @@ extern "C" fn get_field_bool_value(
 fn get_field_value(uuid: ffi::Uuid, entity: ffi::Entity, idx: u32) -> Option<ReflectValue> {
     let uuid = from_ffi_uuid(uuid);
     unsafe {
-        let global = crate::module::MODULE.as_mut()?;
+        let global = crate::module::global()?;
         let reflect = global.core.module.reflection_registry.reflect.get(&uuid)?;
 
         let entity = Entity::from_bits(entity.id);
@@ -268,7 +556,7 @@ fn get_field_value(uuid: ffi::Uuid, entity: ffi::Entity, idx: u32) -> Option<Ref
 
 unsafe extern "C" fn number_of_fields(uuid: ffi::Uuid, out: *mut u32) -> u32 {
     fn get_number_fields(uuid: ffi::Uuid) -> Option<u32> {
-        let global = unsafe { crate::module::MODULE.as_mut() }?;
+        let global = unsafe { crate::module::global() }?;
         let uuid = from_ffi_uuid(uuid);
         let reflect = global.core.module.reflection_registry.reflect.get(&uuid)?;
         Some(reflect.number_of_fields() as u32)
@@ -285,7 +573,7 @@ unsafe extern "C" fn number_of_fields(uuid: ffi::Uuid, out: *mut u32) -> u32 {
 }
 unsafe extern "C" fn get_type_name(uuid: ffi::Uuid, out: *mut ffi::Utf8Str) -> u32 {
     fn get_type_name(uuid: ffi::Uuid) -> Option<&'static str> {
-        let global = unsafe { crate::module::MODULE.as_mut() }?;
+        let global = unsafe { crate::module::global() }?;
         let uuid = from_ffi_uuid(uuid);
         let reflect = global.core.module.reflection_registry.reflect.get(&uuid)?;
         Some(reflect.name())
@@ -302,7 +590,7 @@ unsafe extern "C" fn get_type_name(uuid: ffi::Uuid, out: *mut ffi::Utf8Str) -> u
 }
 unsafe extern "C" fn has_component(entity: ffi::Entity, uuid: ffi::Uuid) -> u32 {
     fn has_component(entity: ffi::Entity, uuid: ffi::Uuid) -> Option<u32> {
-        let global = unsafe { crate::module::MODULE.as_ref() }?;
+        let global = unsafe { crate::module::global() }?;
         let uuid = from_ffi_uuid(uuid);
         let reflect = global.core.module.reflection_registry.reflect.get(&uuid)?;
         let entity = Entity::from_bits(entity.id);
@@ -314,7 +602,7 @@ unsafe extern "C" fn has_component(entity: ffi::Entity, uuid: ffi::Uuid) -> u32
 
 unsafe extern "C" fn is_editor_component(uuid: ffi::Uuid) -> u32 {
     fn is_editor_component_inner(uuid: ffi::Uuid) -> Option<u32> {
-        let global = unsafe { crate::module::MODULE.as_mut() }?;
+        let global = unsafe { crate::module::global() }?;
         let uuid = from_ffi_uuid(uuid);
         Some(
             if global
@@ -334,9 +622,26 @@ unsafe extern "C" fn is_editor_component(uuid: ffi::Uuid) -> u32 {
     result.unwrap_or(0)
 }
 
+unsafe extern "C" fn is_event(uuid: ffi::Uuid) -> u32 {
+    fn is_event_inner(uuid: ffi::Uuid) -> Option<u32> {
+        let global = unsafe { crate::module::global() }?;
+        let uuid = from_ffi_uuid(uuid);
+        Some(
+            global
+                .core
+                .module
+                .reflection_registry
+                .events
+                .contains_key(&uuid) as u32,
+        )
+    }
+    let result = std::panic::catch_unwind(|| is_event_inner(uuid).unwrap_or(0));
+    result.unwrap_or(0)
+}
+
 unsafe extern "C" fn get_field_name(uuid: ffi::Uuid, idx: u32, out: *mut ffi::Utf8Str) -> u32 {
     fn get_field_name(uuid: ffi::Uuid, idx: u32) -> Option<&'static str> {
-        let global = unsafe { crate::module::MODULE.as_mut() }?;
+        let global = unsafe { crate::module::global() }?;
         let uuid = from_ffi_uuid(uuid);
         let reflect = global.core.module.reflection_registry.reflect.get(&uuid)?;
         reflect.get_field_name(idx)
@@ -357,19 +662,10 @@ unsafe extern "C" fn get_field_type(
     out: *mut ffi::ReflectionType,
 ) -> u32 {
     fn get_field_type(uuid: ffi::Uuid, idx: u32) -> Option<ffi::ReflectionType> {
-        let global = unsafe { crate::module::MODULE.as_mut() }?;
+        let global = unsafe { crate::module::global() }?;
         let uuid = from_ffi_uuid(uuid);
         let reflect = global.core.module.reflection_registry.reflect.get(&uuid)?;
-        let ty = reflect.get_field_type(idx)?;
-        Some(match ty {
-            ReflectType::Bool => ffi::ReflectionType::Bool,
-            ReflectType::Float => ffi::ReflectionType::Float,
-            ReflectType::Vector3 => ffi::ReflectionType::Vector3,
-            ReflectType::Quat => ffi::ReflectionType::Quaternion,
-            ReflectType::UClass => ffi::ReflectionType::UClass,
-            ReflectType::USound => ffi::ReflectionType::USound,
-            ReflectType::Composite => ffi::ReflectionType::Composite,
-        })
+        reflect.get_field_type(idx).map(Into::into)
     }
     let result = std::panic::catch_unwind(|| {
         if let Some(ty) = get_field_type(uuid, idx) {
@@ -398,6 +694,7 @@ pub fn to_ffi_uuid(uuid: Uuid) -> ffi::Uuid {
 pub fn create_reflection_fns() -> ffi::ReflectionFns {
     ffi::ReflectionFns {
         is_editor_component,
+        is_event,
         has_component,
         get_field_bool_value,
         get_field_float_value,
@@ -412,6 +709,11 @@ pub fn create_reflection_fns() -> ffi::ReflectionFns {
 
 unsafe extern "C" fn allocate(size: usize, align: usize, ptr: *mut ffi::RustAlloc) -> u32 {
     use std::alloc::{alloc, Layout};
+    // Allocating zero bytes is undefined behavior, empty data is an empty allocation instead
+    if size == 0 {
+        *ptr = ffi::RustAlloc::empty();
+        return 1;
+    }
     let layout = Layout::from_size_align(size, align);
     match layout {
         Ok(layout) => {
@@ -431,9 +733,29 @@ pub fn create_allocate_fns() -> ffi::AllocateFns {
     ffi::AllocateFns { allocate }
 }
 
-pub extern "C" fn tick(dt: f32) -> crate::ffi::ResultCode {
+pub extern "C" fn tick(time: ffi::TickTime) -> crate::ffi::ResultCode {
+    let r = std::panic::catch_unwind(|| unsafe {
+        let events = {
+            let core = &mut crate::module::global().unwrap().core;
+            core.tick(time);
+            take_blueprint_events(&mut core.module.world)
+        };
+        send_blueprint_events(events);
+    });
+    match r {
+        Ok(_) => ffi::ResultCode::Success,
+        Err(_) => ffi::ResultCode::Panic,
+    }
+}
+
+pub extern "C" fn tick_group(group: TickGroup) -> crate::ffi::ResultCode {
     let r = std::panic::catch_unwind(|| unsafe {
-        UnrealCore::tick(&mut crate::module::MODULE.as_mut().unwrap().core, dt);
+        let events = {
+            let core = &mut crate::module::global().unwrap().core;
+            core.tick_group(group);
+            take_blueprint_events(&mut core.module.world)
+        };
+        send_blueprint_events(events);
     });
     match r {
         Ok(_) => ffi::ResultCode::Success,
@@ -443,7 +765,8 @@ pub extern "C" fn tick(dt: f32) -> crate::ffi::ResultCode {
 
 pub extern "C" fn begin_play() -> ffi::ResultCode {
     let r = std::panic::catch_unwind(|| unsafe {
-        let global = crate::module::MODULE.as_mut().unwrap();
+        let mut guard = crate::module::global().unwrap();
+        let global = &mut *guard;
         UnrealCore::begin_play(&mut global.core, global.module.as_ref());
     });
     match r {
@@ -452,39 +775,79 @@ pub extern "C" fn begin_play() -> ffi::ResultCode {
     }
 }
 
+pub extern "C" fn end_play(reason: EndPlayReason) -> ffi::ResultCode {
+    let r = std::panic::catch_unwind(|| unsafe {
+        let mut guard = crate::module::global().unwrap();
+        let global = &mut *guard;
+        UnrealCore::end_play(&mut global.core, global.module.as_ref(), reason);
+    });
+    match r {
+        Ok(_) => ffi::ResultCode::Success,
+        Err(_) => ffi::ResultCode::Panic,
+    }
+}
+
 pub fn register_core_components(registry: &mut ReflectionRegistry) {
     registry.register::<TransformComponent>();
     registry.register::<ActorComponent>();
     registry.register::<PlayerInputComponent>();
     registry.register::<ParentComponent>();
     registry.register::<PhysicsComponent>();
+    registry.register::<OverlapEvents>();
+    registry.register::<HitEvents>();
+    registry.register::<CurrentOverlaps>();
+    registry.register::<GameplayTags>();
+    registry.register::<Timer>();
+    registry.register::<CustomTimeDilation>();
+    registry.register::<InterpolateTransform>();
+    registry.register::<Level>();
+    #[cfg(feature = "save_game")]
+    registry.register::<crate::save_game::SaveId>();
 }
 
 use unreal_api::{module::ReflectionRegistry, Component};
-use unreal_reflect::{
-    registry::{ReflectType, ReflectValue},
-    Uuid,
-};
+use unreal_reflect::{registry::ReflectValue, Uuid};
 #[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
 pub struct StartupStage;
 
+/// The stages from `Startup` to `PostUpdate` run before physics, in `TG_PrePhysics`. The later
+/// stages each run in their own unreal tick group, see [`TickGroup`].
 #[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
 pub enum CoreStage {
     Startup,
+    /// Runs once when play ends, see [`Module::add_shutdown_system_set`]
+    Shutdown,
     RegisterEvent,
     PreUpdate,
+    /// Runs zero or more times per tick at the rate of [`FixedTime`]
+    FixedUpdate,
     Update,
     PostUpdate,
+    /// Runs while physics is simulating. Transforms are not synced with unreal in this stage.
+    DuringPhysics,
+    /// Transforms and physics state are downloaded again at the start of this stage, so systems
+    /// see the results of the physics simulation of this frame.
+    PostPhysics,
+    /// Runs after `PostPhysics`, right before unreal updates the player cameras. Cameras that
+    /// follow simulated actors should be moved here.
+    LateCamera,
+    /// Runs after the cameras were updated
+    PostUpdateWork,
 }
+/// The game delta of the current tick, see [`Time`] for the other clocks
 #[derive(Default, Debug, Copy, Clone)]
 pub struct Frame {
     pub dt: f32,
 }
 
-#[derive(Default, Debug, Copy, Clone)]
-pub struct Time {
-    pub time: f64,
+pub use crate::ffi::{EndPlayReason, TickGroup};
+
+/// Inserted before the shutdown systems run
+#[derive(Debug, Copy, Clone)]
+pub struct EndPlay {
+    pub reason: EndPlayReason,
 }
+pub use crate::time::Time;
 
 #[derive(Default, Debug, Component)]
 #[uuid = "5ad05c2b-7cbc-4081-8819-1997b3e13331"]
@@ -526,6 +889,7 @@ impl ActorComponent {
 
 #[derive(Default, Debug, Component, Clone)]
 #[uuid = "b8738d9e-ab21-47db-8587-4019b38e35a6"]
+#[reflect(default)]
 pub struct TransformComponent {
     pub position: Vec3,
     pub rotation: Quat,
@@ -569,6 +933,32 @@ pub struct PlayerInputComponent {
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub struct ActorPtr(pub *mut AActorOpaque);
 impl ActorPtr {
+    /// Forwards damage to unreal's damage system, eg to trigger `AnyDamage` events in blueprints.
+    /// Returns the damage that the actor actually took.
+    pub fn apply_damage(&self, damage: f32, instigator: Option<ActorPtr>) -> f32 {
+        let instigator = instigator.map_or(std::ptr::null_mut(), |actor| actor.0);
+        unsafe { (bindings().actor_fns.apply_damage)(self.0, damage, instigator) }
+    }
+
+    /// Returns the tags of actors that implement unreal's `IGameplayTagAssetInterface`. These are
+    /// separate from the [`GameplayTags`] editor component.
+    pub fn owned_gameplay_tags(&self) -> Option<GameplayTagContainer> {
+        unsafe {
+            let mut alloc = ffi::RustAlloc::empty();
+            if (bindings().actor_fns.get_owned_gameplay_tags)(self.0, &mut alloc) == 0 {
+                return None;
+            }
+            let tags = if alloc.ptr.is_null() {
+                GameplayTagContainer::new()
+            } else {
+                let slice = std::slice::from_raw_parts(alloc.ptr, alloc.size);
+                GameplayTagContainer::parse(&String::from_utf8_lossy(slice))
+            };
+            alloc.free();
+            Some(tags)
+        }
+    }
+
     pub fn get_actor_name(&self) -> String {
         unsafe {
             let mut alloc = ffi::RustAlloc::empty();
@@ -582,6 +972,20 @@ impl ActorPtr {
             name
         }
     }
+
+    /// A stable id for actors that are placed in a level, which save games use to find them again
+    pub fn get_actor_path(&self) -> String {
+        unsafe {
+            let mut alloc = ffi::RustAlloc::empty();
+            (bindings().actor_fns.get_actor_path)(self.0, &mut alloc);
+            let path = {
+                let slice = std::slice::from_raw_parts(alloc.ptr, alloc.size);
+                String::from_utf8_lossy(slice).into_owned()
+            };
+            alloc.free();
+            path
+        }
+    }
 }
 unsafe impl Send for ActorPtr {}
 unsafe impl Sync for ActorPtr {}
@@ -630,18 +1034,42 @@ pub enum Capsule {}
 #[derive(Debug)]
 pub enum Primitive {}
 
-fn download_physics_from_unreal(mut query: Query<&mut PhysicsComponent>) {
+fn download_physics_from_unreal(
+    _game_thread: NonSend<GameThread>,
+    mut query: Query<&mut PhysicsComponent>,
+) {
     for mut physics in query.iter_mut() {
         physics.download_state();
     }
 }
-fn upload_physics_to_unreal(mut query: Query<&mut PhysicsComponent>) {
+fn upload_physics_to_unreal(
+    _game_thread: NonSend<GameThread>,
+    mut query: Query<&mut PhysicsComponent>,
+) {
     for mut physics in query.iter_mut() {
         physics.download_state();
     }
 }
-fn download_transform_from_unreal(mut query: Query<(&ActorComponent, &mut TransformComponent)>) {
-    for (actor, mut transform) in query.iter_mut() {
+/// The transforms of the last download, so that the late stages only upload what Rust moved since
+#[derive(Default)]
+pub(crate) struct SyncedTransforms(HashMap<Entity, TransformComponent>);
+
+fn download_transform_from_unreal(
+    _game_thread: NonSend<GameThread>,
+    mut synced: ResMut<SyncedTransforms>,
+    mut query: Query<(
+        Entity,
+        &ActorComponent,
+        &mut TransformComponent,
+        Option<&InterpolateTransform>,
+    )>,
+) {
+    synced.0.clear();
+    for (entity, actor, mut transform, interpolate) in query.iter_mut() {
+        // The simulation owns the transform, unreal only has the interpolated one
+        if interpolate.is_some_and(InterpolateTransform::is_simulated) {
+            continue;
+        }
         let mut position = ffi::Vector3::default();
         let mut rotation = ffi::Quaternion::default();
         let mut scale = ffi::Vector3::default();
@@ -657,15 +1085,32 @@ fn download_transform_from_unreal(mut query: Query<(&ActorComponent, &mut Transf
         transform.rotation = rotation.into();
         transform.scale = scale.into();
         assert!(!transform.is_nan());
+        synced.0.insert(entity, transform.clone());
     }
 }
 
-fn upload_transform_to_unreal(query: Query<(&ActorComponent, &TransformComponent)>) {
-    for (actor, transform) in query.iter() {
+fn flush_debug_draw(_game_thread: NonSend<GameThread>, mut debug_draw: ResMut<DebugDraw>) {
+    debug_draw.flush();
+}
+
+fn upload_transform_to_unreal(
+    _game_thread: NonSend<GameThread>,
+    fixed: Res<FixedTime>,
+    query: Query<(
+        &ActorComponent,
+        &TransformComponent,
+        Option<&InterpolateTransform>,
+    )>,
+) {
+    for (actor, transform, interpolate) in query.iter() {
         let is_moveable = unsafe { (bindings().actor_fns.is_moveable)(actor.actor.0) } > 0;
         if !is_moveable {
             continue;
         }
+        let transform = match interpolate {
+            Some(interpolate) => interpolate.interpolate(transform, fixed.alpha()),
+            None => transform.clone(),
+        };
         assert!(!transform.is_nan());
         (bindings().actor_fns.set_spatial_data)(
             actor.actor.0,
@@ -676,7 +1121,51 @@ fn upload_transform_to_unreal(query: Query<(&ActorComponent, &TransformComponent
     }
 }
 
-fn update_input(mut input: ResMut<Input>) {
+/// Uploads the transforms that Rust moved after physics. Unlike [`upload_transform_to_unreal`] this
+/// skips transforms that still match the last download or upload, so that actors that unreal
+/// moved since are not teleported back, and bodies that are simulated by physics are not
+/// teleported to where they already are.
+fn upload_moved_transforms_to_unreal(
+    _game_thread: NonSend<GameThread>,
+    mut synced: ResMut<SyncedTransforms>,
+    query: Query<(
+        Entity,
+        &ActorComponent,
+        &TransformComponent,
+        Option<&InterpolateTransform>,
+    )>,
+) {
+    const TOLERANCE: f32 = 1.0e-4;
+    for (entity, actor, transform, interpolate) in query.iter() {
+        // Was already interpolated before physics
+        if interpolate.is_some_and(InterpolateTransform::is_simulated) {
+            continue;
+        }
+        // Entities that were spawned after the download are always uploaded
+        let unchanged = synced.0.get(&entity).is_some_and(|synced| {
+            transform.position.abs_diff_eq(synced.position, TOLERANCE)
+                && transform.rotation.abs_diff_eq(synced.rotation, TOLERANCE)
+                && transform.scale.abs_diff_eq(synced.scale, TOLERANCE)
+        });
+        if unchanged {
+            continue;
+        }
+        let is_moveable = unsafe { (bindings().actor_fns.is_moveable)(actor.actor.0) } > 0;
+        if !is_moveable {
+            continue;
+        }
+        assert!(!transform.is_nan());
+        (bindings().actor_fns.set_spatial_data)(
+            actor.actor.0,
+            transform.position.into(),
+            transform.rotation.into(),
+            transform.scale.into(),
+        );
+        synced.0.insert(entity, transform.clone());
+    }
+}
+
+fn update_input(_game_thread: NonSend<GameThread>, mut input: ResMut<Input>) {
     input.update();
 }
 #[derive(Debug)]
@@ -717,74 +1206,74 @@ fn process_actor_destroyed(
 }
 
 fn process_actor_spawned(
+    _game_thread: NonSend<GameThread>,
     mut api: ResMut<UnrealApi>,
+    registry: Res<TypeRegistry>,
     mut reader: EventReader<ActorSpawnedEvent>,
     mut commands: Commands,
 ) {
     unsafe {
-        if let Some(global) = crate::module::MODULE.as_mut() {
-            for &ActorSpawnedEvent { actor } in reader.iter() {
-                let mut entity_cmds = commands.spawn();
-
-                let mut len = 0;
-                (bindings().editor_component_fns.get_editor_components)(
-                    actor.0,
-                    std::ptr::null_mut(),
-                    &mut len,
-                );
-
-                let mut uuids = vec![ffi::Uuid::default(); len];
-                (bindings().editor_component_fns.get_editor_components)(
-                    actor.0,
-                    uuids.as_mut_ptr(),
-                    &mut len,
-                );
-                // We might have gotten back fewer uuids, so we truncate
-                uuids.truncate(len);
-
-                // We register all the components that are on the actor in unreal and add
-                // them to the entity
-                for uuid in uuids {
-                    let uuid = from_ffi_uuid(uuid);
-                    if let Some(insert) = global
-                        .core
-                        .module
-                        .reflection_registry
-                        .insert_editor_component
-                        .get(&uuid)
-                    {
-                        insert.insert_component(actor.0, uuid, &mut entity_cmds);
-                    }
-                }
+        for &ActorSpawnedEvent { actor } in reader.iter() {
+            let mut entity_cmds = commands.spawn();
 
-                let entity = entity_cmds
-                    .insert_bundle((ActorComponent { actor }, TransformComponent::default()))
-                    .id();
-
-                // Create a physics component if the root component is a primitive
-                // component
-                // TODO: We probably should get ALL the primitive components as well
-                let mut root_component = ActorComponentPtr::default();
-                (bindings().actor_fns.get_root_component)(actor.0, &mut root_component);
-                if root_component.ty == ActorComponentType::Primitive
-                    && !root_component.ptr.is_null()
+            let mut len = 0;
+            (bindings().editor_component_fns.get_editor_components)(
+                actor.0,
+                std::ptr::null_mut(),
+                &mut len,
+            );
+
+            let mut uuids = vec![ffi::Uuid::default(); len];
+            (bindings().editor_component_fns.get_editor_components)(
+                actor.0,
+                uuids.as_mut_ptr(),
+                &mut len,
+            );
+            // We might have gotten back fewer uuids, so we truncate
+            uuids.truncate(len);
+
+            // We register all the components that are on the actor in unreal and add
+            // them to the entity
+            for uuid in uuids {
+                let uuid = from_ffi_uuid(uuid);
+                if let Some(insert) = registry
+                    .reflection_registry()
+                    .insert_editor_component
+                    .get(&uuid)
                 {
-                    let physics_component =
-                        PhysicsComponent::new(UnrealPtr::from_raw(root_component.ptr));
-                    commands.entity(entity).insert(physics_component);
+                    insert.insert_component(actor.0, uuid, &mut entity_cmds);
                 }
+            }
 
-                api.register_actor(actor, entity);
+            let entity = entity_cmds
+                .insert_bundle((
+                    ActorComponent { actor },
+                    TransformComponent::default(),
+                    Level::of_actor(actor),
+                ))
+                .id();
 
-                // Update the `EntityComponent` with the entity id so we can easily access
-                // it in blueprint etc
-                (bindings().actor_fns.set_entity_for_actor)(
-                    actor.0,
-                    ffi::Entity {
-                        id: entity.to_bits(),
-                    },
-                );
+            // Create a physics component if the root component is a primitive
+            // component
+            // TODO: We probably should get ALL the primitive components as well
+            let mut root_component = ActorComponentPtr::default();
+            (bindings().actor_fns.get_root_component)(actor.0, &mut root_component);
+            if root_component.ty == ActorComponentType::Primitive && !root_component.ptr.is_null() {
+                let physics_component =
+                    PhysicsComponent::new(UnrealPtr::from_raw(root_component.ptr));
+                commands.entity(entity).insert(physics_component);
             }
+
+            api.register_actor(actor, entity);
+
+            // Update the `EntityComponent` with the entity id so we can easily access
+            // it in blueprint etc
+            (bindings().actor_fns.set_entity_for_actor)(
+                actor.0,
+                ffi::Entity {
+                    id: entity.to_bits(),
+                },
+            );
         }
     }
 }
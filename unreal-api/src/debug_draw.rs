@@ -0,0 +1,322 @@
+use glam::{Quat, Vec3};
+use unreal_ffi as ffi;
+use unreal_ffi::Color;
+
+use crate::{module::bindings, physics::CollisionShape};
+
+#[derive(Debug, Copy, Clone)]
+pub struct DrawSettings {
+    pub color: Color,
+    /// In seconds, anything <= 0 is only drawn for the current frame
+    pub duration: f32,
+    /// If false the shape is drawn on top of everything else
+    pub depth_test: bool,
+    pub thickness: f32,
+}
+
+impl Default for DrawSettings {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            duration: 0.0,
+            depth_test: true,
+            thickness: 0.0,
+        }
+    }
+}
+
+impl DrawSettings {
+    pub fn color(color: Color) -> Self {
+        Self {
+            color,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    pub fn with_depth_test(mut self, depth_test: bool) -> Self {
+        self.depth_test = depth_test;
+        self
+    }
+
+    pub fn with_thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+enum DebugShape {
+    Line {
+        start: Vec3,
+        end: Vec3,
+    },
+    Sphere {
+        center: Vec3,
+        radius: f32,
+    },
+    Capsule {
+        center: Vec3,
+        rotation: Quat,
+        half_height: f32,
+        radius: f32,
+    },
+    Box {
+        center: Vec3,
+        rotation: Quat,
+        half_extent: Vec3,
+    },
+    Arrow {
+        start: Vec3,
+        end: Vec3,
+        head_size: f32,
+    },
+    Text {
+        position: Vec3,
+        text: String,
+    },
+    CoordinateFrame {
+        position: Vec3,
+        rotation: Quat,
+        scale: f32,
+    },
+}
+
+/// Draws debug geometry in the viewport. Everything that is drawn during a frame is buffered and
+/// sent to unreal in one call at the end of [`CoreStage::PostUpdateWork`](crate::core::CoreStage),
+/// so every stage of the frame can draw.
+///
+/// ```ignore
+/// fn draw(mut debug_draw: ResMut<DebugDraw>) {
+///     debug_draw.sphere(Vec3::ZERO, 50.0, DrawSettings::color(Color::RED));
+/// }
+/// ```
+#[derive(Default)]
+pub struct DebugDraw {
+    shapes: Vec<(DebugShape, DrawSettings)>,
+}
+
+impl DebugDraw {
+    pub const DEFAULT_ARROW_HEAD_SIZE: f32 = 10.0;
+
+    pub fn line(&mut self, start: Vec3, end: Vec3, settings: DrawSettings) {
+        self.shapes
+            .push((DebugShape::Line { start, end }, settings));
+    }
+
+    pub fn sphere(&mut self, center: Vec3, radius: f32, settings: DrawSettings) {
+        self.shapes
+            .push((DebugShape::Sphere { center, radius }, settings));
+    }
+
+    pub fn capsule(
+        &mut self,
+        center: Vec3,
+        rotation: Quat,
+        half_height: f32,
+        radius: f32,
+        settings: DrawSettings,
+    ) {
+        self.shapes.push((
+            DebugShape::Capsule {
+                center,
+                rotation,
+                half_height,
+                radius,
+            },
+            settings,
+        ));
+    }
+
+    pub fn cuboid(
+        &mut self,
+        center: Vec3,
+        rotation: Quat,
+        half_extent: Vec3,
+        settings: DrawSettings,
+    ) {
+        self.shapes.push((
+            DebugShape::Box {
+                center,
+                rotation,
+                half_extent,
+            },
+            settings,
+        ));
+    }
+
+    pub fn arrow(&mut self, start: Vec3, end: Vec3, settings: DrawSettings) {
+        self.shapes.push((
+            DebugShape::Arrow {
+                start,
+                end,
+                head_size: Self::DEFAULT_ARROW_HEAD_SIZE,
+            },
+            settings,
+        ));
+    }
+
+    pub fn text(&mut self, position: Vec3, text: impl Into<String>, settings: DrawSettings) {
+        self.shapes.push((
+            DebugShape::Text {
+                position,
+                text: text.into(),
+            },
+            settings,
+        ));
+    }
+
+    /// Draws the x, y and z axis in red, green and blue. The color of the settings is ignored.
+    pub fn coordinate_frame(
+        &mut self,
+        position: Vec3,
+        rotation: Quat,
+        scale: f32,
+        settings: DrawSettings,
+    ) {
+        self.shapes.push((
+            DebugShape::CoordinateFrame {
+                position,
+                rotation,
+                scale,
+            },
+            settings,
+        ));
+    }
+
+    pub fn shape(
+        &mut self,
+        position: Vec3,
+        rotation: Quat,
+        shape: CollisionShape,
+        settings: DrawSettings,
+    ) {
+        match shape {
+            CollisionShape::Capsule {
+                half_height,
+                radius,
+            } => self.capsule(position, rotation, half_height, radius, settings),
+            CollisionShape::Box { half_extent } => {
+                self.cuboid(position, rotation, half_extent, settings)
+            }
+            CollisionShape::Sphere { radius } => self.sphere(position, radius, settings),
+        }
+    }
+
+    /// Draws the shape at the start and end of a sweep and connects both with a line.
+    pub fn sweep(
+        &mut self,
+        start: Vec3,
+        end: Vec3,
+        rotation: Quat,
+        shape: CollisionShape,
+        settings: DrawSettings,
+    ) {
+        self.shape(start, rotation, shape, settings);
+        self.shape(end, rotation, shape, settings);
+        self.line(start, end, settings);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.shapes.clear();
+    }
+
+    fn to_command(shape: &DebugShape, settings: &DrawSettings) -> ffi::DebugDrawCommand {
+        let mut command = ffi::DebugDrawCommand {
+            ty: ffi::DebugDrawType::Line,
+            position: Vec3::ZERO.into(),
+            end: Vec3::ZERO.into(),
+            rotation: Quat::IDENTITY.into(),
+            size: 0.0,
+            half_height: 0.0,
+            thickness: settings.thickness,
+            color: settings.color,
+            duration: settings.duration,
+            depth_test: settings.depth_test as u32,
+            text: ffi::Utf8Str::from(""),
+        };
+        match shape {
+            DebugShape::Line { start, end } => {
+                command.position = (*start).into();
+                command.end = (*end).into();
+            }
+            DebugShape::Sphere { center, radius } => {
+                command.ty = ffi::DebugDrawType::Sphere;
+                command.position = (*center).into();
+                command.size = *radius;
+            }
+            DebugShape::Capsule {
+                center,
+                rotation,
+                half_height,
+                radius,
+            } => {
+                command.ty = ffi::DebugDrawType::Capsule;
+                command.position = (*center).into();
+                command.rotation = (*rotation).into();
+                command.half_height = *half_height;
+                command.size = *radius;
+            }
+            DebugShape::Box {
+                center,
+                rotation,
+                half_extent,
+            } => {
+                command.ty = ffi::DebugDrawType::Box;
+                command.position = (*center).into();
+                command.rotation = (*rotation).into();
+                command.end = (*half_extent).into();
+            }
+            DebugShape::Arrow {
+                start,
+                end,
+                head_size,
+            } => {
+                command.ty = ffi::DebugDrawType::Arrow;
+                command.position = (*start).into();
+                command.end = (*end).into();
+                command.size = *head_size;
+            }
+            DebugShape::Text { position, text } => {
+                command.ty = ffi::DebugDrawType::Text;
+                command.position = (*position).into();
+                command.text = ffi::Utf8Str::from(text.as_str());
+            }
+            DebugShape::CoordinateFrame {
+                position,
+                rotation,
+                scale,
+            } => {
+                command.ty = ffi::DebugDrawType::CoordinateFrame;
+                command.position = (*position).into();
+                command.rotation = (*rotation).into();
+                command.size = *scale;
+            }
+        }
+        command
+    }
+
+    pub(crate) fn flush(&mut self) {
+        if self.shapes.is_empty() {
+            return;
+        }
+        // The commands borrow the text of `self.shapes`, so we can only clear after the call
+        let commands: Vec<ffi::DebugDrawCommand> = self
+            .shapes
+            .iter()
+            .map(|(shape, settings)| Self::to_command(shape, settings))
+            .collect();
+        unsafe {
+            (bindings().debug_draw)(commands.as_ptr(), commands.len());
+        }
+        self.shapes.clear();
+    }
+}
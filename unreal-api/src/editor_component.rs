@@ -2,13 +2,13 @@ use bevy_ecs::system::EntityCommands;
 use glam::{Quat, Vec3};
 use unreal_ffi as ffi;
 use unreal_reflect::{
-    registry::{UClass, USound},
+    registry::{AssetRef, AssetType, SoftAssetRef, UClass, USound},
     Uuid,
 };
 
-use crate::{core::to_ffi_uuid, module::bindings};
+use crate::{core::to_ffi_uuid, gameplay_tags::GameplayTagContainer, module::bindings};
 
-pub trait InsertEditorComponent {
+pub trait InsertEditorComponent: Send + Sync {
     /// # Safety
     unsafe fn insert_component(
         &self,
@@ -138,7 +138,7 @@ impl GetEditorComponentValue for USound {
             actor,
             to_ffi_uuid(uuid),
             ffi::Utf8Str::from(field),
-            ffi::UObjectType::UClass,
+            ffi::UObjectType::USound,
             &mut data,
         );
         if code == 1 {
@@ -148,3 +148,87 @@ impl GetEditorComponentValue for USound {
         }
     }
 }
+
+impl<T: AssetType> GetEditorComponentValue for AssetRef<T> {
+    unsafe fn get(
+        actor: *const ffi::AActorOpaque,
+        uuid: Uuid,
+        field: &'static str,
+    ) -> Option<Self> {
+        let mut data: *mut ffi::UObjectOpague = std::ptr::null_mut();
+        let code = (bindings().editor_component_fns.get_editor_component_uobject)(
+            actor,
+            to_ffi_uuid(uuid),
+            ffi::Utf8Str::from(field),
+            T::TYPE,
+            &mut data,
+        );
+        if code == 1 {
+            Some(AssetRef::from_raw(data))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: AssetType> GetEditorComponentValue for SoftAssetRef<T> {
+    unsafe fn get(
+        actor: *const ffi::AActorOpaque,
+        uuid: Uuid,
+        field: &'static str,
+    ) -> Option<Self> {
+        let mut alloc = ffi::RustAlloc::empty();
+        let code = (bindings()
+            .editor_component_fns
+            .get_editor_component_soft_object_path)(
+            actor,
+            to_ffi_uuid(uuid),
+            ffi::Utf8Str::from(field),
+            &mut alloc,
+        );
+        if code == 1 {
+            // Unset fields come back as an empty allocation
+            let path = if alloc.ptr.is_null() {
+                String::new()
+            } else {
+                let slice = std::slice::from_raw_parts(alloc.ptr, alloc.size);
+                String::from_utf8_lossy(slice).into_owned()
+            };
+            alloc.free();
+            Some(SoftAssetRef::new(path))
+        } else {
+            None
+        }
+    }
+}
+
+impl GetEditorComponentValue for GameplayTagContainer {
+    unsafe fn get(
+        actor: *const ffi::AActorOpaque,
+        uuid: Uuid,
+        field: &'static str,
+    ) -> Option<Self> {
+        let mut alloc = ffi::RustAlloc::empty();
+        let code = (bindings()
+            .editor_component_fns
+            .get_editor_component_gameplay_tags)(
+            actor,
+            to_ffi_uuid(uuid),
+            ffi::Utf8Str::from(field),
+            &mut alloc,
+        );
+        if code == 1 {
+            // An empty container comes back as an empty allocation
+            let tags = if alloc.ptr.is_null() {
+                GameplayTagContainer::new()
+            } else {
+                let slice = std::slice::from_raw_parts(alloc.ptr, alloc.size);
+                GameplayTagContainer::parse(&String::from_utf8_lossy(slice))
+            };
+            alloc.free();
+            Some(tags)
+        } else {
+            None
+        }
+    }
+}
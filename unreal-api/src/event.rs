@@ -0,0 +1,237 @@
+use std::marker::PhantomData;
+
+use bevy_ecs::{
+    event::Event,
+    world::{Mut, World},
+};
+use unreal_reflect::{registry::ReflectValue, TypeUuid, Uuid};
+
+use crate::{
+    core::{from_ffi_uuid, to_ffi_uuid},
+    ffi,
+    module::bindings,
+    type_registry::TypeRegistry,
+};
+
+/// An event that can cross the boundary between Rust and blueprints. Use
+/// `#[derive(UnrealEvent)]` together with a `#[uuid = "..."]` and register it with
+/// [`Module::add_unreal_event`](crate::module::Module::add_unreal_event).
+///
+/// Blueprints send the event with `Send Rust Event`, which shows up as a normal
+/// `EventReader<T>` event in the ECS. Rust can send it to blueprints with [`BlueprintEvents`],
+/// which calls the `On Rust Event` dispatcher of the game mode.
+///
+/// ```ignore
+/// #[derive(UnrealEvent)]
+/// #[uuid = "3a1e6d2c-5b4f-4e8a-9c7d-2f1b0a9e8d7c"]
+/// pub struct DoorOpened {
+///     pub position: Vec3,
+///     pub locked: bool,
+/// }
+/// ```
+pub trait UnrealEvent: Event + TypeUuid + Sized {
+    /// Returns the reflected fields in the same order as the reflection registry.
+    fn to_values(&self) -> Vec<ReflectValue>;
+    fn from_values(values: &[ReflectValue]) -> Option<Self>;
+}
+
+pub trait SendEvent: Send + Sync {
+    /// Returns false if the values don't match the fields of the event.
+    fn send(&self, world: &mut World, values: &[ReflectValue]) -> bool;
+}
+
+pub(crate) struct EventSender<E>(PhantomData<fn() -> E>);
+
+impl<E> Default for EventSender<E> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<E: UnrealEvent> SendEvent for EventSender<E> {
+    fn send(&self, world: &mut World, values: &[ReflectValue]) -> bool {
+        match E::from_values(values) {
+            Some(event) => {
+                world.send_event(event);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Events for blueprints. They are sent once the schedule has finished running, because
+/// blueprints that handle them can call back into Rust, which needs the world.
+///
+/// ```ignore
+/// fn open_doors(mut blueprint_events: ResMut<BlueprintEvents>) {
+///     blueprint_events.send(&DoorOpened { position: Vec3::ZERO, locked: false });
+/// }
+/// ```
+#[derive(Default)]
+pub struct BlueprintEvents {
+    queue: Vec<QueuedBlueprintEvent>,
+}
+
+pub(crate) struct QueuedBlueprintEvent {
+    uuid: Uuid,
+    name: &'static str,
+    values: Vec<ReflectValue>,
+}
+// The values can hold pointers to unreal objects, which are only used on the game thread
+unsafe impl Send for BlueprintEvents {}
+unsafe impl Sync for BlueprintEvents {}
+
+impl BlueprintEvents {
+    /// Calls the `On Rust Event` dispatcher in unreal once the schedule has finished
+    pub fn send<E: UnrealEvent>(&mut self, event: &E) {
+        self.queue.push(QueuedBlueprintEvent {
+            uuid: E::TYPE_UUID,
+            name: std::any::type_name::<E>(),
+            values: event.to_values(),
+        });
+    }
+}
+
+pub(crate) fn take_blueprint_events(world: &mut World) -> Vec<QueuedBlueprintEvent> {
+    world
+        .get_resource_mut::<BlueprintEvents>()
+        .map(|mut events| std::mem::take(&mut events.queue))
+        .unwrap_or_default()
+}
+
+/// Must not be called while the world is borrowed
+pub(crate) fn send_blueprint_events(events: Vec<QueuedBlueprintEvent>) {
+    for QueuedBlueprintEvent { uuid, name, values } in events {
+        // Fields are matched up by position, so an event with a missing field isn't sent at all
+        let fields = match values
+            .iter()
+            .map(ReflectValue::to_ffi)
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(fields) => fields,
+            None => {
+                log::warn!(
+                    "Unable to send the event {} to blueprints, one of its fields can't be passed \
+                     to unreal",
+                    name
+                );
+                continue;
+            }
+        };
+        unsafe {
+            (bindings().send_blueprint_event)(to_ffi_uuid(uuid), fields.as_ptr(), fields.len());
+        }
+    }
+}
+
+pub unsafe extern "C" fn send_event(
+    uuid: ffi::Uuid,
+    fields: *const ffi::ReflectValue,
+    len: usize,
+) -> u32 {
+    fn send_event_inner(uuid: ffi::Uuid, fields: &[ffi::ReflectValue]) -> Option<u32> {
+        let uuid = from_ffi_uuid(uuid);
+        let values = fields
+            .iter()
+            .map(|value| unsafe { ReflectValue::from_ffi(value) })
+            .collect::<Option<Vec<_>>>()?;
+        // Blueprints that handle an event from Rust can send one back while the world is still
+        // borrowed. Those are sent once Rust is done with the world.
+        if crate::module::is_module_borrowed() {
+            crate::module::defer_while_borrowed(Box::new(move |world| {
+                world.resource_scope(|world, registry: Mut<TypeRegistry>| {
+                    let sent = registry
+                        .reflection_registry()
+                        .events
+                        .get(&uuid)
+                        .is_some_and(|sender| sender.send(world, &values));
+                    if !sent {
+                        log::warn!("Unable to send the event {} from blueprints", uuid);
+                    }
+                });
+            }));
+            return Some(1);
+        }
+        let mut global = unsafe { crate::module::global() }?;
+        let module = &mut global.core.module;
+        let sender = module.reflection_registry.events.get(&uuid)?;
+        Some(sender.send(&mut module.world, &values) as u32)
+    }
+    let fields = if fields.is_null() {
+        &[]
+    } else {
+        std::slice::from_raw_parts(fields, len)
+    };
+    let result = std::panic::catch_unwind(|| send_event_inner(uuid, fields).unwrap_or(0));
+    result.unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::event::Events;
+    use unreal_api::UnrealEvent;
+
+    use super::*;
+    use crate::{
+        core::UnrealCore,
+        module::{Global, Module, UserModule, MODULE},
+    };
+
+    #[derive(Debug, Clone, PartialEq, UnrealEvent)]
+    #[uuid = "b3c35c36-5a42-4c8d-9a2a-0a4f3b6f8e21"]
+    struct Ping {
+        strength: f32,
+    }
+
+    struct PingModule;
+
+    impl UserModule for PingModule {
+        fn initialize(&self, module: &mut Module) {
+            module.add_unreal_event::<Ping>();
+        }
+    }
+
+    fn pings(world: &World) -> Vec<Ping> {
+        world
+            .resource::<Events<Ping>>()
+            .iter_current_update_events()
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn events_sent_while_the_world_is_borrowed_are_deferred() {
+        unsafe {
+            MODULE = Some(Global {
+                core: UnrealCore::new(&PingModule),
+                module: Box::new(PingModule),
+            });
+        }
+        let fields = [ReflectValue::Float(2.0).to_ffi().unwrap()];
+        let uuid = to_ffi_uuid(Ping::TYPE_UUID);
+
+        let guard = unsafe { crate::module::global() }.unwrap();
+        assert_eq!(
+            unsafe { send_event(uuid, fields.as_ptr(), fields.len()) },
+            1
+        );
+        assert!(pings(&guard.core.module.world).is_empty());
+        drop(guard);
+
+        let guard = unsafe { crate::module::global() }.unwrap();
+        assert_eq!(
+            pings(&guard.core.module.world),
+            vec![Ping { strength: 2.0 }]
+        );
+        drop(guard);
+
+        // Without the guard the event is sent straight away
+        assert_eq!(
+            unsafe { send_event(uuid, fields.as_ptr(), fields.len()) },
+            1
+        );
+        let guard = unsafe { crate::module::global() }.unwrap();
+        assert_eq!(pings(&guard.core.module.world).len(), 2);
+    }
+}
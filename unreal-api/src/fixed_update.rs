@@ -0,0 +1,142 @@
+//! A fixed timestep stage for deterministic, frame rate independent gameplay logic.
+//!
+//! Every tick the game delta is added to an accumulator, and [`CoreStage::FixedUpdate`] runs
+//! once for every full step in it, so zero or more times per tick. Systems in that stage should
+//! use [`FixedTime::step`] instead of [`Frame::dt`].
+//!
+//! Transforms that are simulated in the fixed stage would visibly stutter, because the rendered
+//! frames don't line up with the fixed steps. Add [`InterpolateTransform`] to those entities and
+//! the transform that is uploaded to unreal is interpolated between the last two fixed steps.
+//!
+//! [`CoreStage::FixedUpdate`]: crate::core::CoreStage::FixedUpdate
+//! [`Frame::dt`]: crate::core::Frame
+use bevy_ecs::{prelude::*, schedule::ShouldRun};
+use unreal_api::Component;
+
+use crate::core::TransformComponent;
+
+#[derive(Debug, Copy, Clone)]
+pub struct FixedTime {
+    step: f32,
+    accumulator: f32,
+    /// Limits how many steps can run in a single tick. Without a limit a slow frame would cause
+    /// more steps in the next frame, which makes it even slower.
+    pub max_steps_per_tick: u32,
+    steps_this_tick: u32,
+}
+
+impl Default for FixedTime {
+    fn default() -> Self {
+        Self::from_hz(60.0)
+    }
+}
+
+impl FixedTime {
+    pub fn from_hz(hz: f32) -> Self {
+        Self::from_step(1.0 / hz)
+    }
+
+    pub fn from_step(step: f32) -> Self {
+        assert!(step > 0.0, "The fixed step needs to be positive");
+        Self {
+            step,
+            accumulator: 0.0,
+            max_steps_per_tick: 8,
+            steps_this_tick: 0,
+        }
+    }
+
+    /// Seconds that are simulated by every fixed step
+    pub fn step(&self) -> f32 {
+        self.step
+    }
+
+    pub fn set_step(&mut self, step: f32) {
+        assert!(step > 0.0, "The fixed step needs to be positive");
+        self.step = step;
+    }
+
+    pub fn set_hz(&mut self, hz: f32) {
+        self.set_step(1.0 / hz);
+    }
+
+    /// Game time that has not been simulated by a fixed step yet
+    pub fn accumulator(&self) -> f32 {
+        self.accumulator
+    }
+
+    /// How far the current frame is between the last and the next fixed step, in the range
+    /// `[0, 1)`. Use this to interpolate between the last two fixed steps.
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator / self.step).clamp(0.0, 1.0)
+    }
+
+    pub fn steps_this_tick(&self) -> u32 {
+        self.steps_this_tick
+    }
+
+    pub(crate) fn accumulate(&mut self, dt: f32) {
+        self.accumulator += dt;
+        self.steps_this_tick = 0;
+    }
+
+    fn try_step(&mut self) -> bool {
+        if self.accumulator < self.step {
+            return false;
+        }
+        if self.steps_this_tick >= self.max_steps_per_tick {
+            // Drop the time that we couldn't catch up on, the game will slow down instead
+            self.accumulator %= self.step;
+            return false;
+        }
+        self.accumulator -= self.step;
+        self.steps_this_tick += 1;
+        true
+    }
+}
+
+/// Stage run criteria of [`CoreStage::FixedUpdate`](crate::core::CoreStage::FixedUpdate)
+pub(crate) fn run_fixed_update(mut fixed: ResMut<FixedTime>) -> ShouldRun {
+    if fixed.try_step() {
+        ShouldRun::YesAndCheckAgain
+    } else {
+        ShouldRun::No
+    }
+}
+
+/// Interpolates the transform that is uploaded to unreal between the last two fixed steps. The
+/// [`TransformComponent`] of these entities is owned by the simulation, and is not downloaded from
+/// unreal after the first fixed step.
+#[derive(Default, Debug, Component)]
+#[uuid = "cab45ecc-c8d9-482c-9ee6-3b4792b8eb06"]
+pub struct InterpolateTransform {
+    #[reflect(skip)]
+    previous: Option<TransformComponent>,
+}
+
+impl InterpolateTransform {
+    pub fn is_simulated(&self) -> bool {
+        self.previous.is_some()
+    }
+
+    /// Returns the transform between the last fixed step and `current` at `alpha`
+    pub fn interpolate(&self, current: &TransformComponent, alpha: f32) -> TransformComponent {
+        match &self.previous {
+            Some(previous) => TransformComponent {
+                position: previous.position.lerp(current.position, alpha),
+                rotation: previous.rotation.slerp(current.rotation, alpha),
+                scale: previous.scale.lerp(current.scale, alpha),
+            },
+            None => current.clone(),
+        }
+    }
+}
+
+/// Runs at the start of every fixed step
+pub(crate) fn store_previous_transforms(
+    mut query: Query<(&TransformComponent, &mut InterpolateTransform)>,
+) {
+    for (transform, mut interpolate) in &mut query {
+        interpolate.previous = Some(transform.clone());
+    }
+}
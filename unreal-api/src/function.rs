@@ -0,0 +1,233 @@
+use bevy_ecs::{entity::Entity, world::World};
+use unreal_reflect::registry::{ReflectType, ReflectValue};
+
+use crate::ffi;
+
+pub struct FunctionArgument {
+    pub name: &'static str,
+    pub ty: ReflectType,
+}
+
+#[derive(Debug)]
+pub enum FunctionCallError {
+    WrongNumberOfArguments {
+        expected: usize,
+        found: usize,
+    },
+    /// The index of the argument that has the wrong type
+    WrongArgumentType(usize),
+}
+
+/// A Rust function that can be called from blueprints. Implemented by the `#[unreal_function]`
+/// attribute and registered with
+/// [`Module::add_unreal_function`](crate::module::Module::add_unreal_function).
+///
+/// The first two parameters of the function are always `&mut World` and the `Entity` that the
+/// function was called on, every other parameter needs to be a reflected type.
+///
+/// ```ignore
+/// #[unreal_function]
+/// pub fn apply_damage(world: &mut World, entity: Entity, amount: f32) -> bool {
+///     ...
+/// }
+///
+/// module.add_unreal_function::<apply_damage>();
+/// ```
+pub trait UnrealFunction: 'static {
+    fn name(&self) -> &'static str;
+    fn arguments(&self) -> &'static [FunctionArgument];
+    fn return_type(&self) -> Option<ReflectType>;
+    fn call(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        arguments: &[ReflectValue],
+    ) -> Result<Option<ReflectValue>, FunctionCallError>;
+}
+
+#[derive(Default)]
+pub struct FunctionRegistry {
+    functions: Vec<Box<dyn UnrealFunction>>,
+    // Unreal reads strings of the return value after `call_function` returned
+    return_value: Option<ReflectValue>,
+}
+
+impl FunctionRegistry {
+    pub fn register<F: UnrealFunction + Default>(&mut self) {
+        let function = F::default();
+        if self.get(function.name()).is_some() {
+            panic!("Duplicated unreal function {}", function.name());
+        }
+        self.functions.push(Box::new(function));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn UnrealFunction> {
+        self.functions
+            .iter()
+            .find(|function| function.name() == name)
+            .map(Box::as_ref)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn UnrealFunction> {
+        self.functions.iter().map(Box::as_ref)
+    }
+}
+
+fn with_function<R>(idx: u32, f: impl FnOnce(&dyn UnrealFunction) -> R) -> Option<R> {
+    let global = unsafe { crate::module::global() }?;
+    let function = global.core.module.functions.functions.get(idx as usize)?;
+    Some(f(function.as_ref()))
+}
+
+unsafe extern "C" fn number_of_functions(out: *mut u32) -> u32 {
+    let result = std::panic::catch_unwind(|| {
+        if let Some(global) = crate::module::global() {
+            *out = global.core.module.functions.functions.len() as u32;
+            1
+        } else {
+            0
+        }
+    });
+    result.unwrap_or(0)
+}
+
+unsafe extern "C" fn get_function_name(idx: u32, out: *mut ffi::Utf8Str) -> u32 {
+    let result = std::panic::catch_unwind(|| {
+        if let Some(name) = with_function(idx, |function| function.name()) {
+            *out = ffi::Utf8Str::from(name);
+            1
+        } else {
+            0
+        }
+    });
+    result.unwrap_or(0)
+}
+
+unsafe extern "C" fn get_function_number_of_arguments(idx: u32, out: *mut u32) -> u32 {
+    let result = std::panic::catch_unwind(|| {
+        if let Some(len) = with_function(idx, |function| function.arguments().len()) {
+            *out = len as u32;
+            1
+        } else {
+            0
+        }
+    });
+    result.unwrap_or(0)
+}
+
+unsafe extern "C" fn get_function_argument(
+    idx: u32,
+    argument_idx: u32,
+    name: *mut ffi::Utf8Str,
+    ty: *mut ffi::ReflectionType,
+) -> u32 {
+    let result = std::panic::catch_unwind(|| {
+        if let Some(argument) = with_function(idx, |function| {
+            function.arguments().get(argument_idx as usize)
+        })
+        .flatten()
+        {
+            *name = ffi::Utf8Str::from(argument.name);
+            *ty = argument.ty.into();
+            1
+        } else {
+            0
+        }
+    });
+    result.unwrap_or(0)
+}
+
+unsafe extern "C" fn get_function_return_type(
+    idx: u32,
+    has_return_value: *mut u32,
+    ty: *mut ffi::ReflectionType,
+) -> u32 {
+    let result = std::panic::catch_unwind(|| {
+        if let Some(return_type) = with_function(idx, |function| function.return_type()) {
+            match return_type {
+                Some(return_type) => {
+                    *has_return_value = 1;
+                    *ty = return_type.into();
+                }
+                None => *has_return_value = 0,
+            }
+            1
+        } else {
+            0
+        }
+    });
+    result.unwrap_or(0)
+}
+
+unsafe extern "C" fn call_function(
+    name: ffi::Utf8Str,
+    entity: ffi::Entity,
+    arguments: *const ffi::ReflectValue,
+    len: usize,
+    has_return_value: *mut u32,
+    out: *mut ffi::ReflectValue,
+) -> u32 {
+    fn call_function_inner(
+        name: &str,
+        entity: Entity,
+        arguments: &[ffi::ReflectValue],
+    ) -> Option<Option<ffi::ReflectValue>> {
+        let mut global = unsafe { crate::module::global() }?;
+        let module = &mut global.core.module;
+        let arguments = arguments
+            .iter()
+            .map(|value| unsafe { ReflectValue::from_ffi(value) })
+            .collect::<Option<Vec<_>>>()?;
+        let function = module.functions.get(name)?;
+        let return_value = match function.call(&mut module.world, entity, &arguments) {
+            Ok(return_value) => return_value,
+            Err(err) => {
+                log::error!("Calling unreal function {} failed: {:?}", name, err);
+                return None;
+            }
+        };
+        module.functions.return_value = return_value;
+        Some(
+            module
+                .functions
+                .return_value
+                .as_ref()
+                .and_then(ReflectValue::to_ffi),
+        )
+    }
+    let result = std::panic::catch_unwind(|| {
+        let name = std::str::from_utf8(std::slice::from_raw_parts(name.ptr as *const u8, name.len));
+        let arguments = if arguments.is_null() {
+            &[]
+        } else {
+            std::slice::from_raw_parts(arguments, len)
+        };
+        match name
+            .ok()
+            .and_then(|name| call_function_inner(name, Entity::from_bits(entity.id), arguments))
+        {
+            Some(Some(return_value)) => {
+                *has_return_value = 1;
+                *out = return_value;
+                1
+            }
+            Some(None) => {
+                *has_return_value = 0;
+                1
+            }
+            None => 0,
+        }
+    });
+    result.unwrap_or(0)
+}
+
+pub fn create_function_fns() -> ffi::FunctionFns {
+    ffi::FunctionFns {
+        number_of_functions,
+        get_function_name,
+        get_function_number_of_arguments,
+        get_function_argument,
+        get_function_return_type,
+        call_function,
+    }
+}
@@ -0,0 +1,387 @@
+//! Hierarchical gameplay tags, eg `Weapon.Ranged.Rifle`, that designers can author on actors
+//! through the [`GameplayTags`] editor component.
+//!
+//! Tags map to unreal's `FGameplayTag`. A container crosses the ffi boundary as a single string
+//! with the tag names separated by `,`. Tags that are not registered in unreal's tag table are
+//! dropped when they are passed to unreal.
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use unreal_api::Component;
+use unreal_reflect::registry::{
+    FromReflectValue, ReflectDyn, ReflectStatic, ReflectType, ReflectValue,
+};
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GameplayTag(String);
+
+impl GameplayTag {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// `Weapon.Ranged.Rifle` has the parent `Weapon.Ranged`
+    pub fn parent(&self) -> Option<GameplayTag> {
+        self.0
+            .rfind('.')
+            .map(|idx| GameplayTag::new(&self.0[..idx]))
+    }
+
+    /// Returns true if `self` is `other` or a child of `other`. `Weapon.Ranged.Rifle` matches
+    /// `Weapon.Ranged` and `Weapon`, but not `Weapon.Ran`.
+    pub fn matches(&self, other: &GameplayTag) -> bool {
+        match self.0.strip_prefix(other.as_str()) {
+            Some(rest) => rest.is_empty() || rest.starts_with('.'),
+            None => false,
+        }
+    }
+
+    pub fn matches_exact(&self, other: &GameplayTag) -> bool {
+        self == other
+    }
+}
+
+impl From<&str> for GameplayTag {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for GameplayTag {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl fmt::Display for GameplayTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A sorted set of [`GameplayTag`]s. Every query is hierarchical unless it ends in `_exact`.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct GameplayTagContainer {
+    tags: Vec<GameplayTag>,
+}
+
+impl GameplayTagContainer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses tag names separated by `,`. Whitespace and empty names are ignored.
+    pub fn parse(tags: &str) -> Self {
+        tags.split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(GameplayTag::from)
+            .collect()
+    }
+
+    /// Returns false if the tag was already in the container
+    pub fn add(&mut self, tag: impl Into<GameplayTag>) -> bool {
+        let tag = tag.into();
+        if tag.is_empty() {
+            return false;
+        }
+        match self.tags.binary_search(&tag) {
+            Ok(_) => false,
+            Err(idx) => {
+                self.tags.insert(idx, tag);
+                true
+            }
+        }
+    }
+
+    /// Returns false if the tag was not in the container
+    pub fn remove(&mut self, tag: &GameplayTag) -> bool {
+        match self.tags.binary_search(tag) {
+            Ok(idx) => {
+                self.tags.remove(idx);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.tags.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &GameplayTag> {
+        self.tags.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tags.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// Returns true if any tag in the container matches `tag`, see [`GameplayTag::matches`]
+    pub fn has_tag(&self, tag: &GameplayTag) -> bool {
+        self.tags.iter().any(|own| own.matches(tag))
+    }
+
+    pub fn has_tag_exact(&self, tag: &GameplayTag) -> bool {
+        self.tags.binary_search(tag).is_ok()
+    }
+
+    /// Returns false if `tags` is empty
+    pub fn has_any<'a>(&self, tags: impl IntoIterator<Item = &'a GameplayTag>) -> bool {
+        tags.into_iter().any(|tag| self.has_tag(tag))
+    }
+
+    /// Returns true if `tags` is empty
+    pub fn has_all<'a>(&self, tags: impl IntoIterator<Item = &'a GameplayTag>) -> bool {
+        tags.into_iter().all(|tag| self.has_tag(tag))
+    }
+
+    pub fn has_any_exact<'a>(&self, tags: impl IntoIterator<Item = &'a GameplayTag>) -> bool {
+        tags.into_iter().any(|tag| self.has_tag_exact(tag))
+    }
+
+    pub fn has_all_exact<'a>(&self, tags: impl IntoIterator<Item = &'a GameplayTag>) -> bool {
+        tags.into_iter().all(|tag| self.has_tag_exact(tag))
+    }
+
+    pub fn matches(&self, query: &GameplayTagQuery) -> bool {
+        query.matches(self)
+    }
+}
+
+impl<T: Into<GameplayTag>> FromIterator<T> for GameplayTagContainer {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut container = Self::new();
+        for tag in iter {
+            container.add(tag);
+        }
+        container
+    }
+}
+
+impl<'a> IntoIterator for &'a GameplayTagContainer {
+    type Item = &'a GameplayTag;
+    type IntoIter = std::slice::Iter<'a, GameplayTag>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tags.iter()
+    }
+}
+
+impl fmt::Display for GameplayTagContainer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, tag) in self.tags.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(",")?;
+            }
+            f.write_str(tag.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+impl ReflectDyn for GameplayTagContainer {
+    fn name(&self) -> &'static str {
+        "GameplayTagContainer"
+    }
+
+    fn get_value(&self) -> ReflectValue {
+        ReflectValue::GameplayTags(self.to_string())
+    }
+}
+
+impl ReflectStatic for GameplayTagContainer {
+    const TYPE: ReflectType = ReflectType::GameplayTags;
+}
+
+impl FromReflectValue for GameplayTagContainer {
+    fn from_value(value: &ReflectValue) -> Option<Self> {
+        match value {
+            ReflectValue::GameplayTags(tags) => Some(Self::parse(tags)),
+            _ => None,
+        }
+    }
+}
+
+impl From<GameplayTagContainer> for ReflectValue {
+    fn from(tags: GameplayTagContainer) -> Self {
+        ReflectValue::GameplayTags(tags.to_string())
+    }
+}
+
+/// A tag query expression, modelled after unreal's `FGameplayTagQuery`.
+///
+/// ```ignore
+/// // Ranged weapons that are not broken
+/// let query = GameplayTagQuery::all_expr([
+///     GameplayTagQuery::any_tags(["Weapon.Ranged"]),
+///     GameplayTagQuery::no_tags(["State.Broken"]),
+/// ]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameplayTagQuery {
+    AnyTagsMatch(Vec<GameplayTag>),
+    AllTagsMatch(Vec<GameplayTag>),
+    NoTagsMatch(Vec<GameplayTag>),
+    AnyExprMatch(Vec<GameplayTagQuery>),
+    AllExprMatch(Vec<GameplayTagQuery>),
+    NoExprMatch(Vec<GameplayTagQuery>),
+}
+
+impl GameplayTagQuery {
+    pub fn any_tags<T: Into<GameplayTag>>(tags: impl IntoIterator<Item = T>) -> Self {
+        Self::AnyTagsMatch(tags.into_iter().map(Into::into).collect())
+    }
+
+    pub fn all_tags<T: Into<GameplayTag>>(tags: impl IntoIterator<Item = T>) -> Self {
+        Self::AllTagsMatch(tags.into_iter().map(Into::into).collect())
+    }
+
+    pub fn no_tags<T: Into<GameplayTag>>(tags: impl IntoIterator<Item = T>) -> Self {
+        Self::NoTagsMatch(tags.into_iter().map(Into::into).collect())
+    }
+
+    pub fn any_expr(queries: impl IntoIterator<Item = GameplayTagQuery>) -> Self {
+        Self::AnyExprMatch(queries.into_iter().collect())
+    }
+
+    pub fn all_expr(queries: impl IntoIterator<Item = GameplayTagQuery>) -> Self {
+        Self::AllExprMatch(queries.into_iter().collect())
+    }
+
+    pub fn no_expr(queries: impl IntoIterator<Item = GameplayTagQuery>) -> Self {
+        Self::NoExprMatch(queries.into_iter().collect())
+    }
+
+    pub fn matches(&self, tags: &GameplayTagContainer) -> bool {
+        match self {
+            Self::AnyTagsMatch(query) => tags.has_any(query),
+            Self::AllTagsMatch(query) => tags.has_all(query),
+            Self::NoTagsMatch(query) => !tags.has_any(query),
+            Self::AnyExprMatch(exprs) => exprs.iter().any(|expr| expr.matches(tags)),
+            Self::AllExprMatch(exprs) => exprs.iter().all(|expr| expr.matches(tags)),
+            Self::NoExprMatch(exprs) => !exprs.iter().any(|expr| expr.matches(tags)),
+        }
+    }
+}
+
+/// Designer authored tags of an entity. Systems can filter entities with
+/// [`GameplayTagContainer::matches`] or the `has_*` helpers.
+#[derive(Default, Debug, Clone, Component)]
+#[uuid = "627ad717-5e00-437c-b887-22bc60232ed7"]
+#[reflect(editor)]
+pub struct GameplayTags {
+    pub tags: GameplayTagContainer,
+}
+
+impl Deref for GameplayTags {
+    type Target = GameplayTagContainer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.tags
+    }
+}
+
+impl DerefMut for GameplayTags {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.tags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(name: &str) -> GameplayTag {
+        GameplayTag::new(name)
+    }
+
+    #[test]
+    fn tags_match_their_parents() {
+        let rifle = tag("Weapon.Ranged.Rifle");
+        assert!(rifle.matches(&tag("Weapon.Ranged.Rifle")));
+        assert!(rifle.matches(&tag("Weapon.Ranged")));
+        assert!(rifle.matches(&tag("Weapon")));
+        assert!(!rifle.matches(&tag("Weapon.Ran")));
+        assert!(!tag("Weapon.Ranged").matches(&rifle));
+        assert!(!rifle.matches_exact(&tag("Weapon.Ranged")));
+
+        assert_eq!(rifle.parent(), Some(tag("Weapon.Ranged")));
+        assert_eq!(tag("Weapon").parent(), None);
+    }
+
+    #[test]
+    fn containers_are_sorted_without_duplicates() {
+        let mut tags = GameplayTagContainer::new();
+        assert!(tags.add("State.Stunned"));
+        assert!(tags.add("Character.Player"));
+        assert!(!tags.add("State.Stunned"));
+        assert!(!tags.add(""));
+        let names: Vec<_> = tags.iter().map(GameplayTag::as_str).collect();
+        assert_eq!(names, vec!["Character.Player", "State.Stunned"]);
+
+        assert!(tags.remove(&tag("State.Stunned")));
+        assert!(!tags.remove(&tag("State.Stunned")));
+        assert_eq!(tags.len(), 1);
+    }
+
+    #[test]
+    fn containers_round_trip_through_strings() {
+        let tags = GameplayTagContainer::parse(" Weapon.Ranged , ,Character.Player,Weapon.Ranged");
+        assert_eq!(tags.to_string(), "Character.Player,Weapon.Ranged");
+        assert_eq!(GameplayTagContainer::parse(&tags.to_string()), tags);
+        assert!(GameplayTagContainer::parse("").is_empty());
+    }
+
+    #[test]
+    fn queries() {
+        let tags: GameplayTagContainer = ["Weapon.Ranged.Rifle", "State.Loaded"]
+            .into_iter()
+            .collect();
+        let none: [&str; 0] = [];
+
+        assert!(GameplayTagQuery::any_tags(["Weapon.Melee", "Weapon.Ranged"]).matches(&tags));
+        assert!(!GameplayTagQuery::any_tags(["Weapon.Melee"]).matches(&tags));
+        assert!(!GameplayTagQuery::any_tags(none).matches(&tags));
+
+        assert!(GameplayTagQuery::all_tags(["Weapon", "State.Loaded"]).matches(&tags));
+        assert!(!GameplayTagQuery::all_tags(["Weapon", "State.Broken"]).matches(&tags));
+        assert!(GameplayTagQuery::all_tags(none).matches(&tags));
+
+        assert!(GameplayTagQuery::no_tags(["State.Broken"]).matches(&tags));
+        assert!(!GameplayTagQuery::no_tags(["State"]).matches(&tags));
+        assert!(GameplayTagQuery::no_tags(none).matches(&tags));
+
+        let ranged = GameplayTagQuery::any_tags(["Weapon.Ranged"]);
+        let broken = GameplayTagQuery::any_tags(["State.Broken"]);
+        assert!(GameplayTagQuery::any_expr([ranged.clone(), broken.clone()]).matches(&tags));
+        assert!(!GameplayTagQuery::all_expr([ranged.clone(), broken.clone()]).matches(&tags));
+        assert!(GameplayTagQuery::all_expr([
+            ranged.clone(),
+            GameplayTagQuery::no_tags(["State.Broken"])
+        ])
+        .matches(&tags));
+        assert!(!GameplayTagQuery::no_expr([ranged, broken.clone()]).matches(&tags));
+        assert!(GameplayTagQuery::no_expr([broken]).matches(&tags));
+
+        let empty = GameplayTagContainer::new();
+        assert!(!empty.has_any([&tag("Weapon")]));
+        assert!(!empty.has_all([&tag("Weapon")]));
+        assert!(!empty.has_any([]));
+        assert!(empty.has_all([]));
+    }
+}
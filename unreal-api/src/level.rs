@@ -0,0 +1,219 @@
+//! Level streaming and map travel.
+//!
+//! Every entity that was created for an actor gets a [`Level`] component with the level that the
+//! actor is placed in. Unreal reports streaming levels as they are loaded, shown, hidden and
+//! unloaded. Once a level is hidden its actors are removed from the world, so all entities of that
+//! level are despawned. The actors themselves are owned by unreal and are not destroyed. When the
+//! level is shown again its actors are spawned as new entities.
+//!
+//! Streaming is requested through the [`Levels`] resource, which also keeps track of the streaming
+//! levels that are currently loaded and visible.
+use std::collections::HashSet;
+
+use bevy_ecs::prelude::*;
+use unreal_api::Component;
+
+use crate::{
+    api::UnrealApi,
+    core::ActorPtr,
+    ffi::{self, ULevelOpaque},
+    module::bindings,
+};
+
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct LevelPtr(pub *mut ULevelOpaque);
+
+impl LevelPtr {
+    pub fn of_actor(actor: ActorPtr) -> Self {
+        unsafe { Self((bindings().level_fns.get_actor_level)(actor.0)) }
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.0.is_null()
+    }
+
+    /// The map name of the level, eg `Forest_01`
+    pub fn name(&self) -> String {
+        unsafe {
+            let mut alloc = ffi::RustAlloc::empty();
+            (bindings().level_fns.get_level_name)(self.0, &mut alloc);
+            let name = {
+                let slice = std::slice::from_raw_parts(alloc.ptr, alloc.size);
+                String::from_utf8_lossy(slice).into_owned()
+            };
+            alloc.free();
+            name
+        }
+    }
+
+    /// The persistent level is never streamed out
+    pub fn is_persistent(&self) -> bool {
+        unsafe { (bindings().level_fns.is_persistent_level)(self.0) == 1 }
+    }
+}
+
+unsafe impl Send for LevelPtr {}
+unsafe impl Sync for LevelPtr {}
+impl Default for LevelPtr {
+    fn default() -> Self {
+        Self(std::ptr::null_mut())
+    }
+}
+
+/// The level of the actor of an entity. Actors that are spawned at runtime belong to the
+/// persistent level.
+#[derive(Default, Debug, Component)]
+#[uuid = "c75be2e7-aeca-46b0-8d6f-5441c005cee9"]
+pub struct Level {
+    #[reflect(skip)]
+    pub level: LevelPtr,
+    #[reflect(skip)]
+    pub name: String,
+    #[reflect(skip)]
+    pub is_persistent: bool,
+}
+
+impl Level {
+    pub fn of_actor(actor: ActorPtr) -> Self {
+        let level = LevelPtr::of_actor(actor);
+        if level.is_null() {
+            return Self::default();
+        }
+        Self {
+            level,
+            name: level.name(),
+            is_persistent: level.is_persistent(),
+        }
+    }
+}
+
+/// A streaming level finished loading. It is not visible until [`LevelShownEvent`].
+pub struct LevelLoadedEvent {
+    pub level: LevelPtr,
+    pub name: String,
+}
+
+/// A streaming level was unloaded. The level has already been destroyed at this point.
+pub struct LevelUnloadedEvent {
+    pub name: String,
+}
+
+/// A streaming level was added to the world, its actors are spawned as entities
+pub struct LevelShownEvent {
+    pub level: LevelPtr,
+    pub name: String,
+}
+
+/// A streaming level was removed from the world, the entities of its actors are despawned
+pub struct LevelHiddenEvent {
+    pub level: LevelPtr,
+    pub name: String,
+}
+
+#[derive(Default, Debug)]
+pub struct Levels {
+    loaded: HashSet<String>,
+    visible: HashSet<String>,
+}
+
+impl Levels {
+    pub fn is_loaded(&self, name: &str) -> bool {
+        self.loaded.contains(name)
+    }
+
+    pub fn is_visible(&self, name: &str) -> bool {
+        self.visible.contains(name)
+    }
+
+    /// Names of the streaming levels that are currently loaded
+    pub fn loaded(&self) -> impl Iterator<Item = &str> {
+        self.loaded.iter().map(String::as_str)
+    }
+
+    /// Names of the streaming levels that are currently visible
+    pub fn visible(&self) -> impl Iterator<Item = &str> {
+        self.visible.iter().map(String::as_str)
+    }
+
+    /// Starts streaming in a sublevel of the current map. With `block` the whole level is loaded
+    /// when unreal updates streaming in the next tick, which causes a hitch, instead of over
+    /// several frames. The events are sent in the next tick either way. Returns false if the map
+    /// has no streaming level with that name.
+    pub fn load(&self, name: &str, make_visible: bool, block: bool) -> bool {
+        unsafe {
+            (bindings().level_fns.load_stream_level)(
+                ffi::Utf8Str::from(name),
+                make_visible as u32,
+                block as u32,
+            ) == 1
+        }
+    }
+
+    /// Starts streaming out a sublevel. Returns false if the map has no streaming level with that
+    /// name.
+    pub fn unload(&self, name: &str) -> bool {
+        unsafe { (bindings().level_fns.unload_stream_level)(ffi::Utf8Str::from(name)) == 1 }
+    }
+
+    /// Shows or hides a sublevel without unloading it. Returns false if the map has no streaming
+    /// level with that name.
+    pub fn set_visible(&self, name: &str, visible: bool) -> bool {
+        unsafe {
+            (bindings().level_fns.set_stream_level_visible)(
+                ffi::Utf8Str::from(name),
+                visible as u32,
+            ) == 1
+        }
+    }
+
+    /// Travels to another map at the end of the frame. This ends play, see
+    /// [`CoreStage::Shutdown`](crate::core::CoreStage::Shutdown).
+    pub fn open(&self, name: &str) {
+        unsafe {
+            (bindings().level_fns.open_level)(ffi::Utf8Str::from(name));
+        }
+    }
+
+    pub(crate) fn on_loaded(&mut self, name: &str) {
+        self.loaded.insert(name.to_string());
+    }
+
+    pub(crate) fn on_unloaded(&mut self, name: &str) {
+        self.loaded.remove(name);
+        self.visible.remove(name);
+    }
+
+    pub(crate) fn on_shown(&mut self, name: &str) {
+        self.visible.insert(name.to_string());
+    }
+
+    pub(crate) fn on_hidden(&mut self, name: &str) {
+        self.visible.remove(name);
+    }
+}
+
+/// Despawns the entities of levels that were removed from the world. The actors are owned by the
+/// level, so unlike [`Despawn`](crate::core::Despawn) this doesn't destroy them.
+pub(crate) fn despawn_level_entities(
+    mut api: ResMut<UnrealApi>,
+    mut hidden: EventReader<LevelHiddenEvent>,
+    mut unloaded: EventReader<LevelUnloadedEvent>,
+    query: Query<(Entity, &Level)>,
+    mut commands: Commands,
+) {
+    let hidden: HashSet<LevelPtr> = hidden.iter().map(|event| event.level).collect();
+    // Hidden always comes before unloaded, this only catches entities that were spawned in
+    // between
+    let unloaded: HashSet<&str> = unloaded.iter().map(|event| event.name.as_str()).collect();
+    if hidden.is_empty() && unloaded.is_empty() {
+        return;
+    }
+    for (entity, level) in &query {
+        if hidden.contains(&level.level) || unloaded.contains(level.name.as_str()) {
+            if let Some(actor) = api.entity_to_actor.remove(&entity) {
+                api.actor_to_entity.remove(&actor);
+            }
+            commands.entity(entity).despawn();
+        }
+    }
+}
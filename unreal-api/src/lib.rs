@@ -2,16 +2,35 @@
 extern crate self as unreal_api;
 
 pub mod api;
+pub mod asset;
+pub mod collision;
+pub mod debug_draw;
 pub use unreal_ffi as ffi;
 pub mod core;
 pub mod editor_component;
+pub mod event;
+pub mod fixed_update;
+pub mod function;
+pub mod gameplay_tags;
 pub mod input;
+pub mod level;
 pub mod log;
 pub mod module;
+pub mod object;
 pub mod physics;
 pub mod plugin;
+#[cfg(feature = "save_game")]
+pub mod save_game;
+#[cfg(feature = "serde")]
+pub mod snapshot;
 pub mod sound;
-pub use unreal_api_derive::Component;
+pub mod state;
+pub mod time;
+pub mod timer;
+#[cfg(feature = "tracing")]
+pub mod tracing;
+pub mod type_registry;
+pub use unreal_api_derive::{unreal_function, Component, UnrealEvent};
 
 // TODO: Here for the unreal_api_derive macro. Lets restructure this
 pub use bevy_ecs as ecs;
@@ -1,21 +1,127 @@
-use glam::{Quat, Vec3};
-use log::{set_boxed_logger, set_max_level, LevelFilter, Metadata, Record, SetLoggerError};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::RwLock,
+};
+
+use glam::{Quat, Vec2, Vec3};
+use log::{set_boxed_logger, set_max_level, Level, LevelFilter, Metadata, Record, SetLoggerError};
 use unreal_ffi as ffi;
 use unreal_ffi::Color;
 
-use crate::{core::ActorPtr, module::bindings, physics::CollisionShape};
+use crate::{
+    core::ActorPtr,
+    module::{bindings, try_bindings},
+    physics::CollisionShape,
+};
+
+struct LogFilter {
+    default: LevelFilter,
+    categories: BTreeMap<String, LevelFilter>,
+}
+
+impl LogFilter {
+    fn level(&self, category: &str) -> LevelFilter {
+        self.categories
+            .get(category)
+            .copied()
+            .unwrap_or(self.default)
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.categories
+            .values()
+            .copied()
+            .fold(self.default, Ord::max)
+    }
+}
+
+static FILTER: RwLock<LogFilter> = RwLock::new(LogFilter {
+    default: LevelFilter::Info,
+    categories: BTreeMap::new(),
+});
+
+/// Sets the level for every category that doesn't have its own level set with
+/// [`set_category_level`].
+pub fn set_default_level(level: LevelFilter) {
+    let mut filter = FILTER.write().unwrap();
+    filter.default = level;
+    set_max_level(filter.max_level());
+}
+
+/// Sets the level of a single category at runtime, eg `set_category_level("unreal_movement",
+/// LevelFilter::Trace)`.
+pub fn set_category_level(category: &str, level: LevelFilter) {
+    let mut filter = FILTER.write().unwrap();
+    filter.categories.insert(category.to_string(), level);
+    set_max_level(filter.max_level());
+}
+
+/// Makes the category fall back to the default level again.
+pub fn reset_category_level(category: &str) {
+    let mut filter = FILTER.write().unwrap();
+    filter.categories.remove(category);
+    set_max_level(filter.max_level());
+}
+
+/// The `log` target is used as the unreal log category. The target defaults to the module path,
+/// in that case we only use the crate name so that we don't end up with a category per module.
+pub fn category_for_target(target: &str) -> &str {
+    target.split("::").next().unwrap_or(target)
+}
+
+pub fn is_enabled(category: &str, level: Level) -> bool {
+    level <= FILTER.read().unwrap().level(category)
+}
+
+pub fn to_verbosity(level: Level) -> ffi::LogVerbosity {
+    match level {
+        Level::Error => ffi::LogVerbosity::Error,
+        Level::Warn => ffi::LogVerbosity::Warning,
+        Level::Info => ffi::LogVerbosity::Display,
+        Level::Debug => ffi::LogVerbosity::Log,
+        Level::Trace => ffi::LogVerbosity::Verbose,
+    }
+}
+
+/// Sends a single log line to unreal. This bypasses the category filter.
+pub fn write_record(
+    category: &str,
+    level: Level,
+    message: &str,
+    file: Option<&str>,
+    line: Option<u32>,
+) {
+    let record = ffi::LogRecord {
+        category: ffi::Utf8Str::from(category),
+        verbosity: to_verbosity(level),
+        message: ffi::Utf8Str::from(message),
+        file: ffi::Utf8Str::from(file.unwrap_or("")),
+        line: line.unwrap_or(0),
+    };
+    // The log binding locks its category map and unreal's log is thread safe, so this skips the
+    // game thread check of `bindings`. Systems that run on worker threads can log as well.
+    if let Some(bindings) = try_bindings() {
+        unsafe { (bindings.log)(&record) };
+    }
+}
+
 struct UnrealLogger;
 
 impl log::Log for UnrealLogger {
-    fn enabled(&self, _metadata: &Metadata) -> bool {
-        // TODO
-        true
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        is_enabled(category_for_target(metadata.target()), metadata.level())
     }
 
     fn log(&self, record: &Record) {
         if self.enabled(record.metadata()) {
             let text = record.args().to_string();
-            (crate::module::bindings().log)(text.as_ptr() as *const _, text.len() as i32);
+            write_record(
+                category_for_target(record.target()),
+                record.level(),
+                &text,
+                record.file(),
+                record.line(),
+            );
         }
     }
 
@@ -23,7 +129,96 @@ impl log::Log for UnrealLogger {
 }
 
 pub fn init() -> Result<(), SetLoggerError> {
-    set_boxed_logger(Box::new(UnrealLogger)).map(|()| set_max_level(LevelFilter::Info))
+    set_boxed_logger(Box::new(UnrealLogger))
+        .map(|()| set_max_level(FILTER.read().unwrap().max_level()))
+}
+
+static DISABLED_VISUAL_LOG_CATEGORIES: RwLock<BTreeSet<String>> = RwLock::new(BTreeSet::new());
+
+/// Visual log categories are enabled by default. A disabled category skips the call into unreal
+/// completely.
+pub fn set_visual_log_category_enabled(category: &str, enabled: bool) {
+    let mut disabled = DISABLED_VISUAL_LOG_CATEGORIES.write().unwrap();
+    if enabled {
+        disabled.remove(category);
+    } else {
+        disabled.insert(category.to_string());
+    }
+}
+
+/// Returns true if the visual logger is recording and the category hasn't been disabled. Use this
+/// to skip expensive work that only exists to build visual log entries.
+pub fn is_visual_log_enabled(category: LogCategory) -> bool {
+    let is_recording = unsafe { (bindings().visual_log_fns.is_recording)() != 0 };
+    is_recording
+        && !DISABLED_VISUAL_LOG_CATEGORIES
+            .read()
+            .unwrap()
+            .contains(category.name)
+}
+
+fn visual_log(
+    category: LogCategory,
+    actor: ActorPtr,
+    color: Color,
+    label: &str,
+    f: impl FnOnce(&ffi::VisualLogEntry),
+) {
+    if !is_visual_log_enabled(category) {
+        return;
+    }
+    let entry = ffi::VisualLogEntry {
+        category: ffi::Utf8Str::from(category.name),
+        verbosity: category.verbosity,
+        owner: actor.0,
+        color,
+        label: ffi::Utf8Str::from(label),
+    };
+    f(&entry);
+}
+
+/// Adds a line of text to the visual log entry of the actor.
+pub fn visual_log_text(category: LogCategory, actor: ActorPtr, text: &str) {
+    visual_log(category, actor, Color::WHITE, text, |entry| unsafe {
+        (bindings().visual_log_fns.text)(entry);
+    });
+}
+
+/// Draws a small marker at `position` with `text` next to it.
+pub fn visual_log_label(
+    category: LogCategory,
+    actor: ActorPtr,
+    position: Vec3,
+    text: &str,
+    color: Color,
+) {
+    visual_log(category, actor, color, text, |entry| unsafe {
+        (bindings().visual_log_fns.location)(entry, position.into(), 1.0);
+    });
+}
+
+pub fn visual_log_segment(
+    category: LogCategory,
+    actor: ActorPtr,
+    start: Vec3,
+    end: Vec3,
+    color: Color,
+) {
+    visual_log(category, actor, color, "", |entry| unsafe {
+        (bindings().visual_log_fns.segment)(entry, start.into(), end.into());
+    });
+}
+
+pub fn visual_log_arrow(
+    category: LogCategory,
+    actor: ActorPtr,
+    start: Vec3,
+    end: Vec3,
+    color: Color,
+) {
+    visual_log(category, actor, color, "", |entry| unsafe {
+        (bindings().visual_log_fns.arrow)(entry, start.into(), end.into());
+    });
 }
 
 pub fn visual_log_capsule(
@@ -35,17 +230,108 @@ pub fn visual_log_capsule(
     radius: f32,
     color: Color,
 ) {
-    unsafe {
-        (bindings().visual_log_capsule)(
-            ffi::Utf8Str::from(category.name),
-            actor.0,
+    visual_log(category, actor, color, "", |entry| unsafe {
+        (bindings().visual_log_fns.capsule)(
+            entry,
             position.into(),
             rotation.into(),
             half_height,
             radius,
-            color,
         );
-    }
+    });
+}
+
+/// Draws an axis aligned box.
+pub fn visual_log_box(category: LogCategory, actor: ActorPtr, min: Vec3, max: Vec3, color: Color) {
+    visual_log(category, actor, color, "", |entry| unsafe {
+        (bindings().visual_log_fns.aligned_box)(entry, min.into(), max.into());
+    });
+}
+
+pub fn visual_log_oriented_box(
+    category: LogCategory,
+    actor: ActorPtr,
+    position: Vec3,
+    rotation: Quat,
+    half_extent: Vec3,
+    color: Color,
+) {
+    visual_log(category, actor, color, "", |entry| unsafe {
+        (bindings().visual_log_fns.oriented_box)(
+            entry,
+            position.into(),
+            rotation.into(),
+            half_extent.into(),
+        );
+    });
+}
+
+/// `angle` is the half angle of the cone in radians.
+pub fn visual_log_cone(
+    category: LogCategory,
+    actor: ActorPtr,
+    origin: Vec3,
+    direction: Vec3,
+    length: f32,
+    angle: f32,
+    color: Color,
+) {
+    visual_log(category, actor, color, "", |entry| unsafe {
+        (bindings().visual_log_fns.cone)(entry, origin.into(), direction.into(), length, angle);
+    });
+}
+
+/// Connects all `points` with segments.
+pub fn visual_log_polyline(category: LogCategory, actor: ActorPtr, points: &[Vec3], color: Color) {
+    visual_log(category, actor, color, "", |entry| {
+        let points: Vec<ffi::Vector3> = points.iter().copied().map(Into::into).collect();
+        unsafe {
+            (bindings().visual_log_fns.polyline)(entry, points.as_ptr(), points.len());
+        }
+    });
+}
+
+/// Draws a path as a polyline with a marker on every path point and an arrow on the last segment
+/// to show the direction.
+pub fn visual_log_navigation_path(
+    category: LogCategory,
+    actor: ActorPtr,
+    points: &[Vec3],
+    color: Color,
+) {
+    const POINT_RADIUS: f32 = 5.0;
+    visual_log(category, actor, color, "", |entry| {
+        let points: Vec<ffi::Vector3> = points.iter().copied().map(Into::into).collect();
+        unsafe {
+            (bindings().visual_log_fns.polyline)(entry, points.as_ptr(), points.len());
+            for &point in &points {
+                (bindings().visual_log_fns.location)(entry, point, POINT_RADIUS);
+            }
+            if let [.., start, end] = points.as_slice() {
+                (bindings().visual_log_fns.arrow)(entry, *start, *end);
+            }
+        }
+    });
+}
+
+/// Adds a sample to the histogram `graph`. Every `data` name is its own line in the graph.
+pub fn visual_log_histogram(
+    category: LogCategory,
+    actor: ActorPtr,
+    graph: &str,
+    data: &str,
+    sample: Vec2,
+    color: Color,
+) {
+    visual_log(category, actor, color, "", |entry| unsafe {
+        (bindings().visual_log_fns.histogram)(
+            entry,
+            ffi::Utf8Str::from(graph),
+            ffi::Utf8Str::from(data),
+            sample.x,
+            sample.y,
+        );
+    });
 }
 
 pub fn visual_log_shape(
@@ -60,29 +346,21 @@ pub fn visual_log_shape(
         CollisionShape::Capsule {
             half_height,
             radius,
-        } => unsafe {
-            (bindings().visual_log_capsule)(
-                ffi::Utf8Str::from(category.name),
-                actor.0,
-                position.into(),
-                rotation.into(),
-                half_height,
-                radius,
-                color,
-            );
-        },
-        CollisionShape::Box { half_extent: _ } => {
-            unimplemented!()
+        } => visual_log_capsule(
+            category,
+            actor,
+            position,
+            rotation,
+            half_height,
+            radius,
+            color,
+        ),
+        CollisionShape::Box { half_extent } => {
+            visual_log_oriented_box(category, actor, position, rotation, half_extent, color)
+        }
+        CollisionShape::Sphere { radius } => {
+            visual_log_location(category, actor, position, radius, color)
         }
-        CollisionShape::Sphere { radius } => unsafe {
-            (bindings().visual_log_location)(
-                ffi::Utf8Str::from(category.name),
-                actor.0,
-                position.into(),
-                radius,
-                color,
-            );
-        },
     }
 }
 
@@ -93,24 +371,71 @@ pub fn visual_log_location(
     radius: f32,
     color: Color,
 ) {
-    unsafe {
-        (bindings().visual_log_location)(
-            ffi::Utf8Str::from(category.name),
-            actor.0,
-            position.into(),
-            radius,
-            color,
-        );
+    visual_log(category, actor, color, "", |entry| unsafe {
+        (bindings().visual_log_fns.location)(entry, position.into(), radius);
+    });
+}
+
+/// A snapshot of key value pairs that shows up in the status panel of the visual logger for the
+/// current frame.
+///
+/// ```ignore
+/// VisualLogStatus::new(MovementLog::STATUS)
+///     .add("Mode", format!("{:?}", movement.mode))
+///     .add("Velocity", movement.velocity)
+///     .log(actor.actor);
+/// ```
+pub struct VisualLogStatus {
+    category: LogCategory,
+    entries: Vec<(String, String)>,
+}
+
+impl VisualLogStatus {
+    pub fn new(category: LogCategory) -> Self {
+        Self {
+            category,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn add(mut self, key: impl Into<String>, value: impl std::fmt::Display) -> Self {
+        self.entries.push((key.into(), value.to_string()));
+        self
+    }
+
+    pub fn log(&self, actor: ActorPtr) {
+        visual_log(self.category, actor, Color::WHITE, "", |entry| {
+            let status: Vec<ffi::VisualLogStatusEntry> = self
+                .entries
+                .iter()
+                .map(|(key, value)| ffi::VisualLogStatusEntry {
+                    key: ffi::Utf8Str::from(key.as_str()),
+                    value: ffi::Utf8Str::from(value.as_str()),
+                })
+                .collect();
+            unsafe {
+                (bindings().visual_log_fns.status)(entry, status.as_ptr(), status.len());
+            }
+        });
     }
 }
 
 #[derive(Copy, Clone)]
 pub struct LogCategory {
     pub name: &'static str,
+    pub verbosity: ffi::LogVerbosity,
 }
 
 impl LogCategory {
     pub const fn new(name: &'static str) -> Self {
-        Self { name }
+        Self {
+            name,
+            verbosity: ffi::LogVerbosity::Log,
+        }
+    }
+
+    /// The verbosity is used to filter entries in the visual logger.
+    pub const fn with_verbosity(self, verbosity: ffi::LogVerbosity) -> Self {
+        Self { verbosity, ..self }
     }
 }
@@ -1,18 +1,28 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
+    sync::Arc,
+};
 
 use bevy_ecs::{
     event::Event,
     prelude::{Events, System},
     schedule::{Schedule, StageLabel, SystemSet, SystemStage},
     system::Resource,
+    world::FromWorld,
 };
 use unreal_reflect::{registry::ReflectDyn, uuid, TypeUuid, World};
 
 use crate::{
     core::{CoreStage, StartupStage, UnrealCore},
     editor_component::InsertEditorComponent,
-    ffi::UnrealBindings,
-    plugin::Plugin,
+    event::{EventSender, SendEvent, UnrealEvent},
+    ffi::{TickGroup, UnrealBindings},
+    function::{FunctionRegistry, UnrealFunction},
+    plugin::{Plugin, PluginGroup, PluginGroupBuilder},
+    state::{State, StateData, StateRegistry, UnrealState},
+    type_registry::TypeRegistry,
 };
 
 pub static mut MODULE: Option<Global> = None;
@@ -21,6 +31,71 @@ pub struct Global {
     pub module: Box<dyn UserModule>,
 }
 
+type DeferredEvent = Box<dyn FnOnce(&mut World)>;
+
+thread_local! {
+    static IS_MODULE_BORROWED: Cell<bool> = const { Cell::new(false) };
+    static DEFERRED_EVENTS: RefCell<Vec<DeferredEvent>> = const { RefCell::new(Vec::new()) };
+}
+
+pub(crate) fn is_module_borrowed() -> bool {
+    IS_MODULE_BORROWED.with(Cell::get)
+}
+
+/// Runs `send` with the world once the current [`GlobalGuard`] is dropped
+pub(crate) fn defer_while_borrowed(send: DeferredEvent) {
+    DEFERRED_EVENTS.with(|events| events.borrow_mut().push(send));
+}
+
+/// Exclusive access to [`MODULE`], see [`global`]
+pub(crate) struct GlobalGuard(&'static mut Global);
+
+impl std::ops::Deref for GlobalGuard {
+    type Target = Global;
+
+    fn deref(&self) -> &Global {
+        self.0
+    }
+}
+
+impl std::ops::DerefMut for GlobalGuard {
+    fn deref_mut(&mut self) -> &mut Global {
+        self.0
+    }
+}
+
+impl Drop for GlobalGuard {
+    fn drop(&mut self) {
+        let events = DEFERRED_EVENTS.with(|events| std::mem::take(&mut *events.borrow_mut()));
+        for send in events {
+            send(&mut self.0.core.module.world);
+        }
+        IS_MODULE_BORROWED.with(|borrowed| borrowed.set(false));
+    }
+}
+
+/// Every call from unreal into Rust goes through here. Unreal can call back into Rust while Rust
+/// is still using the world, eg from a blueprint that a system called. That would alias the
+/// world, so those calls are refused and `None` is returned.
+// `MODULE` is only ever accessed from the game thread
+#[allow(static_mut_refs)]
+pub(crate) unsafe fn global() -> Option<GlobalGuard> {
+    if IS_MODULE_BORROWED.with(|borrowed| borrowed.replace(true)) {
+        log::error!(
+            "Unreal called into Rust while the world was in use, eg from a blueprint that a \
+             system called. The call was ignored."
+        );
+        return None;
+    }
+    match MODULE.as_mut() {
+        Some(global) => Some(GlobalGuard(global)),
+        None => {
+            IS_MODULE_BORROWED.with(|borrowed| borrowed.set(false));
+            None
+        }
+    }
+}
+
 pub trait InitUserModule {
     fn initialize() -> Self;
 }
@@ -43,6 +118,7 @@ pub struct ReflectionRegistry {
     pub uuid_set: HashSet<uuid::Uuid>,
     pub reflect: HashMap<uuid::Uuid, Box<dyn ReflectDyn>>,
     pub insert_editor_component: HashMap<uuid::Uuid, Box<dyn InsertEditorComponent>>,
+    pub events: HashMap<uuid::Uuid, Box<dyn SendEvent>>,
 }
 
 impl ReflectionRegistry {
@@ -63,22 +139,60 @@ impl ReflectionRegistry {
 }
 
 pub struct Module {
+    /// Runs in `TG_PrePhysics`, from the tick of the game mode
     pub(crate) schedule: Schedule,
+    /// Every other tick group has its own schedule, which unreal runs with a tick function
+    pub(crate) tick_groups: HashMap<TickGroup, Schedule>,
     pub(crate) startup: Schedule,
-    pub(crate) reflection_registry: ReflectionRegistry,
+    /// Runs once in `end_play`
+    pub(crate) shutdown: Schedule,
+    /// Shared with the [`TypeRegistry`] resource once the plugins are finished
+    pub(crate) reflection_registry: Arc<ReflectionRegistry>,
+    pub(crate) functions: FunctionRegistry,
+    pub(crate) states: StateRegistry,
     pub(crate) world: World,
+    /// In the order in which they were built
+    plugins: Vec<Box<dyn Plugin>>,
+    /// Plugins that wait for their dependencies
+    pending_plugins: Vec<Box<dyn Plugin>>,
 }
 
 impl Module {
     pub fn new() -> Self {
         let mut startup = Schedule::default();
-        startup.add_stage(StartupStage, SystemStage::single_threaded());
+        startup.add_stage(StartupStage, SystemStage::parallel());
+        let mut shutdown = Schedule::default();
+        shutdown.add_stage(CoreStage::Shutdown, SystemStage::parallel());
+
+        let tick_groups = [
+            TickGroup::DuringPhysics,
+            TickGroup::PostPhysics,
+            TickGroup::LateCamera,
+            TickGroup::PostUpdateWork,
+        ]
+        .into_iter()
+        .map(|group| (group, Schedule::default()))
+        .collect();
+
+        let mut world = World::new();
+        world.insert_non_send_resource(GameThread::default());
+
+        // Events are updated in this stage, it exists from the start so that events can be added
+        // before the other stages
+        let mut schedule = Schedule::default();
+        schedule.add_stage(CoreStage::RegisterEvent, SystemStage::parallel());
 
         Self {
-            schedule: Schedule::default(),
+            schedule,
+            tick_groups,
             startup,
-            reflection_registry: ReflectionRegistry::default(),
-            world: World::new(),
+            shutdown,
+            reflection_registry: Arc::default(),
+            functions: FunctionRegistry::default(),
+            states: StateRegistry::default(),
+            world,
+            plugins: Vec::new(),
+            pending_plugins: Vec::new(),
         }
     }
     pub fn insert_resource(&mut self, resource: impl Resource) -> &mut Self {
@@ -86,55 +200,268 @@ impl Module {
         self
     }
 
+    /// Inserts the default value of a resource, unless it already exists
+    pub fn init_resource<R: Resource + FromWorld>(&mut self) -> &mut Self {
+        self.world.init_resource::<R>();
+        self
+    }
+
+    /// Changes the settings resource of a plugin. Plugins read their settings with
+    /// [`Module::init_resource`], so this can be called before or after the plugin is added.
+    ///
+    /// ```ignore
+    /// module
+    ///     .configure::<DamageSettings>(|settings| settings.forward_to_unreal = true)
+    ///     .add_plugin(DamagePlugin);
+    /// ```
+    pub fn configure<S: Resource + Default>(&mut self, f: impl FnOnce(&mut S)) -> &mut Self {
+        f(&mut self.world.get_resource_or_insert_with(S::default));
+        self
+    }
+
+    /// Non send resources can only be accessed from the game thread, systems that use them are
+    /// never run on a worker thread.
+    pub fn insert_non_send_resource<R: 'static>(&mut self, resource: R) -> &mut Self {
+        self.world.insert_non_send_resource(resource);
+        self
+    }
+
     pub fn add_stage(&mut self, label: impl StageLabel) -> &mut Self {
-        self.schedule
-            .add_stage(label, SystemStage::single_threaded());
+        self.schedule.add_stage(label, SystemStage::parallel());
+        self
+    }
+
+    /// Adds a stage to the end of the schedule of an unreal tick group
+    pub fn add_stage_to_tick_group(
+        &mut self,
+        group: TickGroup,
+        label: impl StageLabel,
+    ) -> &mut Self {
+        self.tick_groups
+            .entry(group)
+            .or_default()
+            .add_stage(label, SystemStage::parallel());
         self
     }
 
+    /// The new stage runs in the same tick group as `label`
     pub fn add_stage_after(
         &mut self,
         label: impl StageLabel,
         insert: impl StageLabel,
     ) -> &mut Self {
-        self.schedule
-            .add_stage_after(label, insert, SystemStage::single_threaded());
+        self.schedule_for(&label)
+            .add_stage_after(label, insert, SystemStage::parallel());
         self
     }
 
+    /// The new stage runs in the same tick group as `label`
     pub fn add_stage_before(
         &mut self,
         label: impl StageLabel,
         insert: impl StageLabel,
     ) -> &mut Self {
-        self.schedule
-            .add_stage_before(label, insert, SystemStage::single_threaded());
+        self.schedule_for(&label)
+            .add_stage_before(label, insert, SystemStage::parallel());
         self
     }
 
     pub fn add_system_set_to_stage(&mut self, label: impl StageLabel, set: SystemSet) -> &mut Self {
-        self.schedule.add_system_set_to_stage(label, set);
+        self.schedule_for(&label)
+            .add_system_set_to_stage(label, set);
         self
     }
 
+    /// Returns the schedule that contains the stage. Falls back to the pre physics schedule, which
+    /// panics with a useful message if the stage doesn't exist at all.
+    pub(crate) fn schedule_for(&mut self, label: &dyn StageLabel) -> &mut Schedule {
+        if self.shutdown.get_stage::<SystemStage>(label).is_some() {
+            return &mut self.shutdown;
+        }
+        let group = self
+            .tick_groups
+            .iter()
+            .find(|(_, schedule)| schedule.get_stage::<SystemStage>(label).is_some())
+            .map(|(group, _)| *group);
+        match group {
+            Some(group) => self.tick_groups.get_mut(&group).unwrap(),
+            None => &mut self.schedule,
+        }
+    }
+
     pub fn register_component<T>(&mut self)
     where
         T: InsertReflectionStruct + TypeUuid + 'static,
     {
-        self.reflection_registry.register::<T>();
+        self.reflection_registry_mut().register::<T>();
+    }
+
+    fn reflection_registry_mut(&mut self) -> &mut ReflectionRegistry {
+        Arc::get_mut(&mut self.reflection_registry)
+            .expect("Types can only be registered while the plugins are built")
     }
 
+    /// Builds the plugin right away if all of its dependencies have been built, otherwise it
+    /// waits until they are added. Panics if a unique plugin is added twice.
     pub fn add_plugin<P: Plugin>(&mut self, plugin: P) -> &mut Self {
-        plugin.build(self);
+        self.add_boxed_plugin(Box::new(plugin))
+    }
+
+    pub fn add_plugins<G: PluginGroup>(&mut self, group: G) -> &mut Self {
+        let mut builder = PluginGroupBuilder::default();
+        group.build(&mut builder);
+        for plugin in builder.finish() {
+            self.add_boxed_plugin(plugin);
+        }
         self
     }
 
+    /// Returns true if a plugin with this [`Plugin::name`] was added, even if it is still waiting
+    /// for its dependencies
+    pub fn has_plugin(&self, name: &str) -> bool {
+        self.plugins
+            .iter()
+            .chain(&self.pending_plugins)
+            .any(|plugin| plugin.name() == name)
+    }
+
+    fn is_plugin_built(&self, name: &str) -> bool {
+        self.plugins.iter().any(|plugin| plugin.name() == name)
+    }
+
+    fn add_boxed_plugin(&mut self, plugin: Box<dyn Plugin>) -> &mut Self {
+        if plugin.is_unique() && self.has_plugin(plugin.name()) {
+            panic!("Plugin {} was added twice", plugin.name());
+        }
+        self.pending_plugins.push(plugin);
+        self.build_ready_plugins();
+        self
+    }
+
+    fn build_ready_plugins(&mut self) {
+        // Building a plugin can make other pending plugins ready
+        while let Some(idx) = self.pending_plugins.iter().position(|plugin| {
+            plugin
+                .dependencies()
+                .iter()
+                .all(|dependency| self.is_plugin_built(dependency))
+        }) {
+            let plugin = self.pending_plugins.remove(idx);
+            plugin.build(self);
+            self.plugins.push(plugin);
+        }
+    }
+
+    /// Calls [`Plugin::finish`] once every plugin was added. Panics if a plugin is still missing
+    /// a dependency.
+    pub(crate) fn finish_plugins(&mut self) {
+        if !self.pending_plugins.is_empty() {
+            let missing: Vec<String> = self
+                .pending_plugins
+                .iter()
+                .map(|plugin| {
+                    let dependencies: Vec<_> = plugin
+                        .dependencies()
+                        .into_iter()
+                        .filter(|dependency| !self.is_plugin_built(dependency))
+                        .collect();
+                    format!("{} needs {}", plugin.name(), dependencies.join(", "))
+                })
+                .collect();
+            panic!("Missing plugin dependencies: {}", missing.join("; "));
+        }
+        let plugins = std::mem::take(&mut self.plugins);
+        for plugin in &plugins {
+            plugin.finish(self);
+        }
+        self.plugins = plugins;
+        self.world
+            .insert_resource(TypeRegistry::new(self.reflection_registry.clone()));
+    }
+
+    pub(crate) fn cleanup_plugins(&mut self) {
+        let plugins = std::mem::take(&mut self.plugins);
+        for plugin in plugins.iter().rev() {
+            plugin.cleanup(self);
+        }
+        self.plugins = plugins;
+    }
+
     pub fn add_startup_system_set(&mut self, system_set: SystemSet) -> &mut Self {
         self.startup
             .add_system_set_to_stage(StartupStage, system_set);
         self
     }
 
+    /// Systems that run once when play ends, before the world is dropped. The reason is available
+    /// as the [`EndPlay`](crate::core::EndPlay) resource.
+    pub fn add_shutdown_system_set(&mut self, system_set: SystemSet) -> &mut Self {
+        self.shutdown
+            .add_system_set_to_stage(CoreStage::Shutdown, system_set);
+        self
+    }
+
+    /// Registers an event that can be sent from blueprints into the ECS and from Rust to
+    /// blueprints. See [`UnrealEvent`].
+    pub fn add_unreal_event<E>(&mut self) -> &mut Self
+    where
+        E: UnrealEvent + InsertReflectionStruct,
+    {
+        let registry = self.reflection_registry_mut();
+        registry.register::<E>();
+        registry
+            .events
+            .insert(E::TYPE_UUID, Box::<EventSender<E>>::default());
+        self.add_event::<E>()
+    }
+
+    /// Makes a function that was annotated with `#[unreal_function]` callable from blueprints.
+    pub fn add_unreal_function<F: UnrealFunction + Default>(&mut self) -> &mut Self {
+        self.functions.register::<F>();
+        self
+    }
+
+    /// Adds a state machine that is driven in [`CoreStage::Update`], see [`crate::state`]
+    pub fn add_state<S: StateData>(&mut self, initial: S) -> &mut Self {
+        self.add_state_to_stage(CoreStage::Update, initial)
+    }
+
+    /// Adds a state machine that is driven in `stage`. The `on_enter`, `on_exit` and `on_update`
+    /// systems of the state machine run in that stage.
+    pub fn add_state_to_stage<S: StateData>(
+        &mut self,
+        stage: impl StageLabel,
+        initial: S,
+    ) -> &mut Self {
+        self.states.add::<S>(stage.as_label());
+        self.insert_resource(State::new(initial));
+        self.add_system_set_to_stage(stage, State::<S>::get_driver())
+    }
+
+    /// Adds a state machine that can also be switched from blueprints with `Set Rust State`
+    pub fn add_unreal_state<S: UnrealState>(&mut self, initial: S) -> &mut Self {
+        self.states.add_unreal::<S>();
+        self.add_state(initial)
+    }
+
+    /// Runs the systems once when the state machine enters `state`
+    pub fn on_enter<S: StateData>(&mut self, state: S, set: SystemSet) -> &mut Self {
+        let stage = self.states.stage::<S>();
+        self.add_system_set_to_stage(stage, set.with_run_criteria(State::on_enter(state)))
+    }
+
+    /// Runs the systems once when the state machine leaves `state`
+    pub fn on_exit<S: StateData>(&mut self, state: S, set: SystemSet) -> &mut Self {
+        let stage = self.states.stage::<S>();
+        self.add_system_set_to_stage(stage, set.with_run_criteria(State::on_exit(state)))
+    }
+
+    /// Runs the systems every tick while the state machine is in `state`
+    pub fn on_update<S: StateData>(&mut self, state: S, set: SystemSet) -> &mut Self {
+        let stage = self.states.stage::<S>();
+        self.add_system_set_to_stage(stage, set.with_run_criteria(State::on_update(state)))
+    }
+
     pub fn add_event<T: Event>(&mut self) -> &mut Self {
         self.world.init_resource::<Events<T>>();
         self.add_system_set_to_stage(
@@ -156,6 +483,33 @@ pub trait UserModule {
 }
 pub static mut BINDINGS: Option<UnrealBindings> = None;
 
+thread_local! {
+    static IS_GAME_THREAD: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Stages run their systems in parallel on a thread pool, but unreal can only be called from the
+/// game thread. Every system that calls into unreal, directly through [`bindings`] or through
+/// helpers like [`UnrealApi`](crate::api::UnrealApi), needs to take a `NonSend<GameThread>`, which
+/// pins it to the game thread.
+///
+/// ```ignore
+/// fn play_sounds(_game_thread: NonSend<GameThread>, query: Query<&TransformComponent>) {
+///     // Can call into unreal here
+/// }
+/// ```
+#[derive(Default)]
+pub struct GameThread(PhantomData<*const ()>);
+
+/// Remembers the thread that loaded the module as the game thread
+#[doc(hidden)]
+pub fn set_game_thread() {
+    IS_GAME_THREAD.with(|is_game_thread| is_game_thread.set(true));
+}
+
+pub fn is_game_thread() -> bool {
+    IS_GAME_THREAD.with(Cell::get)
+}
+
 #[macro_export]
 macro_rules! implement_unreal_module {
     ($module: ty) => {
@@ -184,6 +538,7 @@ macro_rules! implement_unreal_module {
                 }
             }));
             $crate::module::BINDINGS = Some(bindings);
+            $crate::module::set_game_thread();
             let _ = $crate::log::init();
 
             let r = std::panic::catch_unwind(|| unsafe {
@@ -194,10 +549,15 @@ macro_rules! implement_unreal_module {
                 $crate::ffi::RustBindings {
                     retrieve_uuids: $crate::core::retrieve_uuids,
                     tick: $crate::core::tick,
+                    tick_group: $crate::core::tick_group,
                     begin_play: $crate::core::begin_play,
+                    end_play: $crate::core::end_play,
                     unreal_event: $crate::core::unreal_event,
+                    send_event: $crate::event::send_event,
                     reflection_fns: $crate::core::create_reflection_fns(),
+                    function_fns: $crate::function::create_function_fns(),
                     allocate_fns: $crate::core::create_allocate_fns(),
+                    state_fns: $crate::state::create_state_fns(),
                 }
             });
             match r {
@@ -211,6 +571,20 @@ macro_rules! implement_unreal_module {
     };
 }
 
+/// Panics when called outside of the game thread
+// `BINDINGS` is only written once, when the module is loaded
+#[allow(static_mut_refs)]
 pub fn bindings() -> &'static UnrealBindings {
+    assert!(
+        is_game_thread(),
+        "Called into unreal from a worker thread. Add a `NonSend<GameThread>` to the system."
+    );
     unsafe { BINDINGS.as_ref().unwrap() }
 }
+
+/// Like [`bindings`] without the game thread check, for the few bindings that unreal allows on
+/// any thread. `None` until the module is loaded.
+#[allow(static_mut_refs)]
+pub(crate) fn try_bindings() -> Option<&'static UnrealBindings> {
+    unsafe { BINDINGS.as_ref() }
+}
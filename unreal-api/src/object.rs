@@ -0,0 +1,120 @@
+use unreal_reflect::registry::{FromReflectValue, ReflectValue, UClass, USound};
+
+use crate::{core::ActorPtr, ffi, module::bindings};
+
+/// A pointer to any unreal `UObject`. Functions and properties are looked up by name through the
+/// unreal reflection system, which makes it possible to call functionality that was authored in
+/// blueprints.
+///
+/// Only the types that can be stored in a [`ReflectValue`] are supported. Floats are converted
+/// to doubles and quaternions to rotators if the property expects them.
+///
+/// ```ignore
+/// let object = UObjectPtr::from(actor);
+/// object.set_property("Health", 100.0f32);
+/// let alive: Option<bool> = object.call_function_typed("IsAlive", &[]);
+/// ```
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct UObjectPtr(pub *mut ffi::UObjectOpague);
+unsafe impl Send for UObjectPtr {}
+unsafe impl Sync for UObjectPtr {}
+
+impl From<ActorPtr> for UObjectPtr {
+    fn from(actor: ActorPtr) -> Self {
+        Self(actor.0)
+    }
+}
+
+impl From<UClass> for UObjectPtr {
+    fn from(class: UClass) -> Self {
+        Self(class.ptr)
+    }
+}
+
+impl From<USound> for UObjectPtr {
+    fn from(sound: USound) -> Self {
+        Self(sound.ptr)
+    }
+}
+
+impl UObjectPtr {
+    /// Calls the `UFunction` with the given name. Returns `None` if the function doesn't exist, if
+    /// the arguments don't match its parameters or if the return value has a type that can't be
+    /// read. `Some(None)` means the call succeeded and the function has no return value.
+    pub fn call_function(
+        &self,
+        name: &str,
+        arguments: &[ReflectValue],
+    ) -> Option<Option<ReflectValue>> {
+        // `ffi_arguments` borrows strings from `arguments`
+        let ffi_arguments = arguments
+            .iter()
+            .map(ReflectValue::to_ffi)
+            .collect::<Option<Vec<_>>>()?;
+        let mut has_return_value = 0;
+        let mut out = std::mem::MaybeUninit::<ffi::ReflectValue>::uninit();
+        unsafe {
+            let called = (bindings().object_fns.call_function)(
+                self.0,
+                name.into(),
+                ffi_arguments.as_ptr(),
+                ffi_arguments.len(),
+                &mut has_return_value,
+                out.as_mut_ptr(),
+            ) == 1;
+            if !called {
+                return None;
+            }
+            if has_return_value == 0 {
+                return Some(None);
+            }
+            ReflectValue::from_ffi(&out.assume_init()).map(Some)
+        }
+    }
+
+    /// Calls the `UFunction` and converts the return value. Returns `None` if the call failed or
+    /// the return value has a different type.
+    pub fn call_function_typed<T: FromReflectValue>(
+        &self,
+        name: &str,
+        arguments: &[ReflectValue],
+    ) -> Option<T> {
+        self.call_function(name, arguments)
+            .flatten()
+            .as_ref()
+            .and_then(T::from_value)
+    }
+
+    /// Reads the `UProperty` with the given name.
+    pub fn get_property_value(&self, name: &str) -> Option<ReflectValue> {
+        let mut out = std::mem::MaybeUninit::<ffi::ReflectValue>::uninit();
+        unsafe {
+            if (bindings().object_fns.get_property)(self.0, name.into(), out.as_mut_ptr()) == 1 {
+                ReflectValue::from_ffi(&out.assume_init())
+            } else {
+                None
+            }
+        }
+    }
+
+    pub fn get_property<T: FromReflectValue>(&self, name: &str) -> Option<T> {
+        self.get_property_value(name)
+            .as_ref()
+            .and_then(T::from_value)
+    }
+
+    /// Writes the `UProperty` with the given name. Returns false if the property doesn't exist or
+    /// has a different type.
+    pub fn set_property_value(&self, name: &str, value: &ReflectValue) -> bool {
+        match value.to_ffi() {
+            Some(value) => unsafe {
+                (bindings().object_fns.set_property)(self.0, name.into(), &value) == 1
+            },
+            None => false,
+        }
+    }
+
+    pub fn set_property(&self, name: &str, value: impl Into<ReflectValue>) -> bool {
+        self.set_property_value(name, &value.into())
+    }
+}
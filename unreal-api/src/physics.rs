@@ -1,13 +1,107 @@
+use std::collections::HashMap;
+
 use crate::Component;
 use ffi::AActorOpaque;
 use glam::{Quat, Vec3};
 use unreal_ffi as ffi;
+use unreal_reflect::registry::{AssetRef, ParticleSystem, USound};
 
 use crate::{
     core::{ActorPtr, Primitive, UnrealPtr},
     module::bindings,
+    object::UObjectPtr,
 };
 
+/// Mirrors `EPhysicalSurface`. The names of the surface types are configured in the unreal
+/// project settings under "Physics > Physical Surface", `SurfaceType(1)` is `SurfaceType1`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SurfaceType(pub u8);
+
+impl SurfaceType {
+    pub const DEFAULT: Self = Self(0);
+
+    pub(crate) fn from_ffi(surface_type: u32) -> Self {
+        Self(surface_type as u8)
+    }
+}
+
+/// A handle to a `UPhysicalMaterial`.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct PhysicalMaterial(pub UObjectPtr);
+
+impl PhysicalMaterial {
+    pub(crate) fn from_raw(ptr: *mut ffi::UObjectOpague) -> Option<Self> {
+        (!ptr.is_null()).then_some(Self(UObjectPtr(ptr)))
+    }
+
+    pub fn friction(&self) -> Option<f32> {
+        self.0.get_property("Friction")
+    }
+
+    pub fn restitution(&self) -> Option<f32> {
+        self.0.get_property("Restitution")
+    }
+
+    pub fn density(&self) -> Option<f32> {
+        self.0.get_property("Density")
+    }
+}
+
+/// Gameplay properties of a surface type, see [`SurfaceTable`].
+#[derive(Debug, Clone, Copy)]
+pub struct SurfaceProperties {
+    /// Multiplier that movement code can apply to its own friction
+    pub friction: f32,
+    pub footstep_sound: Option<USound>,
+    pub impact_effect: Option<AssetRef<ParticleSystem>>,
+}
+
+impl Default for SurfaceProperties {
+    fn default() -> Self {
+        Self {
+            friction: 1.0,
+            footstep_sound: None,
+            impact_effect: None,
+        }
+    }
+}
+
+/// Maps surface types to gameplay properties. Surface types without an entry use the default
+/// properties.
+///
+/// ```ignore
+/// pub struct Surfaces;
+/// impl Surfaces {
+///     pub const ICE: SurfaceType = SurfaceType(1);
+/// }
+///
+/// surface_table.insert(Surfaces::ICE, SurfaceProperties { friction: 0.1, ..Default::default() });
+/// ```
+#[derive(Default, Debug)]
+pub struct SurfaceTable {
+    pub default: SurfaceProperties,
+    surfaces: HashMap<SurfaceType, SurfaceProperties>,
+}
+
+impl SurfaceTable {
+    pub fn insert(
+        &mut self,
+        surface_type: SurfaceType,
+        properties: SurfaceProperties,
+    ) -> &mut Self {
+        self.surfaces.insert(surface_type, properties);
+        self
+    }
+
+    pub fn remove(&mut self, surface_type: SurfaceType) -> Option<SurfaceProperties> {
+        self.surfaces.remove(&surface_type)
+    }
+
+    pub fn get(&self, surface_type: SurfaceType) -> &SurfaceProperties {
+        self.surfaces.get(&surface_type).unwrap_or(&self.default)
+    }
+}
+
 #[derive(Debug)]
 pub struct SweepResult {
     pub actor: Option<ActorPtr>,
@@ -17,6 +111,8 @@ pub struct SweepResult {
     pub normal: Vec3,
     pub impact_normal: Vec3,
     pub start_in_penentration: bool,
+    pub physical_material: Option<PhysicalMaterial>,
+    pub surface_type: SurfaceType,
 }
 
 #[derive(Clone, Default)]
@@ -72,6 +168,8 @@ pub fn sweep_multi(
                             penetration_depth: hit.pentration_depth,
                             start_in_penentration: hit.start_penetrating == 1,
                             impact_normal: hit.impact_normal.into(),
+                            physical_material: PhysicalMaterial::from_raw(hit.physical_material),
+                            surface_type: SurfaceType::from_ffi(hit.surface_type),
                         }
                     })
                     .collect(),
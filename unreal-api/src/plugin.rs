@@ -1,5 +1,189 @@
+//! Plugins bundle components, resources, events and systems so that gameplay crates can be added
+//! to a [`Module`] with a single call.
+//!
+//! Plugins can depend on other plugins by name. A plugin is built as soon as all of its
+//! dependencies have been built, so plugins can be added in any order. Settings of a plugin are
+//! stored as a resource, see [`Module::configure`].
 use crate::module::Module;
 
-pub trait Plugin {
+pub trait Plugin: 'static {
     fn build(&self, module: &mut Module);
+
+    /// Used for dependencies and duplicate detection. Defaults to the type name.
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Names of the plugins that need to be built before this one, see [`plugin_name`]
+    fn dependencies(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// Adding a unique plugin twice panics
+    fn is_unique(&self) -> bool {
+        true
+    }
+
+    /// Called once every plugin of the module has been built, in the order in which the plugins
+    /// were built. Use this for setup that needs to see what other plugins added.
+    fn finish(&self, _module: &mut Module) {}
+
+    /// Called before the module is torn down, in reverse build order
+    fn cleanup(&self, _module: &mut Module) {}
+}
+
+/// The default [`Plugin::name`] of `P`
+pub fn plugin_name<P: Plugin>() -> &'static str {
+    std::any::type_name::<P>()
+}
+
+/// A set of plugins that are usually added together.
+///
+/// ```ignore
+/// pub struct GameplayPlugins;
+///
+/// impl PluginGroup for GameplayPlugins {
+///     fn build(self, group: &mut PluginGroupBuilder) {
+///         group.add(MovementPlugin).add(DamagePlugin);
+///     }
+/// }
+///
+/// module.add_plugins(GameplayPlugins);
+/// ```
+pub trait PluginGroup {
+    fn build(self, group: &mut PluginGroupBuilder);
+}
+
+#[derive(Default)]
+pub struct PluginGroupBuilder {
+    plugins: Vec<Box<dyn Plugin>>,
+}
+
+impl PluginGroupBuilder {
+    pub fn add<P: Plugin>(&mut self, plugin: P) -> &mut Self {
+        self.plugins.push(Box::new(plugin));
+        self
+    }
+
+    /// Removes a plugin from the group, eg to replace it with a custom one
+    pub fn disable<P: Plugin>(&mut self) -> &mut Self {
+        self.plugins
+            .retain(|plugin| plugin.name() != plugin_name::<P>());
+        self
+    }
+
+    pub(crate) fn finish(self) -> Vec<Box<dyn Plugin>> {
+        self.plugins
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Built(Vec<&'static str>);
+
+    struct Dummy {
+        name: &'static str,
+        dependencies: Vec<&'static str>,
+        unique: bool,
+    }
+
+    fn dummy(name: &'static str, dependencies: &[&'static str]) -> Dummy {
+        Dummy {
+            name,
+            dependencies: dependencies.to_vec(),
+            unique: true,
+        }
+    }
+
+    impl Plugin for Dummy {
+        fn build(&self, module: &mut Module) {
+            module
+                .world
+                .get_resource_or_insert_with(Built::default)
+                .0
+                .push(self.name);
+        }
+
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn dependencies(&self) -> Vec<&'static str> {
+            self.dependencies.clone()
+        }
+
+        fn is_unique(&self) -> bool {
+            self.unique
+        }
+    }
+
+    fn built(module: &Module) -> Vec<&'static str> {
+        module
+            .world
+            .get_resource::<Built>()
+            .map_or_else(Vec::new, |built| built.0.clone())
+    }
+
+    #[test]
+    fn plugins_are_built_after_their_dependencies() {
+        let mut module = Module::new();
+        module
+            .add_plugin(dummy("c", &["b"]))
+            .add_plugin(dummy("b", &["a"]));
+        assert!(built(&module).is_empty());
+        assert!(module.has_plugin("c"));
+
+        module.add_plugin(dummy("a", &[]));
+        assert_eq!(built(&module), vec!["a", "b", "c"]);
+        module.finish_plugins();
+    }
+
+    #[test]
+    fn groups_can_disable_plugins() {
+        struct Group;
+        impl PluginGroup for Group {
+            fn build(self, group: &mut PluginGroupBuilder) {
+                group.add(dummy("a", &[])).add(Other).disable::<Other>();
+            }
+        }
+        struct Other;
+        impl Plugin for Other {
+            fn build(&self, _module: &mut Module) {}
+        }
+
+        let mut module = Module::new();
+        module.add_plugins(Group);
+        assert_eq!(built(&module), vec!["a"]);
+        assert!(!module.has_plugin(plugin_name::<Other>()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Plugin a was added twice")]
+    fn unique_plugins_can_only_be_added_once() {
+        Module::new()
+            .add_plugin(dummy("a", &[]))
+            .add_plugin(dummy("a", &[]));
+    }
+
+    #[test]
+    fn plugins_that_are_not_unique_can_be_added_twice() {
+        let shared = || Dummy {
+            unique: false,
+            ..dummy("shared", &[])
+        };
+        let mut module = Module::new();
+        module.add_plugin(shared()).add_plugin(shared());
+        assert_eq!(built(&module), vec!["shared", "shared"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Missing plugin dependencies: b needs a")]
+    fn missing_dependencies_panic() {
+        let mut module = Module::new();
+        module.add_plugin(dummy("b", &["a"]));
+        module.finish_plugins();
+    }
 }
@@ -0,0 +1,728 @@
+//! Saving and loading the reflected components of entities.
+//!
+//! A save walks every entity that has an actor or a [`SaveId`], and stores the reflected fields
+//! of all of its registered components, keyed by the [`TypeUuid`](crate::TypeUuid) of the
+//! component and the name of the field. Loading matches the saved entities by [`SaveKey`] and
+//! writes the fields back. Components that the entity lost in the meantime are inserted again if
+//! they are editor components or have `#[reflect(default)]`.
+//!
+//! Only plain values are saved: floats, bools, vectors, rotations, gameplay tags and soft asset
+//! references. Hard asset references and skipped fields keep the value that they have when the
+//! save is loaded. Actors that were destroyed after the save are not spawned again.
+//!
+//! ```ignore
+//! module.configure::<SaveGameSettings>(|settings| {
+//!     settings.backend = Box::new(FileBackend::new("Saved/Rust"));
+//!     settings.format = SaveFormat::Ron;
+//! });
+//!
+//! fn quick_save(input: Res<Input>, mut commands: Commands) {
+//!     if input.is_action_pressed("QuickSave") {
+//!         commands.add(SaveToSlot::new("quick"));
+//!     }
+//! }
+//! ```
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use bevy_ecs::{prelude::*, system::Command};
+use serde::{Deserialize, Serialize};
+use unreal_api::Component;
+use unreal_reflect::{
+    registry::{ReflectDyn, ReflectType, ReflectValue},
+    Uuid,
+};
+
+use crate::{
+    core::ActorComponent,
+    ffi,
+    math::{Quat, Vec3},
+    module::{bindings, ReflectionRegistry},
+    type_registry::TypeRegistry,
+};
+
+/// Bumped whenever the layout of [`SaveData`] changes
+pub const SAVE_FORMAT_VERSION: u32 = 1;
+
+const BINARY_MAGIC: &[u8; 4] = b"URSV";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SaveFormat {
+    /// Small and fast, the default
+    Binary,
+    /// Human readable, useful while developing
+    Ron,
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    /// The save game system of unreal reported an error
+    Unreal,
+    NotFound(String),
+    Encode(String),
+    Decode(String),
+    /// The save was written by a newer version of the format
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "{}", err),
+            SaveError::Unreal => write!(f, "The unreal save game system failed"),
+            SaveError::NotFound(slot) => write!(f, "There is no save in slot '{}'", slot),
+            SaveError::Encode(err) => write!(f, "Unable to encode the save: {}", err),
+            SaveError::Decode(err) => write!(f, "Unable to decode the save: {}", err),
+            SaveError::UnsupportedVersion(version) => write!(
+                f,
+                "Save format version {} is newer than {}",
+                version, SAVE_FORMAT_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<std::io::Error> for SaveError {
+    fn from(err: std::io::Error) -> Self {
+        SaveError::Io(err)
+    }
+}
+
+/// Gives an entity without an actor a stable id, so that it is saved. Saved entities with an id
+/// that doesn't exist anymore are spawned again when the save is loaded.
+#[derive(Default, Debug, Component)]
+#[uuid = "0b0e7c4d-5a43-4f0e-9d5c-2f6f8a1c3e77"]
+pub struct SaveId {
+    #[reflect(skip)]
+    pub id: String,
+}
+
+impl SaveId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+/// How a saved entity is found again when the save is loaded
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum SaveKey {
+    /// The path of the actor, see [`ActorPtr::get_actor_path`](crate::core::ActorPtr::get_actor_path)
+    Actor(String),
+    /// See [`SaveId`]
+    Id(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SavedValue {
+    Float(f32),
+    Vector3([f32; 3]),
+    Bool(bool),
+    Quat([f32; 4]),
+    SoftObjectPath(String),
+    GameplayTags(String),
+}
+
+impl SavedValue {
+    /// Returns `None` for values that can't be saved, like hard asset references
+    pub fn from_reflect(value: &ReflectValue) -> Option<Self> {
+        Some(match value {
+            ReflectValue::Float(f) => SavedValue::Float(*f),
+            ReflectValue::Vector3(v) => SavedValue::Vector3(v.to_array()),
+            ReflectValue::Bool(b) => SavedValue::Bool(*b),
+            ReflectValue::Quat(q) => SavedValue::Quat(q.to_array()),
+            ReflectValue::SoftObjectPath(_, path) => SavedValue::SoftObjectPath(path.clone()),
+            ReflectValue::GameplayTags(tags) => SavedValue::GameplayTags(tags.clone()),
+            ReflectValue::UClass(_)
+            | ReflectValue::USound(_)
+            | ReflectValue::UObject(..)
+            | ReflectValue::Composite => return None,
+        })
+    }
+
+    /// Returns `None` if the value doesn't fit the type of the field
+    pub fn to_reflect(&self, ty: ReflectType) -> Option<ReflectValue> {
+        Some(match (self, ty) {
+            (SavedValue::Float(f), ReflectType::Float) => ReflectValue::Float(*f),
+            (SavedValue::Vector3(v), ReflectType::Vector3) => {
+                ReflectValue::Vector3(Vec3::from_array(*v))
+            }
+            (SavedValue::Bool(b), ReflectType::Bool) => ReflectValue::Bool(*b),
+            (SavedValue::Quat(q), ReflectType::Quat) => ReflectValue::Quat(Quat::from_array(*q)),
+            (SavedValue::SoftObjectPath(path), ReflectType::SoftObjectPath(ty)) => {
+                ReflectValue::SoftObjectPath(ty, path.clone())
+            }
+            (SavedValue::GameplayTags(tags), ReflectType::GameplayTags) => {
+                ReflectValue::GameplayTags(tags.clone())
+            }
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedComponent {
+    pub uuid: Uuid,
+    /// Only informative, components are matched by uuid
+    pub name: String,
+    pub fields: Vec<(String, SavedValue)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedEntity {
+    pub key: SaveKey,
+    pub components: Vec<SavedComponent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveData {
+    pub format_version: u32,
+    /// See [`SaveGameSettings::game_version`]
+    pub game_version: u32,
+    pub entities: Vec<SavedEntity>,
+}
+
+impl SaveData {
+    /// Saves the reflected components of every entity with an actor or a [`SaveId`]. The output
+    /// is sorted, so saving the same world twice gives the same data.
+    pub fn capture(world: &mut World, registry: &ReflectionRegistry, game_version: u32) -> Self {
+        let mut reflected: Vec<_> = registry.reflect.iter().collect();
+        reflected.sort_by_key(|(uuid, _)| **uuid);
+
+        let mut query = world.query::<(Entity, Option<&SaveId>, Option<&ActorComponent>)>();
+        let keyed: Vec<(Entity, SaveKey)> = query
+            .iter(world)
+            .filter_map(|(entity, id, actor)| {
+                let key = match (id, actor) {
+                    (Some(id), _) => SaveKey::Id(id.id.clone()),
+                    (None, Some(actor)) => SaveKey::Actor(actor.actor.get_actor_path()),
+                    (None, None) => return None,
+                };
+                Some((entity, key))
+            })
+            .collect();
+
+        let mut entities: Vec<SavedEntity> = keyed
+            .into_iter()
+            .map(|(entity, key)| {
+                let components = reflected
+                    .iter()
+                    .filter(|(_, reflect)| reflect.has_component(world, entity))
+                    .filter_map(|(uuid, reflect)| {
+                        let fields = saved_fields(world, reflect.as_ref(), entity);
+                        (!fields.is_empty()).then(|| SavedComponent {
+                            uuid: **uuid,
+                            name: reflect.name().to_string(),
+                            fields,
+                        })
+                    })
+                    .collect();
+                SavedEntity { key, components }
+            })
+            .filter(|saved| !saved.components.is_empty())
+            .collect();
+        entities.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Self {
+            format_version: SAVE_FORMAT_VERSION,
+            game_version,
+            entities,
+        }
+    }
+
+    /// Writes the saved fields back onto the matching entities. Returns the number of entities
+    /// that were restored.
+    pub fn restore(&self, world: &mut World, registry: &ReflectionRegistry) -> usize {
+        let mut query = world.query::<(Entity, Option<&SaveId>, Option<&ActorComponent>)>();
+        let mut entities: HashMap<SaveKey, Entity> = HashMap::new();
+        for (entity, id, actor) in query.iter(world) {
+            if let Some(id) = id {
+                entities.insert(SaveKey::Id(id.id.clone()), entity);
+            } else if let Some(actor) = actor {
+                entities.insert(SaveKey::Actor(actor.actor.get_actor_path()), entity);
+            }
+        }
+
+        let mut restored = 0;
+        for saved in &self.entities {
+            let entity = match (&saved.key, entities.get(&saved.key)) {
+                (_, Some(entity)) => *entity,
+                (SaveKey::Id(id), None) => world.spawn().insert(SaveId::new(id.clone())).id(),
+                (SaveKey::Actor(path), None) => {
+                    log::warn!("Actor '{}' of the save does not exist anymore", path);
+                    continue;
+                }
+            };
+            for component in &saved.components {
+                restore_component(world, registry, entity, component);
+            }
+            restored += 1;
+        }
+        restored
+    }
+
+    pub fn to_bytes(&self, format: SaveFormat) -> Result<Vec<u8>, SaveError> {
+        match format {
+            SaveFormat::Binary => {
+                let mut bytes = BINARY_MAGIC.to_vec();
+                bincode::serialize_into(&mut bytes, self)
+                    .map_err(|err| SaveError::Encode(err.to_string()))?;
+                Ok(bytes)
+            }
+            SaveFormat::Ron => ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+                .map(String::into_bytes)
+                .map_err(|err| SaveError::Encode(err.to_string())),
+        }
+    }
+
+    /// Detects the format from the data
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SaveError> {
+        let data: SaveData = if let Some(binary) = bytes.strip_prefix(BINARY_MAGIC) {
+            // The version comes first, so that newer saves are reported as such instead of as
+            // corrupt data
+            let format_version: u32 =
+                bincode::deserialize(binary).map_err(|err| SaveError::Decode(err.to_string()))?;
+            if format_version > SAVE_FORMAT_VERSION {
+                return Err(SaveError::UnsupportedVersion(format_version));
+            }
+            bincode::deserialize(binary).map_err(|err| SaveError::Decode(err.to_string()))?
+        } else {
+            let text =
+                std::str::from_utf8(bytes).map_err(|err| SaveError::Decode(err.to_string()))?;
+            ron::from_str(text).map_err(|err| SaveError::Decode(err.to_string()))?
+        };
+        if data.format_version > SAVE_FORMAT_VERSION {
+            return Err(SaveError::UnsupportedVersion(data.format_version));
+        }
+        Ok(data)
+    }
+}
+
+fn restore_component(
+    world: &mut World,
+    registry: &ReflectionRegistry,
+    entity: Entity,
+    component: &SavedComponent,
+) {
+    let Some(reflect) = registry.reflect.get(&component.uuid) else {
+        log::warn!(
+            "Component {} ({}) of the save is not registered",
+            component.name,
+            component.uuid
+        );
+        return;
+    };
+    restore_fields(world, reflect.as_ref(), entity, |name| {
+        component
+            .fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value)
+    });
+}
+
+/// The fields of a component that hold plain values
+pub(crate) fn saved_fields(
+    world: &World,
+    reflect: &dyn ReflectDyn,
+    entity: Entity,
+) -> Vec<(String, SavedValue)> {
+    (0..reflect.number_of_fields())
+        .filter_map(|idx| {
+            let name = reflect.get_field_name(idx)?;
+            let value = reflect.get_field_value(world, entity, idx)?;
+            Some((name.to_string(), SavedValue::from_reflect(&value)?))
+        })
+        .collect()
+}
+
+/// Writes the fields onto the component of the entity, or inserts the component if the entity
+/// doesn't have it
+pub(crate) fn restore_fields<'a>(
+    world: &mut World,
+    reflect: &dyn ReflectDyn,
+    entity: Entity,
+    field: impl Fn(&str) -> Option<&'a SavedValue>,
+) {
+    let values: Vec<Option<ReflectValue>> = (0..reflect.number_of_fields())
+        .map(|idx| {
+            let name = reflect.get_field_name(idx)?;
+            let ty = reflect.get_field_type(idx)?;
+            field(name)?.to_reflect(ty)
+        })
+        .collect();
+
+    if reflect.has_component(world, entity) {
+        for (idx, value) in values.iter().enumerate() {
+            if let Some(value) = value {
+                reflect.set_field_value(world, entity, idx as u32, value);
+            }
+        }
+    } else if !reflect.insert_component(world, entity, &values) {
+        log::warn!(
+            "Component {} can not be inserted, it needs to be an editor component or have `#[reflect(default)]`",
+            reflect.name()
+        );
+    }
+}
+
+/// Where save slots are stored
+pub trait SaveBackend: Send + Sync + 'static {
+    fn write(&self, slot: &str, data: &[u8]) -> Result<(), SaveError>;
+    fn read(&self, slot: &str) -> Result<Vec<u8>, SaveError>;
+    fn exists(&self, slot: &str) -> bool;
+    fn delete(&self, slot: &str) -> Result<(), SaveError>;
+}
+
+/// Stores every slot as `<slot>.sav` in a directory
+#[derive(Debug, Clone)]
+pub struct FileBackend {
+    dir: PathBuf,
+}
+
+impl FileBackend {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, slot: &str) -> PathBuf {
+        self.dir.join(format!("{}.sav", slot))
+    }
+}
+
+impl SaveBackend for FileBackend {
+    fn write(&self, slot: &str, data: &[u8]) -> Result<(), SaveError> {
+        std::fs::create_dir_all(&self.dir)?;
+        // Write to a temporary file first, so that a crash never leaves a half written save
+        let tmp = self.dir.join(format!("{}.sav.tmp", slot));
+        std::fs::write(&tmp, data)?;
+        std::fs::rename(&tmp, self.path(slot))?;
+        Ok(())
+    }
+
+    fn read(&self, slot: &str) -> Result<Vec<u8>, SaveError> {
+        match std::fs::read(self.path(slot)) {
+            Ok(data) => Ok(data),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                Err(SaveError::NotFound(slot.to_string()))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn exists(&self, slot: &str) -> bool {
+        self.path(slot).is_file()
+    }
+
+    fn delete(&self, slot: &str) -> Result<(), SaveError> {
+        std::fs::remove_file(self.path(slot))?;
+        Ok(())
+    }
+}
+
+/// Stores slots through unreal's `SaveDataToSlot`, which uses the save game system of the
+/// platform. Can only be used from the game thread.
+#[derive(Default, Debug, Clone)]
+pub struct UnrealSaveBackend {
+    pub user_index: i32,
+}
+
+impl SaveBackend for UnrealSaveBackend {
+    fn write(&self, slot: &str, data: &[u8]) -> Result<(), SaveError> {
+        let saved = unsafe {
+            (bindings().save_game_fns.save_data_to_slot)(
+                ffi::Utf8Str::from(slot),
+                self.user_index,
+                data.as_ptr(),
+                data.len(),
+            )
+        };
+        if saved == 1 {
+            Ok(())
+        } else {
+            Err(SaveError::Unreal)
+        }
+    }
+
+    fn read(&self, slot: &str) -> Result<Vec<u8>, SaveError> {
+        if !self.exists(slot) {
+            return Err(SaveError::NotFound(slot.to_string()));
+        }
+        unsafe {
+            let mut alloc = ffi::RustAlloc::empty();
+            if (bindings().save_game_fns.load_data_from_slot)(
+                ffi::Utf8Str::from(slot),
+                self.user_index,
+                &mut alloc,
+            ) == 0
+            {
+                return Err(SaveError::Unreal);
+            }
+            let data = if alloc.ptr.is_null() {
+                Vec::new()
+            } else {
+                std::slice::from_raw_parts(alloc.ptr, alloc.size).to_vec()
+            };
+            alloc.free();
+            Ok(data)
+        }
+    }
+
+    fn exists(&self, slot: &str) -> bool {
+        unsafe {
+            (bindings().save_game_fns.does_save_game_exist)(
+                ffi::Utf8Str::from(slot),
+                self.user_index,
+            ) == 1
+        }
+    }
+
+    fn delete(&self, slot: &str) -> Result<(), SaveError> {
+        let deleted = unsafe {
+            (bindings().save_game_fns.delete_save_game_in_slot)(
+                ffi::Utf8Str::from(slot),
+                self.user_index,
+            )
+        };
+        if deleted == 1 {
+            Ok(())
+        } else {
+            Err(SaveError::Unreal)
+        }
+    }
+}
+
+pub struct SaveGameSettings {
+    pub backend: Box<dyn SaveBackend>,
+    pub format: SaveFormat,
+    /// Stored in every save, so that the game can migrate saves of older versions
+    pub game_version: u32,
+}
+
+impl Default for SaveGameSettings {
+    fn default() -> Self {
+        Self {
+            backend: Box::new(UnrealSaveBackend::default()),
+            format: SaveFormat::Binary,
+            game_version: 0,
+        }
+    }
+}
+
+pub struct GameSavedEvent {
+    pub slot: String,
+    pub result: Result<(), SaveError>,
+}
+
+pub struct GameLoadedEvent {
+    pub slot: String,
+    /// The number of restored entities
+    pub result: Result<usize, SaveError>,
+}
+
+/// Saves the world into a slot when the commands are applied, and sends a [`GameSavedEvent`]
+pub struct SaveToSlot {
+    pub slot: String,
+}
+
+impl SaveToSlot {
+    pub fn new(slot: impl Into<String>) -> Self {
+        Self { slot: slot.into() }
+    }
+}
+
+impl Command for SaveToSlot {
+    fn write(self, world: &mut World) {
+        let game_version = world.resource::<SaveGameSettings>().game_version;
+        let registry = world.resource::<TypeRegistry>().clone();
+        let data = SaveData::capture(world, registry.reflection_registry(), game_version);
+        let settings = world.resource::<SaveGameSettings>();
+        let result = data
+            .to_bytes(settings.format)
+            .and_then(|bytes| settings.backend.write(&self.slot, &bytes));
+        if let Err(err) = &result {
+            log::error!("Saving slot '{}' failed: {}", self.slot, err);
+        }
+        world.send_event(GameSavedEvent {
+            slot: self.slot,
+            result,
+        });
+    }
+}
+
+/// Loads a slot when the commands are applied, and sends a [`GameLoadedEvent`]
+pub struct LoadFromSlot {
+    pub slot: String,
+}
+
+impl LoadFromSlot {
+    pub fn new(slot: impl Into<String>) -> Self {
+        Self { slot: slot.into() }
+    }
+}
+
+impl Command for LoadFromSlot {
+    fn write(self, world: &mut World) {
+        let registry = world.resource::<TypeRegistry>().clone();
+        let result = world
+            .resource::<SaveGameSettings>()
+            .backend
+            .read(&self.slot)
+            .and_then(|bytes| SaveData::from_bytes(&bytes))
+            .map(|data| data.restore(world, registry.reflection_registry()));
+        if let Err(err) = &result {
+            log::error!("Loading slot '{}' failed: {}", self.slot, err);
+        }
+        world.send_event(GameLoadedEvent {
+            slot: self.slot,
+            result,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use unreal_reflect::TypeUuid;
+
+    use super::*;
+
+    #[derive(Default, Debug, Clone, PartialEq, Component)]
+    #[uuid = "e6cbbab5-6f32-4075-9b02-7d22cfd55803"]
+    #[reflect(default)]
+    struct Stats {
+        health: f32,
+        position: Vec3,
+        alive: bool,
+    }
+
+    fn registry() -> ReflectionRegistry {
+        let mut registry = ReflectionRegistry::default();
+        registry.register::<Stats>();
+        registry
+    }
+
+    fn stats() -> Stats {
+        Stats {
+            health: 75.0,
+            position: Vec3::new(1.0, 2.0, 3.0),
+            alive: true,
+        }
+    }
+
+    fn world() -> (World, Entity) {
+        let mut world = World::new();
+        let entity = world
+            .spawn()
+            .insert_bundle((SaveId::new("chest"), stats()))
+            .id();
+        // Not saved, it has neither an actor nor a `SaveId`
+        world.spawn().insert(Stats::default());
+        (world, entity)
+    }
+
+    fn saved_ids(world: &mut World) -> Vec<(String, Stats)> {
+        let mut ids: Vec<_> = world
+            .query::<(&SaveId, &Stats)>()
+            .iter(world)
+            .map(|(id, stats)| (id.id.clone(), stats.clone()))
+            .collect();
+        ids.sort_by(|a, b| a.0.cmp(&b.0));
+        ids
+    }
+
+    #[test]
+    fn captures_entities_with_a_save_id() {
+        let (mut world, _) = world();
+        let data = SaveData::capture(&mut world, &registry(), 7);
+
+        assert_eq!(data.format_version, SAVE_FORMAT_VERSION);
+        assert_eq!(data.game_version, 7);
+        assert_eq!(data.entities.len(), 1);
+        let saved = &data.entities[0];
+        assert_eq!(saved.key, SaveKey::Id("chest".to_string()));
+        assert_eq!(saved.components.len(), 1);
+        assert_eq!(saved.components[0].uuid, Stats::TYPE_UUID);
+        assert_eq!(
+            saved.components[0].fields,
+            vec![
+                ("health".to_string(), SavedValue::Float(75.0)),
+                ("position".to_string(), SavedValue::Vector3([1.0, 2.0, 3.0])),
+                ("alive".to_string(), SavedValue::Bool(true)),
+            ]
+        );
+    }
+
+    #[test]
+    fn restores_changed_and_despawned_entities() {
+        let registry = registry();
+        let (mut world, entity) = world();
+        let data = SaveData::capture(&mut world, &registry, 0);
+
+        world.get_mut::<Stats>(entity).unwrap().health = 0.0;
+        world
+            .spawn()
+            .insert_bundle((SaveId::new("barrel"), stats()));
+        assert_eq!(data.restore(&mut world, &registry), 1);
+        assert_eq!(*world.get::<Stats>(entity).unwrap(), stats());
+
+        // Entities with a `SaveId` are spawned again
+        world.despawn(entity);
+        assert_eq!(data.restore(&mut world, &registry), 1);
+        assert_eq!(
+            saved_ids(&mut world),
+            vec![
+                ("barrel".to_string(), stats()),
+                ("chest".to_string(), stats()),
+            ]
+        );
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let (mut world, _) = world();
+        let data = SaveData::capture(&mut world, &registry(), 3);
+        for format in [SaveFormat::Binary, SaveFormat::Ron] {
+            let bytes = data.to_bytes(format).unwrap();
+            assert_eq!(SaveData::from_bytes(&bytes).unwrap(), data, "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let (mut world, _) = world();
+        let mut data = SaveData::capture(&mut world, &registry(), 0);
+        data.format_version = SAVE_FORMAT_VERSION + 1;
+        for format in [SaveFormat::Binary, SaveFormat::Ron] {
+            let bytes = data.to_bytes(format).unwrap();
+            assert!(
+                matches!(
+                    SaveData::from_bytes(&bytes),
+                    Err(SaveError::UnsupportedVersion(version)) if version == SAVE_FORMAT_VERSION + 1
+                ),
+                "{:?}",
+                format
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_corrupt_data() {
+        assert!(matches!(
+            SaveData::from_bytes(b"URSV\x01"),
+            Err(SaveError::Decode(_))
+        ));
+        assert!(matches!(
+            SaveData::from_bytes(b"not a save"),
+            Err(SaveError::Decode(_))
+        ));
+    }
+}
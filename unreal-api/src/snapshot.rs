@@ -0,0 +1,302 @@
+//! Dumps of all reflected components, for debugging, bug reports and golden file tests. Needs the
+//! `serde` feature.
+//!
+//! Components with `#[reflect(serde)]` are stored as a whole, every other component stores the
+//! reflected fields that hold plain values, like a save game does. Unlike a save, a snapshot
+//! contains every entity with a reflected component, keyed by its entity id.
+//!
+//! ```ignore
+//! #[derive(Default, Debug, Component, Serialize, Deserialize)]
+//! #[uuid = "..."]
+//! #[reflect(serde)]
+//! pub struct Inventory {
+//!     #[reflect(skip)]
+//!     pub items: Vec<String>,
+//! }
+//!
+//! commands.add(WriteSnapshot::new("Saved/snapshot.json"));
+//! ```
+use std::{collections::BTreeMap, path::PathBuf};
+
+use bevy_ecs::{prelude::*, system::Command};
+use serde::{Deserialize, Serialize};
+use unreal_reflect::{
+    registry::{ReflectDyn, SerdeValue},
+    serde_json, Uuid,
+};
+
+use crate::{
+    module::ReflectionRegistry,
+    save_game::{restore_fields, saved_fields, SaveError, SavedValue},
+    type_registry::TypeRegistry,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SnapshotFormat {
+    Json,
+    Ron,
+}
+
+impl SnapshotFormat {
+    /// Picks the format from the extension of a path, `json` or `ron`
+    pub fn from_extension(path: &std::path::Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" => Some(SnapshotFormat::Json),
+            "ron" => Some(SnapshotFormat::Ron),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ComponentData {
+    /// The whole component, for components with `#[reflect(serde)]`
+    Serde(SerdeValue),
+    /// The reflected fields that hold plain values
+    Fields(BTreeMap<String, SavedValue>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentSnapshot {
+    /// Only informative, components are matched by uuid
+    pub name: String,
+    pub data: ComponentData,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntitySnapshot {
+    /// See [`Entity::to_bits`]
+    pub entity: u64,
+    pub components: BTreeMap<Uuid, ComponentSnapshot>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldSnapshot {
+    pub entities: Vec<EntitySnapshot>,
+}
+
+impl WorldSnapshot {
+    /// Dumps every entity that has a registered component. The output is sorted, so capturing
+    /// the same world twice gives the same snapshot.
+    pub fn capture(world: &mut World, registry: &ReflectionRegistry) -> Self {
+        let mut query = world.query::<Entity>();
+        let all: Vec<Entity> = query.iter(world).collect();
+        let mut entities: Vec<EntitySnapshot> = all
+            .into_iter()
+            .map(|entity| EntitySnapshot {
+                entity: entity.to_bits(),
+                components: registry
+                    .reflect
+                    .iter()
+                    .filter(|(_, reflect)| reflect.has_component(world, entity))
+                    .map(|(uuid, reflect)| {
+                        (*uuid, capture_component(world, reflect.as_ref(), entity))
+                    })
+                    .collect(),
+            })
+            .filter(|snapshot| !snapshot.components.is_empty())
+            .collect();
+        entities.sort_by_key(|snapshot| snapshot.entity);
+        Self { entities }
+    }
+
+    /// Writes the components back. Entities keep their id if it is still free, otherwise a new
+    /// entity is spawned. Returns the entity of every snapshot entity.
+    pub fn load(&self, world: &mut World, registry: &ReflectionRegistry) -> Vec<Entity> {
+        self.entities
+            .iter()
+            .map(|snapshot| {
+                let id = Entity::from_bits(snapshot.entity);
+                // A live entity with the same id is unrelated to the snapshot
+                let entity = if world.get_entity(id).is_none() {
+                    world
+                        .get_or_spawn(id)
+                        .map(|entity_mut| entity_mut.id())
+                        .unwrap_or_else(|| world.spawn().id())
+                } else {
+                    world.spawn().id()
+                };
+                for (uuid, component) in &snapshot.components {
+                    let Some(reflect) = registry.reflect.get(uuid) else {
+                        log::warn!(
+                            "Component {} ({}) of the snapshot is not registered",
+                            component.name,
+                            uuid
+                        );
+                        continue;
+                    };
+                    load_component(world, reflect.as_ref(), entity, &component.data);
+                }
+                entity
+            })
+            .collect()
+    }
+
+    pub fn to_string(&self, format: SnapshotFormat) -> Result<String, SaveError> {
+        match format {
+            SnapshotFormat::Json => {
+                serde_json::to_string_pretty(self).map_err(|err| SaveError::Encode(err.to_string()))
+            }
+            SnapshotFormat::Ron => ron::ser::to_string_pretty(self, Default::default())
+                .map_err(|err| SaveError::Encode(err.to_string())),
+        }
+    }
+
+    pub fn from_str(text: &str, format: SnapshotFormat) -> Result<Self, SaveError> {
+        match format {
+            SnapshotFormat::Json => {
+                serde_json::from_str(text).map_err(|err| SaveError::Decode(err.to_string()))
+            }
+            SnapshotFormat::Ron => {
+                ron::from_str(text).map_err(|err| SaveError::Decode(err.to_string()))
+            }
+        }
+    }
+}
+
+fn capture_component(world: &World, reflect: &dyn ReflectDyn, entity: Entity) -> ComponentSnapshot {
+    let data = match reflect.serialize_component(world, entity) {
+        Some(value) => ComponentData::Serde(value),
+        None => ComponentData::Fields(saved_fields(world, reflect, entity).into_iter().collect()),
+    };
+    ComponentSnapshot {
+        name: reflect.name().to_string(),
+        data,
+    }
+}
+
+fn load_component(
+    world: &mut World,
+    reflect: &dyn ReflectDyn,
+    entity: Entity,
+    data: &ComponentData,
+) {
+    match data {
+        ComponentData::Serde(value) => {
+            if !reflect.deserialize_component(world, entity, value.clone()) {
+                log::warn!("Unable to deserialize component {}", reflect.name());
+            }
+        }
+        ComponentData::Fields(fields) => {
+            restore_fields(world, reflect, entity, |name| fields.get(name))
+        }
+    }
+}
+
+/// Writes a snapshot of the world to a `.json` or `.ron` file when the commands are applied
+pub struct WriteSnapshot {
+    pub path: PathBuf,
+}
+
+impl WriteSnapshot {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl Command for WriteSnapshot {
+    fn write(self, world: &mut World) {
+        let format = SnapshotFormat::from_extension(&self.path).unwrap_or(SnapshotFormat::Json);
+        let registry = world.resource::<TypeRegistry>().clone();
+        let snapshot = WorldSnapshot::capture(world, registry.reflection_registry());
+        let result = snapshot.to_string(format).and_then(|text| {
+            if let Some(dir) = self.path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(&self.path, text).map_err(SaveError::from)
+        });
+        if let Err(err) = result {
+            log::error!("Writing snapshot {} failed: {}", self.path.display(), err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use unreal_api::Component;
+
+    use super::*;
+    use crate::math::Vec3;
+
+    #[derive(Default, Debug, Clone, PartialEq, Component, Serialize, Deserialize)]
+    #[uuid = "69e206ce-bc5b-4247-89ec-f818c00a0f4f"]
+    #[reflect(serde)]
+    struct Inventory {
+        #[reflect(skip)]
+        items: Vec<String>,
+        gold: f32,
+    }
+
+    #[derive(Default, Debug, Clone, PartialEq, Component)]
+    #[uuid = "4ee7eb9f-6c79-4ac9-8907-883dd1a2b9de"]
+    #[reflect(default)]
+    struct Stats {
+        health: f32,
+        position: Vec3,
+    }
+
+    fn registry() -> ReflectionRegistry {
+        let mut registry = ReflectionRegistry::default();
+        registry.register::<Inventory>();
+        registry.register::<Stats>();
+        registry
+    }
+
+    fn inventory() -> Inventory {
+        Inventory {
+            items: vec!["sword".to_string(), "shield".to_string()],
+            gold: 12.5,
+        }
+    }
+
+    fn stats() -> Stats {
+        Stats {
+            health: 80.0,
+            position: Vec3::new(1.0, 2.0, 3.0),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let registry = registry();
+        let mut world = World::new();
+        let player = world.spawn().insert_bundle((inventory(), stats())).id();
+        let chest = world.spawn().insert(inventory()).id();
+        // Not in the snapshot, it has no registered component
+        world.spawn();
+        let snapshot = WorldSnapshot::capture(&mut world, &registry);
+        assert_eq!(snapshot.entities.len(), 2);
+
+        for format in [SnapshotFormat::Json, SnapshotFormat::Ron] {
+            let text = snapshot.to_string(format).unwrap();
+            let loaded = WorldSnapshot::from_str(&text, format).unwrap();
+            assert_eq!(loaded, snapshot, "{:?}", format);
+
+            let mut restored = World::new();
+            assert_eq!(loaded.load(&mut restored, &registry), vec![player, chest]);
+            assert_eq!(*restored.get::<Inventory>(player).unwrap(), inventory());
+            assert_eq!(*restored.get::<Stats>(player).unwrap(), stats());
+            assert_eq!(*restored.get::<Inventory>(chest).unwrap(), inventory());
+            assert!(restored.get::<Stats>(chest).is_none());
+            assert_eq!(WorldSnapshot::capture(&mut restored, &registry), snapshot);
+        }
+    }
+
+    #[test]
+    fn load_keeps_live_entities() {
+        let registry = registry();
+        let mut world = World::new();
+        world.spawn().insert_bundle((inventory(), stats()));
+        let snapshot = WorldSnapshot::capture(&mut world, &registry);
+
+        let mut other = World::new();
+        let existing = other.spawn().insert(Stats::default()).id();
+        let loaded = snapshot.load(&mut other, &registry);
+        assert_eq!(loaded.len(), 1);
+        assert_ne!(loaded[0], existing);
+        assert_eq!(*other.get::<Stats>(existing).unwrap(), Stats::default());
+        assert!(other.get::<Inventory>(existing).is_none());
+        assert_eq!(*other.get::<Inventory>(loaded[0]).unwrap(), inventory());
+        assert_eq!(*other.get::<Stats>(loaded[0]).unwrap(), stats());
+    }
+}
@@ -0,0 +1,195 @@
+//! Game states like `MainMenu`, `InGame` or `Paused`, built on bevy's [`State`].
+//!
+//! A state machine is added with [`Module::add_state`](crate::module::Module::add_state) and is
+//! driven in a single stage, [`CoreStage::Update`](crate::core::CoreStage::Update) by default.
+//! Systems can be run when a state is entered, exited or while it is active with
+//! [`Module::on_enter`](crate::module::Module::on_enter),
+//! [`Module::on_exit`](crate::module::Module::on_exit) and
+//! [`Module::on_update`](crate::module::Module::on_update). Transitions are queued with
+//! [`State::set`] and happen the next time the stage of the state machine runs.
+//!
+//! State machines that implement [`UnrealState`] can also be switched from blueprints with
+//! `Set Rust State`. Blueprints that are called from a system, eg with
+//! [`UObjectPtr::call_function`](crate::object::UObjectPtr::call_function), can't reach the world
+//! and the call fails. Those should send an event instead.
+//!
+//! ```ignore
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+//! pub enum GameState {
+//!     MainMenu,
+//!     InGame,
+//!     Paused,
+//! }
+//!
+//! impl UnrealState for GameState {
+//!     const MACHINE: &'static str = "GameState";
+//!
+//!     fn name(&self) -> &'static str {
+//!         match self {
+//!             GameState::MainMenu => "MainMenu",
+//!             GameState::InGame => "InGame",
+//!             GameState::Paused => "Paused",
+//!         }
+//!     }
+//!
+//!     fn from_name(name: &str) -> Option<Self> {
+//!         match name {
+//!             "MainMenu" => Some(GameState::MainMenu),
+//!             "InGame" => Some(GameState::InGame),
+//!             "Paused" => Some(GameState::Paused),
+//!             _ => None,
+//!         }
+//!     }
+//! }
+//!
+//! module
+//!     .add_unreal_state(GameState::MainMenu)
+//!     .on_enter(GameState::Paused, SystemSet::new().with_system(show_pause_menu))
+//!     .on_exit(GameState::Paused, SystemSet::new().with_system(hide_pause_menu))
+//!     .on_update(GameState::InGame, SystemSet::new().with_system(update_hud));
+//! ```
+use std::{any::TypeId, collections::HashMap, marker::PhantomData};
+
+use bevy_ecs::{
+    prelude::*,
+    schedule::{ShouldRun, StageLabelId},
+};
+
+pub use bevy_ecs::schedule::{State, StateData, StateError};
+
+use crate::ffi;
+
+/// A state machine that blueprints can switch by name
+pub trait UnrealState: StateData {
+    /// The name of the state machine in blueprints, eg `GameState`
+    const MACHINE: &'static str;
+
+    fn name(&self) -> &'static str;
+    fn from_name(name: &str) -> Option<Self>;
+}
+
+/// Run criteria that runs a system while the state machine is in `state`
+pub fn in_state<S: StateData>(state: S) -> impl FnMut(Res<State<S>>) -> ShouldRun {
+    move |current: Res<State<S>>| {
+        if *current.current() == state {
+            ShouldRun::Yes
+        } else {
+            ShouldRun::No
+        }
+    }
+}
+
+/// Run criteria that runs a system while the state machine is not in `state`
+pub fn not_in_state<S: StateData>(state: S) -> impl FnMut(Res<State<S>>) -> ShouldRun {
+    move |current: Res<State<S>>| {
+        if *current.current() == state {
+            ShouldRun::No
+        } else {
+            ShouldRun::Yes
+        }
+    }
+}
+
+pub(crate) trait StateMachine {
+    /// Returns false if the state is unknown or the transition was refused
+    fn set(&self, world: &mut World, state: &str) -> bool;
+    fn current(&self, world: &World) -> Option<&'static str>;
+}
+
+struct UnrealStateMachine<S>(PhantomData<fn() -> S>);
+
+impl<S: UnrealState> StateMachine for UnrealStateMachine<S> {
+    fn set(&self, world: &mut World, state: &str) -> bool {
+        let Some(state) = S::from_name(state) else {
+            return false;
+        };
+        world
+            .get_resource_mut::<State<S>>()
+            .is_some_and(|mut current| current.set(state).is_ok())
+    }
+
+    fn current(&self, world: &World) -> Option<&'static str> {
+        world
+            .get_resource::<State<S>>()
+            .map(|current| current.current().name())
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct StateRegistry {
+    /// The stage that drives each state machine
+    stages: HashMap<TypeId, StageLabelId>,
+    machines: HashMap<&'static str, Box<dyn StateMachine>>,
+}
+
+impl StateRegistry {
+    pub(crate) fn add<S: StateData>(&mut self, stage: StageLabelId) {
+        if self.stages.insert(TypeId::of::<S>(), stage).is_some() {
+            panic!("State {} was added twice", std::any::type_name::<S>());
+        }
+    }
+
+    pub(crate) fn add_unreal<S: UnrealState>(&mut self) {
+        if self.machines.contains_key(S::MACHINE) {
+            panic!("Duplicated state machine name {}", S::MACHINE);
+        }
+        self.machines
+            .insert(S::MACHINE, Box::new(UnrealStateMachine::<S>(PhantomData)));
+    }
+
+    pub(crate) fn stage<S: StateData>(&self) -> StageLabelId {
+        match self.stages.get(&TypeId::of::<S>()) {
+            Some(stage) => *stage,
+            None => panic!(
+                "State {} needs to be added with `Module::add_state` first",
+                std::any::type_name::<S>()
+            ),
+        }
+    }
+}
+
+unsafe fn utf8_str(s: &ffi::Utf8Str) -> Option<&str> {
+    if s.ptr.is_null() {
+        return Some("");
+    }
+    std::str::from_utf8(std::slice::from_raw_parts(s.ptr as *const u8, s.len)).ok()
+}
+
+unsafe extern "C" fn set_state(machine: ffi::Utf8Str, state: ffi::Utf8Str) -> u32 {
+    fn set_state_inner(machine: &str, state: &str) -> Option<bool> {
+        let mut global = unsafe { crate::module::global() }?;
+        let module = &mut global.core.module;
+        let machine = module.states.machines.get(machine)?;
+        Some(machine.set(&mut module.world, state))
+    }
+    let result = std::panic::catch_unwind(|| {
+        let machine = utf8_str(&machine)?;
+        let state = utf8_str(&state)?;
+        set_state_inner(machine, state)
+    });
+    result.ok().flatten().unwrap_or(false) as u32
+}
+
+unsafe extern "C" fn get_state(machine: ffi::Utf8Str, out: *mut ffi::Utf8Str) -> u32 {
+    fn get_state_inner(machine: &str) -> Option<&'static str> {
+        let global = unsafe { crate::module::global() }?;
+        let module = &global.core.module;
+        module.states.machines.get(machine)?.current(&module.world)
+    }
+    let result = std::panic::catch_unwind(|| {
+        if let Some(state) = utf8_str(&machine).and_then(get_state_inner) {
+            *out = ffi::Utf8Str::from(state);
+            1
+        } else {
+            0
+        }
+    });
+    result.unwrap_or(0)
+}
+
+pub fn create_state_fns() -> ffi::StateFns {
+    ffi::StateFns {
+        set_state,
+        get_state,
+    }
+}
@@ -0,0 +1,200 @@
+//! Game, unscaled and real time clocks, time dilation and pause.
+//!
+//! Unreal keeps ticking the ECS while the game is paused, so that pause menus can still be driven
+//! from gameplay code. While paused the game clock and [`Frame::dt`](crate::core::Frame) stand
+//! still, only the real clock keeps running. Use [`run_if_not_paused`] to skip systems while
+//! paused.
+use bevy_ecs::{prelude::*, schedule::ShouldRun};
+use unreal_api::Component;
+
+use crate::{
+    api::UnrealApi,
+    core::ActorComponent,
+    ffi,
+    module::{bindings, GameThread},
+};
+
+#[derive(Debug, Copy, Clone)]
+pub struct Time {
+    /// Game time in seconds since begin play. Scaled by the global time dilation and stops while
+    /// the game is paused.
+    pub time: f64,
+    pub delta: f32,
+    /// Not scaled by time dilation, but stops while the game is paused
+    pub unscaled_time: f64,
+    pub unscaled_delta: f32,
+    /// Wall clock time, which keeps running while the game is paused
+    pub real_time: f64,
+    pub real_delta: f32,
+    /// Number of ticks since begin play, including the ticks while paused
+    pub frame_count: u64,
+    /// Number of ticks in which the game was not paused
+    pub game_frame_count: u64,
+    pub time_dilation: f32,
+    pub paused: bool,
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self {
+            time: 0.0,
+            delta: 0.0,
+            unscaled_time: 0.0,
+            unscaled_delta: 0.0,
+            real_time: 0.0,
+            real_delta: 0.0,
+            frame_count: 0,
+            game_frame_count: 0,
+            time_dilation: 1.0,
+            paused: false,
+        }
+    }
+}
+
+impl Time {
+    pub(crate) fn update(&mut self, tick: ffi::TickTime) {
+        self.paused = tick.paused == 1;
+        self.time_dilation = tick.time_dilation;
+        self.real_delta = tick.real_delta;
+        self.real_time += tick.real_delta as f64;
+        self.frame_count += 1;
+        if self.paused {
+            self.delta = 0.0;
+            self.unscaled_delta = 0.0;
+        } else {
+            self.delta = tick.delta;
+            // The game delta without the global dilation. Unlike the real delta it is clamped to
+            // the longest tick that unreal allows.
+            self.unscaled_delta = if tick.time_dilation > 0.0 {
+                tick.delta / tick.time_dilation
+            } else {
+                tick.real_delta
+            };
+            self.time += tick.delta as f64;
+            self.unscaled_time += self.unscaled_delta as f64;
+            self.game_frame_count += 1;
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// The game delta of an entity with its [`CustomTimeDilation`] applied
+    pub fn delta_for(&self, dilation: Option<&CustomTimeDilation>) -> f32 {
+        self.delta * dilation.map_or(1.0, |dilation| dilation.dilation)
+    }
+
+    /// Slow motion below 1, fast forward above 1. Unreal clamps the value to the range that is
+    /// configured in the world settings. Takes effect in the next tick.
+    pub fn set_global_time_dilation(&self, dilation: f32) {
+        unsafe {
+            (bindings().time_fns.set_global_time_dilation)(dilation);
+        }
+    }
+
+    /// Pauses or unpauses the game. Takes effect in the next tick. Returns false if unreal
+    /// refused to pause.
+    pub fn set_paused(&self, paused: bool) -> bool {
+        unsafe { (bindings().time_fns.set_game_paused)(paused as u32) == 1 }
+    }
+}
+
+/// Scales the time of a single entity, on top of the global time dilation. This is forwarded to
+/// `AActor::CustomTimeDilation`, use [`Time::delta_for`] in systems.
+#[derive(Debug, Component)]
+#[uuid = "a4b36c1e-5d0e-4a8f-bc0c-8d7e4f2a6b19"]
+#[reflect(editor)]
+pub struct CustomTimeDilation {
+    pub dilation: f32,
+}
+
+impl Default for CustomTimeDilation {
+    fn default() -> Self {
+        Self { dilation: 1.0 }
+    }
+}
+
+pub fn upload_custom_time_dilation(
+    _game_thread: NonSend<GameThread>,
+    query: Query<(&ActorComponent, &CustomTimeDilation), Changed<CustomTimeDilation>>,
+) {
+    for (actor, dilation) in &query {
+        unsafe {
+            (bindings().time_fns.set_custom_time_dilation)(actor.actor.0, dilation.dilation);
+        }
+    }
+}
+
+/// Resets the dilation of actors that lost their [`CustomTimeDilation`]
+pub fn reset_custom_time_dilation(
+    _game_thread: NonSend<GameThread>,
+    api: Res<UnrealApi>,
+    removed: RemovedComponents<CustomTimeDilation>,
+) {
+    for entity in removed.iter() {
+        if let Some(actor) = api.entity_to_actor.get(&entity) {
+            unsafe {
+                (bindings().time_fns.set_custom_time_dilation)(actor.0, 1.0);
+            }
+        }
+    }
+}
+
+/// Run criteria that only runs the systems while the game is paused, eg a pause menu
+pub fn run_if_paused(time: Res<Time>) -> ShouldRun {
+    if time.paused {
+        ShouldRun::Yes
+    } else {
+        ShouldRun::No
+    }
+}
+
+/// Run criteria that skips the systems while the game is paused
+pub fn run_if_not_paused(time: Res<Time>) -> ShouldRun {
+    if time.paused {
+        ShouldRun::No
+    } else {
+        ShouldRun::Yes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(delta: f32, real_delta: f32, time_dilation: f32, paused: bool) -> ffi::TickTime {
+        ffi::TickTime {
+            delta,
+            real_delta,
+            time_dilation,
+            paused: paused as u32,
+        }
+    }
+
+    #[test]
+    fn unscaled_time_ignores_dilation() {
+        let mut time = Time::default();
+        time.update(tick(0.05, 0.1, 0.5, false));
+        assert_eq!(time.delta, 0.05);
+        assert_eq!(time.unscaled_delta, 0.1);
+        assert_eq!(time.real_delta, 0.1);
+        assert_eq!(time.game_frame_count, 1);
+    }
+
+    #[test]
+    fn only_real_time_runs_while_paused() {
+        let mut time = Time::default();
+        time.update(tick(0.1, 0.1, 1.0, false));
+        time.update(tick(0.1, 0.1, 1.0, true));
+        time.update(tick(0.1, 0.1, 1.0, true));
+        assert!(time.is_paused());
+        assert_eq!(time.delta, 0.0);
+        assert_eq!(time.unscaled_delta, 0.0);
+        assert_eq!(time.real_delta, 0.1);
+        assert!((time.unscaled_time - 0.1).abs() < 1e-6);
+        assert!((time.real_time - 0.3).abs() < 1e-6);
+        assert_eq!(time.frame_count, 3);
+        assert_eq!(time.game_frame_count, 1);
+    }
+}
@@ -0,0 +1,478 @@
+//! One-shot and repeating timers that run on the ECS clock.
+//!
+//! Timers are advanced by [`UnrealCore::tick`](crate::core::UnrealCore::tick) with the same delta
+//! as [`Frame::dt`](crate::core::Frame), which is scaled by the global time dilation and is zero
+//! while the game is paused.
+//!
+//! There are two kinds of timers:
+//! * [`Timer`] components, which send a [`TimerFinishedEvent`] for their entity.
+//! * Timers that are added to the [`Timers`] resource, which send a [`TimerFiredEvent`] and can
+//!   run a callback with access to the [`World`].
+use std::collections::HashMap;
+
+use bevy_ecs::prelude::*;
+use unreal_api::Component;
+
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TimerMode {
+    /// Fires once and then stays finished until it is reset
+    #[default]
+    Once,
+    /// Fires every `duration` seconds
+    Repeating,
+}
+
+/// Counts up to `duration`. Can be used on its own by calling [`Timer::tick`], or as a component
+/// in which case it is ticked automatically.
+#[derive(Default, Debug, Clone, Component)]
+#[uuid = "2d0f4ba1-7b54-4c6b-9f69-0b5d0d43c8a7"]
+pub struct Timer {
+    duration: f32,
+    elapsed: f32,
+    paused: bool,
+    finished: bool,
+    #[reflect(skip)]
+    mode: TimerMode,
+    #[reflect(skip)]
+    times_finished_this_tick: u32,
+}
+
+impl Timer {
+    pub fn new(duration: f32, mode: TimerMode) -> Self {
+        Self {
+            duration,
+            mode,
+            ..Default::default()
+        }
+    }
+
+    pub fn once(duration: f32) -> Self {
+        Self::new(duration, TimerMode::Once)
+    }
+
+    pub fn repeating(duration: f32) -> Self {
+        Self::new(duration, TimerMode::Repeating)
+    }
+
+    /// Advances the timer by `dt` seconds. Does nothing if the timer is paused or if a one-shot
+    /// timer has already finished.
+    pub fn tick(&mut self, dt: f32) -> &Self {
+        self.times_finished_this_tick = 0;
+        if self.paused || (self.finished && self.mode == TimerMode::Once) {
+            return self;
+        }
+
+        self.elapsed += dt;
+        match self.mode {
+            TimerMode::Once => {
+                if self.elapsed >= self.duration {
+                    self.elapsed = self.duration;
+                    self.finished = true;
+                    self.times_finished_this_tick = 1;
+                }
+            }
+            TimerMode::Repeating => {
+                if self.duration <= 0.0 {
+                    // A zero duration would fire infinitely often, fire once per tick instead
+                    self.elapsed = 0.0;
+                    self.times_finished_this_tick = 1;
+                } else if self.elapsed >= self.duration {
+                    self.times_finished_this_tick = (self.elapsed / self.duration) as u32;
+                    self.elapsed %= self.duration;
+                }
+                self.finished = self.times_finished_this_tick > 0;
+            }
+        }
+        self
+    }
+
+    /// For one-shot timers this stays true after the timer fired. Repeating timers are only
+    /// finished in the tick in which they fired.
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// True if the timer fired during the last tick
+    pub fn just_finished(&self) -> bool {
+        self.times_finished_this_tick > 0
+    }
+
+    /// A repeating timer can fire more than once per tick if the tick was longer than its
+    /// duration
+    pub fn times_finished_this_tick(&self) -> u32 {
+        self.times_finished_this_tick
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn unpause(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+        self.finished = false;
+        self.times_finished_this_tick = 0;
+    }
+
+    pub fn mode(&self) -> TimerMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: TimerMode) {
+        self.mode = mode;
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    pub fn set_duration(&mut self, duration: f32) {
+        self.duration = duration;
+    }
+
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn remaining(&self) -> f32 {
+        f32::max(self.duration - self.elapsed, 0.0)
+    }
+
+    /// Progress in the range `[0, 1]`
+    pub fn percent(&self) -> f32 {
+        if self.duration > 0.0 {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Sent every time a [`Timer`] component fires
+#[derive(Debug, Copy, Clone)]
+pub struct TimerFinishedEvent {
+    pub entity: Entity,
+}
+
+/// Sent every time a timer of the [`Timers`] resource fires
+#[derive(Debug, Copy, Clone)]
+pub struct TimerFiredEvent {
+    pub handle: TimerHandle,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TimerHandle(u64);
+
+type TimerCallback = Box<dyn FnMut(&mut World) + Send + Sync>;
+
+struct ScheduledTimer {
+    timer: Timer,
+    callback: Option<TimerCallback>,
+}
+
+/// Timers that are not attached to an entity. One-shot timers are removed after they fired.
+///
+/// ```ignore
+/// fn explode_later(mut timers: ResMut<Timers>, bomb: Entity) {
+///     timers.after(3.0, move |world| {
+///         world.despawn(bomb);
+///     });
+/// }
+/// ```
+#[derive(Default)]
+pub struct Timers {
+    next_handle: u64,
+    timers: HashMap<TimerHandle, ScheduledTimer>,
+}
+
+impl Timers {
+    /// Adds a timer that only sends a [`TimerFiredEvent`]
+    pub fn add(&mut self, timer: Timer) -> TimerHandle {
+        self.insert(timer, None)
+    }
+
+    /// Adds a timer that runs `callback` every time it fires, in addition to sending a
+    /// [`TimerFiredEvent`]
+    pub fn add_with(
+        &mut self,
+        timer: Timer,
+        callback: impl FnMut(&mut World) + Send + Sync + 'static,
+    ) -> TimerHandle {
+        self.insert(timer, Some(Box::new(callback)))
+    }
+
+    /// Runs `callback` once after `seconds`
+    pub fn after(
+        &mut self,
+        seconds: f32,
+        callback: impl FnMut(&mut World) + Send + Sync + 'static,
+    ) -> TimerHandle {
+        self.add_with(Timer::once(seconds), callback)
+    }
+
+    /// Runs `callback` every `seconds` until the timer is cancelled
+    pub fn every(
+        &mut self,
+        seconds: f32,
+        callback: impl FnMut(&mut World) + Send + Sync + 'static,
+    ) -> TimerHandle {
+        self.add_with(Timer::repeating(seconds), callback)
+    }
+
+    fn insert(&mut self, timer: Timer, callback: Option<TimerCallback>) -> TimerHandle {
+        let handle = TimerHandle(self.next_handle);
+        self.next_handle += 1;
+        self.timers
+            .insert(handle, ScheduledTimer { timer, callback });
+        handle
+    }
+
+    pub fn get(&self, handle: TimerHandle) -> Option<&Timer> {
+        self.timers.get(&handle).map(|scheduled| &scheduled.timer)
+    }
+
+    pub fn get_mut(&mut self, handle: TimerHandle) -> Option<&mut Timer> {
+        self.timers
+            .get_mut(&handle)
+            .map(|scheduled| &mut scheduled.timer)
+    }
+
+    pub fn contains(&self, handle: TimerHandle) -> bool {
+        self.timers.contains_key(&handle)
+    }
+
+    /// Returns false if the timer doesn't exist anymore
+    pub fn pause(&mut self, handle: TimerHandle) -> bool {
+        self.get_mut(handle).map(Timer::pause).is_some()
+    }
+
+    /// Returns false if the timer doesn't exist anymore
+    pub fn unpause(&mut self, handle: TimerHandle) -> bool {
+        self.get_mut(handle).map(Timer::unpause).is_some()
+    }
+
+    /// Returns false if the timer doesn't exist anymore
+    pub fn cancel(&mut self, handle: TimerHandle) -> bool {
+        self.timers.remove(&handle).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+
+    fn tick(&mut self, dt: f32) -> Vec<(TimerHandle, u32)> {
+        let mut fired: Vec<_> = self
+            .timers
+            .iter_mut()
+            .filter_map(|(handle, scheduled)| {
+                let times = scheduled.timer.tick(dt).times_finished_this_tick();
+                (times > 0).then_some((*handle, times))
+            })
+            .collect();
+        // Fire in the order in which the timers were added
+        fired.sort_by_key(|(handle, _)| handle.0);
+        fired
+    }
+}
+
+/// Advances all timers by `dt`, sends the events and runs the callbacks.
+pub(crate) fn tick_timers(world: &mut World, dt: f32) {
+    let mut finished = Vec::new();
+    for (entity, mut timer) in world.query::<(Entity, &mut Timer)>().iter_mut(world) {
+        let times = timer.tick(dt).times_finished_this_tick();
+        finished.extend((0..times).map(|_| TimerFinishedEvent { entity }));
+    }
+    for event in finished {
+        world.send_event(event);
+    }
+
+    let fired = match world.get_resource_mut::<Timers>() {
+        Some(mut timers) => timers.tick(dt),
+        None => return,
+    };
+    for (handle, times) in fired {
+        for _ in 0..times {
+            // A callback might have cancelled the timer
+            if !world.resource::<Timers>().contains(handle) {
+                break;
+            }
+            world.send_event(TimerFiredEvent { handle });
+            // The callback is taken out of the resource so that it can access the world,
+            // including the `Timers` resource itself.
+            let callback = world
+                .resource_mut::<Timers>()
+                .timers
+                .get_mut(&handle)
+                .and_then(|scheduled| scheduled.callback.take());
+            let Some(mut callback) = callback else {
+                continue;
+            };
+            callback(world);
+            // The callback might have cancelled its own timer
+            if let Some(scheduled) = world.resource_mut::<Timers>().timers.get_mut(&handle) {
+                scheduled.callback = Some(callback);
+            }
+        }
+    }
+    world
+        .resource_mut::<Timers>()
+        .timers
+        .retain(|_, scheduled| {
+            !(scheduled.timer.mode() == TimerMode::Once && scheduled.timer.finished())
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    };
+
+    use super::*;
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.init_resource::<Events<TimerFinishedEvent>>();
+        world.init_resource::<Events<TimerFiredEvent>>();
+        world.init_resource::<Timers>();
+        world
+    }
+
+    fn count<E: bevy_ecs::event::Event>(world: &World) -> usize {
+        world
+            .resource::<Events<E>>()
+            .iter_current_update_events()
+            .count()
+    }
+
+    #[test]
+    fn repeating_timer_fires_several_times_per_tick() {
+        let mut timer = Timer::repeating(1.0);
+        assert_eq!(timer.tick(3.5).times_finished_this_tick(), 3);
+        assert!(timer.finished());
+        assert_eq!(timer.elapsed(), 0.5);
+
+        assert_eq!(timer.tick(0.25).times_finished_this_tick(), 0);
+        assert!(!timer.finished());
+        assert_eq!(timer.tick(0.25).times_finished_this_tick(), 1);
+    }
+
+    #[test]
+    fn one_shot_timer_fires_once() {
+        let mut timer = Timer::once(1.0);
+        assert!(!timer.tick(0.5).just_finished());
+        assert!(timer.tick(0.75).just_finished());
+        assert_eq!(timer.elapsed(), 1.0);
+        assert!(!timer.tick(1.0).just_finished());
+        assert!(timer.finished());
+
+        timer.reset();
+        assert!(!timer.finished());
+        assert!(timer.tick(1.0).just_finished());
+    }
+
+    #[test]
+    fn zero_duration_fires_once_per_tick() {
+        let mut repeating = Timer::repeating(0.0);
+        assert_eq!(repeating.tick(1.0).times_finished_this_tick(), 1);
+        assert_eq!(repeating.tick(0.0).times_finished_this_tick(), 1);
+
+        let mut once = Timer::once(0.0);
+        assert!(once.tick(0.0).just_finished());
+        assert!(!once.tick(0.0).just_finished());
+    }
+
+    #[test]
+    fn paused_timer_does_not_advance() {
+        let mut timer = Timer::repeating(1.0);
+        timer.pause();
+        assert!(!timer.tick(2.0).just_finished());
+        assert_eq!(timer.elapsed(), 0.0);
+
+        timer.unpause();
+        assert_eq!(timer.tick(2.0).times_finished_this_tick(), 2);
+    }
+
+    #[test]
+    fn timer_components_send_an_event_per_fire() {
+        let mut world = world();
+        let entity = world.spawn().insert(Timer::repeating(1.0)).id();
+        world.spawn().insert(Timer::once(10.0));
+
+        tick_timers(&mut world, 2.0);
+        let events = world.resource::<Events<TimerFinishedEvent>>();
+        let entities: Vec<_> = events
+            .iter_current_update_events()
+            .map(|event| event.entity)
+            .collect();
+        assert_eq!(entities, vec![entity, entity]);
+    }
+
+    #[test]
+    fn finished_one_shots_are_removed() {
+        let mut world = world();
+        let calls = Arc::new(AtomicU32::new(0));
+        let mut timers = world.resource_mut::<Timers>();
+        let once = {
+            let calls = calls.clone();
+            timers.after(1.0, move |_| {
+                calls.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+        let repeating = timers.add(Timer::repeating(1.0));
+
+        tick_timers(&mut world, 0.5);
+        assert!(world.resource::<Timers>().contains(once));
+
+        tick_timers(&mut world, 0.5);
+        let timers = world.resource::<Timers>();
+        assert!(!timers.contains(once));
+        assert!(timers.contains(repeating));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(count::<TimerFiredEvent>(&world), 2);
+    }
+
+    #[test]
+    fn paused_timers_in_the_resource_do_not_fire() {
+        let mut world = world();
+        let handle = world.resource_mut::<Timers>().add(Timer::once(1.0));
+        world.resource_mut::<Timers>().pause(handle);
+
+        tick_timers(&mut world, 2.0);
+        assert_eq!(count::<TimerFiredEvent>(&world), 0);
+        assert!(world.resource::<Timers>().contains(handle));
+    }
+
+    #[test]
+    fn callback_can_cancel_its_own_timer() {
+        let mut world = world();
+        let calls = Arc::new(AtomicU32::new(0));
+        // Handles are handed out in order, so the timer knows its own handle
+        let handle = TimerHandle(0);
+        let added = {
+            let calls = calls.clone();
+            world.resource_mut::<Timers>().every(1.0, move |world| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                world.resource_mut::<Timers>().cancel(handle);
+            })
+        };
+        assert_eq!(added, handle);
+
+        tick_timers(&mut world, 3.0);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(count::<TimerFiredEvent>(&world), 1);
+        assert!(world.resource::<Timers>().is_empty());
+    }
+}
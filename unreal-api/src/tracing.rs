@@ -0,0 +1,83 @@
+use std::fmt::Write;
+
+use ::tracing::{
+    field::{Field, Visit},
+    subscriber::SetGlobalDefaultError,
+    Event, Level, Subscriber,
+};
+use tracing_subscriber::{layer::Context, prelude::*, registry::LookupSpan, Layer, Registry};
+
+use crate::log::{category_for_target, is_enabled, write_record};
+
+/// A `tracing` layer that writes events to the unreal log. The names of all the spans that the
+/// event is in are prepended to the message, eg `character_control: find_floor: no floor found`.
+pub struct UnrealLayer;
+
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+}
+
+fn to_log_level(level: &Level) -> log::Level {
+    match *level {
+        Level::ERROR => log::Level::Error,
+        Level::WARN => log::Level::Warn,
+        Level::INFO => log::Level::Info,
+        Level::DEBUG => log::Level::Debug,
+        Level::TRACE => log::Level::Trace,
+    }
+}
+
+impl<S> Layer<S> for UnrealLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let category = category_for_target(metadata.target());
+        let level = to_log_level(metadata.level());
+        if !is_enabled(category, level) {
+            return;
+        }
+
+        let mut text = String::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                text.push_str(span.name());
+                text.push_str(": ");
+            }
+        }
+
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        text.push_str(&visitor.message);
+        text.push_str(&visitor.fields);
+
+        write_record(category, level, &text, metadata.file(), metadata.line());
+    }
+}
+
+/// Installs [`UnrealLayer`] as the global `tracing` subscriber. The `log` based logger is
+/// initialized separately by `implement_unreal_module!`.
+pub fn init() -> Result<(), SetGlobalDefaultError> {
+    ::tracing::subscriber::set_global_default(Registry::default().with(UnrealLayer))
+}
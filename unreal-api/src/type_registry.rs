@@ -0,0 +1,412 @@
+//! Safe access to the reflection registry from Rust, for tools like inspectors or consoles.
+//!
+//! The [`TypeRegistry`] resource is available once every plugin was built. It lists the registered
+//! components and events, describes their fields, and gets, sets, inserts or removes components on
+//! an entity by uuid or by name.
+//!
+//! ```ignore
+//! fn print_components(world: &mut World, entity: Entity) {
+//!     let registry = world.resource::<TypeRegistry>().clone();
+//!     for ty in registry.components_of(world, entity) {
+//!         for field in ty.fields() {
+//!             log::info!("{}.{} = {:?}", ty.name(), field.name, ty.get_field(world, entity, field.name));
+//!         }
+//!     }
+//!     if let Some(ty) = registry.get_by_name("CharacterConfigComponent") {
+//!         ty.set_field(world, entity, "max_movement_speed", &ReflectValue::Float(600.0)).ok();
+//!     }
+//! }
+//! ```
+use std::{fmt, sync::Arc};
+
+use bevy_ecs::prelude::*;
+use unreal_reflect::{
+    registry::{ReflectDyn, ReflectType, ReflectValue},
+    Uuid,
+};
+
+use crate::module::ReflectionRegistry;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReflectError {
+    UnknownField(String),
+    /// The entity doesn't have the component
+    MissingComponent,
+    /// The value doesn't have the type of the field
+    WrongType {
+        field: String,
+        expected: ReflectType,
+    },
+    /// The component can't be built from reflected values, see
+    /// [`ReflectDyn::insert_component`]
+    NotInsertable,
+}
+
+impl fmt::Display for ReflectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReflectError::UnknownField(field) => write!(f, "There is no field '{}'", field),
+            ReflectError::MissingComponent => write!(f, "The entity doesn't have the component"),
+            ReflectError::WrongType { field, expected } => {
+                write!(
+                    f,
+                    "Field '{}' expects a value of type {:?}",
+                    field, expected
+                )
+            }
+            ReflectError::NotInsertable => {
+                write!(f, "The component can't be built from reflected values")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReflectError {}
+
+/// Every registered component and event. Cloning is cheap, which allows holding on to the
+/// registry while the world is borrowed mutably.
+#[derive(Clone)]
+pub struct TypeRegistry {
+    registry: Arc<ReflectionRegistry>,
+    /// Sorted by name and then by uuid
+    sorted: Arc<Vec<Uuid>>,
+}
+
+impl TypeRegistry {
+    pub(crate) fn new(registry: Arc<ReflectionRegistry>) -> Self {
+        let mut sorted: Vec<Uuid> = registry.reflect.keys().copied().collect();
+        sorted.sort_by_key(|uuid| (registry.reflect[uuid].name(), *uuid));
+        Self {
+            registry,
+            sorted: Arc::new(sorted),
+        }
+    }
+
+    pub fn reflection_registry(&self) -> &ReflectionRegistry {
+        &self.registry
+    }
+
+    /// Sorted by name
+    pub fn types(&self) -> impl Iterator<Item = TypeInfo<'_>> + '_ {
+        self.sorted.iter().filter_map(|uuid| self.get(*uuid))
+    }
+
+    pub fn get(&self, uuid: Uuid) -> Option<TypeInfo<'_>> {
+        let reflect = self.registry.reflect.get(&uuid)?;
+        Some(TypeInfo {
+            uuid,
+            reflect: reflect.as_ref(),
+            registry: &self.registry,
+        })
+    }
+
+    /// Returns `None` if no type or several types have this name, use [`TypeRegistry::get`] to
+    /// tell them apart
+    pub fn get_by_name(&self, name: &str) -> Option<TypeInfo<'_>> {
+        let mut found = self.types().filter(|ty| ty.name() == name);
+        match (found.next(), found.next()) {
+            (Some(ty), None) => Some(ty),
+            _ => None,
+        }
+    }
+
+    /// The registered components of the entity, sorted by name
+    pub fn components_of<'a>(
+        &'a self,
+        world: &'a World,
+        entity: Entity,
+    ) -> impl Iterator<Item = TypeInfo<'a>> + 'a {
+        self.types()
+            .filter(move |ty| ty.has_component(world, entity))
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FieldInfo {
+    pub index: u32,
+    pub name: &'static str,
+    pub ty: ReflectType,
+}
+
+#[derive(Clone, Copy)]
+pub struct TypeInfo<'a> {
+    uuid: Uuid,
+    reflect: &'a dyn ReflectDyn,
+    registry: &'a ReflectionRegistry,
+}
+
+impl<'a> fmt::Debug for TypeInfo<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypeInfo")
+            .field("uuid", &self.uuid)
+            .field("name", &self.name())
+            .finish()
+    }
+}
+
+impl<'a> TypeInfo<'a> {
+    pub fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.reflect.name()
+    }
+
+    pub fn reflect(&self) -> &'a dyn ReflectDyn {
+        self.reflect
+    }
+
+    /// Editor components can be added to actors in the editor
+    pub fn is_editor_component(&self) -> bool {
+        self.registry
+            .insert_editor_component
+            .contains_key(&self.uuid)
+    }
+
+    /// Events that were added with [`Module::add_unreal_event`](crate::module::Module::add_unreal_event)
+    pub fn is_event(&self) -> bool {
+        self.registry.events.contains_key(&self.uuid)
+    }
+
+    /// The reflected fields, in declaration order. Skipped fields are not included.
+    pub fn fields(&self) -> impl Iterator<Item = FieldInfo> + 'a {
+        let reflect = self.reflect;
+        (0..reflect.number_of_fields()).filter_map(move |index| {
+            Some(FieldInfo {
+                index,
+                name: reflect.get_field_name(index)?,
+                ty: reflect.get_field_type(index)?,
+            })
+        })
+    }
+
+    pub fn field(&self, name: &str) -> Option<FieldInfo> {
+        self.fields().find(|field| field.name == name)
+    }
+
+    pub fn has_component(&self, world: &World, entity: Entity) -> bool {
+        self.reflect.has_component(world, entity)
+    }
+
+    /// Returns `None` if the entity doesn't have the component or the field doesn't exist
+    pub fn get_field(&self, world: &World, entity: Entity, name: &str) -> Option<ReflectValue> {
+        let field = self.field(name)?;
+        self.reflect.get_field_value(world, entity, field.index)
+    }
+
+    pub fn set_field(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        name: &str,
+        value: &ReflectValue,
+    ) -> Result<(), ReflectError> {
+        let field = self
+            .field(name)
+            .ok_or_else(|| ReflectError::UnknownField(name.to_string()))?;
+        if !self.has_component(world, entity) {
+            return Err(ReflectError::MissingComponent);
+        }
+        if self
+            .reflect
+            .set_field_value(world, entity, field.index, value)
+        {
+            Ok(())
+        } else {
+            Err(ReflectError::WrongType {
+                field: name.to_string(),
+                expected: field.ty,
+            })
+        }
+    }
+
+    /// Inserts the component, replacing the one that the entity already has. Editor components
+    /// need a value for every field, components with `#[reflect(default)]` use the default value
+    /// for missing fields.
+    pub fn insert(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        values: &[(&str, ReflectValue)],
+    ) -> Result<(), ReflectError> {
+        let mut indexed = vec![None; self.reflect.number_of_fields() as usize];
+        for (name, value) in values {
+            let field = self
+                .field(name)
+                .ok_or_else(|| ReflectError::UnknownField(name.to_string()))?;
+            if value.ty() != field.ty {
+                return Err(ReflectError::WrongType {
+                    field: name.to_string(),
+                    expected: field.ty,
+                });
+            }
+            indexed[field.index as usize] = Some(value.clone());
+        }
+        if self.reflect.insert_component(world, entity, &indexed) {
+            Ok(())
+        } else {
+            Err(ReflectError::NotInsertable)
+        }
+    }
+
+    /// Returns false if the entity didn't have the component
+    pub fn remove(&self, world: &mut World, entity: Entity) -> bool {
+        self.reflect.remove_component(world, entity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use unreal_api::Component;
+    use unreal_reflect::TypeUuid;
+
+    use super::*;
+    use crate::math::Vec3;
+
+    #[derive(Default, Debug, PartialEq, Component)]
+    #[uuid = "0b1c8f57-3d4e-4f6a-8a9b-2c3d4e5f6a7b"]
+    #[reflect(default)]
+    struct Stats {
+        health: f32,
+        position: Vec3,
+    }
+
+    #[derive(Debug, Component)]
+    #[uuid = "7c2d9e68-4e5f-4a7b-9bac-3d4e5f6a7b8c"]
+    #[reflect(editor)]
+    struct Door {
+        open: bool,
+        angle: f32,
+    }
+
+    mod other {
+        use unreal_api::Component;
+
+        #[derive(Debug, Component)]
+        #[uuid = "8d3eaf79-5f6a-4b8c-acbd-4e5f6a7b8c9d"]
+        #[reflect(editor)]
+        pub struct Door {
+            pub locked: bool,
+        }
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = ReflectionRegistry::default();
+        registry.register::<Stats>();
+        registry.register::<Door>();
+        registry.register::<other::Door>();
+        TypeRegistry::new(Arc::new(registry))
+    }
+
+    #[test]
+    fn types_are_found_by_uuid_and_unique_name() {
+        let registry = registry();
+        let names: Vec<_> = registry.types().map(|ty| ty.name()).collect();
+        assert_eq!(names, vec!["Door", "Door", "Stats"]);
+
+        assert_eq!(
+            registry.get_by_name("Stats").unwrap().uuid(),
+            Stats::TYPE_UUID
+        );
+        // Two types are called `Door`
+        assert!(registry.get_by_name("Door").is_none());
+        assert_eq!(registry.get(Door::TYPE_UUID).unwrap().name(), "Door");
+        assert!(registry
+            .get(other::Door::TYPE_UUID)
+            .unwrap()
+            .is_editor_component());
+        assert!(registry.get_by_name("Missing").is_none());
+    }
+
+    #[test]
+    fn fields_are_read_and_written() {
+        let registry = registry();
+        let mut world = World::new();
+        let entity = world.spawn().insert(Stats::default()).id();
+        let by_uuid = registry.get(Stats::TYPE_UUID).unwrap();
+        let by_name = registry.get_by_name("Stats").unwrap();
+
+        by_uuid
+            .set_field(&mut world, entity, "health", &ReflectValue::Float(42.0))
+            .unwrap();
+        assert!(matches!(
+            by_name.get_field(&world, entity, "health"),
+            Some(ReflectValue::Float(health)) if health == 42.0
+        ));
+        assert_eq!(
+            by_name.set_field(&mut world, entity, "health", &ReflectValue::Bool(true)),
+            Err(ReflectError::WrongType {
+                field: "health".to_string(),
+                expected: ReflectType::Float,
+            })
+        );
+        assert_eq!(
+            by_name.set_field(&mut world, entity, "speed", &ReflectValue::Float(1.0)),
+            Err(ReflectError::UnknownField("speed".to_string()))
+        );
+
+        let empty = world.spawn().id();
+        assert!(by_uuid.get_field(&world, empty, "health").is_none());
+        assert_eq!(
+            by_uuid.set_field(&mut world, empty, "health", &ReflectValue::Float(1.0)),
+            Err(ReflectError::MissingComponent)
+        );
+    }
+
+    #[test]
+    fn components_are_inserted_and_removed() {
+        let registry = registry();
+        let mut world = World::new();
+        let entity = world.spawn().id();
+
+        let stats = registry.get_by_name("Stats").unwrap();
+        stats
+            .insert(&mut world, entity, &[("health", ReflectValue::Float(5.0))])
+            .unwrap();
+        assert_eq!(
+            *world.get::<Stats>(entity).unwrap(),
+            Stats {
+                health: 5.0,
+                position: Vec3::ZERO,
+            }
+        );
+        assert_eq!(
+            stats.insert(&mut world, entity, &[("health", ReflectValue::Bool(false))]),
+            Err(ReflectError::WrongType {
+                field: "health".to_string(),
+                expected: ReflectType::Float,
+            })
+        );
+        assert_eq!(
+            stats.insert(&mut world, entity, &[("speed", ReflectValue::Float(1.0))]),
+            Err(ReflectError::UnknownField("speed".to_string()))
+        );
+
+        let door = registry.get(Door::TYPE_UUID).unwrap();
+        // Without `#[reflect(default)]` every field needs a value
+        assert_eq!(
+            door.insert(&mut world, entity, &[("open", ReflectValue::Bool(true))]),
+            Err(ReflectError::NotInsertable)
+        );
+        door.insert(
+            &mut world,
+            entity,
+            &[
+                ("open", ReflectValue::Bool(true)),
+                ("angle", ReflectValue::Float(90.0)),
+            ],
+        )
+        .unwrap();
+        let uuids: Vec<_> = registry
+            .components_of(&world, entity)
+            .map(|ty| ty.uuid())
+            .collect();
+        assert_eq!(uuids, vec![Door::TYPE_UUID, Stats::TYPE_UUID]);
+
+        assert!(stats.remove(&mut world, entity));
+        assert!(!stats.remove(&mut world, entity));
+        assert!(world.get::<Stats>(entity).is_none());
+        assert!(door.has_component(&world, entity));
+    }
+}
@@ -18,6 +18,8 @@ fn main() {
         .include_item("ActorEndOverlap")
         .include_item("ActorHitEvent")
         .include_item("ActorDestroyEvent")
+        .include_item("AssetLoadedEvent")
+        .include_item("LevelStreamingEvent")
         .with_pragma_once(true)
         //.with_config(Config {
         //    structure: StructConfig  {
@@ -1,5 +1,3 @@
-use std::os::raw::c_char;
-
 use crate::{
     AActorOpaque, ActorComponentPtr, Entity, Quaternion, RustAlloc, UClassOpague, Vector3,
 };
@@ -11,8 +9,6 @@ pub type GetSpatialDataFn = extern "C" fn(
     scale: &mut Vector3,
 );
 
-pub type LogFn = extern "C" fn(*const c_char, i32);
-
 pub type SetSpatialDataFn = extern "C" fn(
     actor: *mut AActorOpaque,
     position: Vector3,
@@ -37,19 +33,42 @@ pub type IsMoveableFn = unsafe extern "C" fn(actor: *const AActorOpaque) -> u32;
 
 pub type GetActorNameFn = unsafe extern "C" fn(actor: *const AActorOpaque, data: *mut RustAlloc);
 
+/// Writes the object path of the actor without the play in editor prefix. Actors that are placed
+/// in a level keep their path between sessions.
+pub type GetActorPathFn = unsafe extern "C" fn(actor: *const AActorOpaque, data: *mut RustAlloc);
+
+/// Writes the tags of actors that implement `IGameplayTagAssetInterface` separated by `,` into
+/// `data`. Returns 0 if the actor doesn't implement the interface.
+pub type GetOwnedGameplayTagsFn =
+    unsafe extern "C" fn(actor: *const AActorOpaque, data: *mut RustAlloc) -> u32;
+
 pub type SetOwnerFn =
     unsafe extern "C" fn(actor: *mut AActorOpaque, new_owner: *const AActorOpaque);
 
 pub type RegisterActorOnOverlapFn = unsafe extern "C" fn(actor: *mut AActorOpaque);
 pub type RegisterActorOnHitFn = unsafe extern "C" fn(actor: *mut AActorOpaque);
+pub type UnregisterActorOnOverlapFn = unsafe extern "C" fn(actor: *mut AActorOpaque);
+pub type UnregisterActorOnHitFn = unsafe extern "C" fn(actor: *mut AActorOpaque);
 
 pub type SetViewTargetFn = unsafe extern "C" fn(actor: *const AActorOpaque);
 
 pub type DestroyActorFn = unsafe extern "C" fn(actor: *const AActorOpaque);
 
+/// Calls `UGameplayStatics::ApplyDamage` and returns the damage that the actor actually took.
+/// `instigator` can be null.
+pub type ApplyDamageFn = unsafe extern "C" fn(
+    actor: *mut AActorOpaque,
+    damage: f32,
+    instigator: *mut AActorOpaque,
+) -> f32;
+
 extern "C" {
     pub fn RegisterActorOnHit(actor: *mut AActorOpaque);
     pub fn RegisterActorOnOverlap(actor: *mut AActorOpaque);
+    pub fn ApplyDamage(actor: *mut AActorOpaque, damage: f32, instigator: *mut AActorOpaque)
+        -> f32;
+    pub fn UnregisterActorOnHit(actor: *mut AActorOpaque);
+    pub fn UnregisterActorOnOverlap(actor: *mut AActorOpaque);
 
     pub fn SetOwner(actor: *mut AActorOpaque, new_owner: *const AActorOpaque);
 
@@ -83,6 +102,8 @@ extern "C" {
     pub fn IsMoveable(actor: *const AActorOpaque) -> u32;
 
     pub fn GetActorName(actor: *const AActorOpaque, data: *mut RustAlloc);
+    pub fn GetActorPath(actor: *const AActorOpaque, data: *mut RustAlloc);
+    pub fn GetOwnedGameplayTags(actor: *const AActorOpaque, data: *mut RustAlloc) -> u32;
 
     pub fn DestroyActor(actor: *const AActorOpaque);
 
@@ -97,12 +118,17 @@ pub struct ActorFns {
     pub get_actor_components: GetActorComponentsFn,
     pub register_actor_on_overlap: RegisterActorOnOverlapFn,
     pub register_actor_on_hit: RegisterActorOnHitFn,
+    pub unregister_actor_on_overlap: UnregisterActorOnOverlapFn,
+    pub unregister_actor_on_hit: UnregisterActorOnHitFn,
     pub get_root_component: GetRootComponentFn,
     pub get_registered_classes: GetRegisteredClassesFn,
     pub get_class: GetClassFn,
     pub set_view_target: SetViewTargetFn,
     pub get_actor_name: GetActorNameFn,
+    pub get_actor_path: GetActorPathFn,
     pub set_owner: SetOwnerFn,
     pub is_moveable: IsMoveableFn,
     pub destroy_actor: DestroyActorFn,
+    pub apply_damage: ApplyDamageFn,
+    pub get_owned_gameplay_tags: GetOwnedGameplayTagsFn,
 }
@@ -0,0 +1,23 @@
+use crate::{UObjectOpague, UObjectType, Utf8Str};
+
+pub type LoadAssetFn = unsafe extern "C" fn(path: Utf8Str, ty: UObjectType) -> *mut UObjectOpague;
+
+pub type LoadAssetAsyncFn =
+    unsafe extern "C" fn(path: Utf8Str, ty: UObjectType, handle: u64) -> u32;
+
+pub type UnloadAssetFn = unsafe extern "C" fn(handle: u64);
+
+extern "C" {
+    pub fn LoadAsset(path: Utf8Str, ty: UObjectType) -> *mut UObjectOpague;
+
+    pub fn LoadAssetAsync(path: Utf8Str, ty: UObjectType, handle: u64) -> u32;
+
+    pub fn UnloadAsset(handle: u64);
+}
+
+#[repr(C)]
+pub struct AssetFns {
+    pub load_asset: LoadAssetFn,
+    pub load_asset_async: LoadAssetAsyncFn,
+    pub unload_asset: UnloadAssetFn,
+}
@@ -0,0 +1,39 @@
+use crate::{Color, Quaternion, Utf8Str, Vector3};
+
+#[repr(u32)]
+#[derive(Debug, Copy, Clone)]
+pub enum DebugDrawType {
+    Line,
+    Sphere,
+    Capsule,
+    Box,
+    Arrow,
+    Text,
+    CoordinateFrame,
+}
+
+/// A single debug draw call. Fields that the `ty` doesn't use are ignored.
+#[repr(C)]
+pub struct DebugDrawCommand {
+    pub ty: DebugDrawType,
+    /// Start of lines and arrows, center of every other shape
+    pub position: Vector3,
+    /// End of lines and arrows, half extent of boxes
+    pub end: Vector3,
+    pub rotation: Quaternion,
+    /// Radius of spheres and capsules, head size of arrows and axis length of coordinate frames
+    pub size: f32,
+    pub half_height: f32,
+    pub thickness: f32,
+    pub color: Color,
+    /// In seconds, anything <= 0 is only drawn for the current frame
+    pub duration: f32,
+    pub depth_test: u32,
+    pub text: Utf8Str,
+}
+
+pub type DebugDrawFn = unsafe extern "C" fn(commands: *const DebugDrawCommand, len: usize);
+
+extern "C" {
+    pub fn DebugDraw(commands: *const DebugDrawCommand, len: usize);
+}
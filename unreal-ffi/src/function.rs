@@ -0,0 +1,33 @@
+use crate::{Entity, ReflectValue, ReflectionType, Utf8Str};
+
+pub type NumberOfFunctionsFn = unsafe extern "C" fn(out: *mut u32) -> u32;
+pub type GetFunctionNameFn = unsafe extern "C" fn(idx: u32, name: *mut Utf8Str) -> u32;
+pub type GetFunctionNumberOfArgumentsFn = unsafe extern "C" fn(idx: u32, out: *mut u32) -> u32;
+pub type GetFunctionArgumentFn = unsafe extern "C" fn(
+    idx: u32,
+    argument_idx: u32,
+    name: *mut Utf8Str,
+    ty: *mut ReflectionType,
+) -> u32;
+/// Writes 0 to `has_return_value` if the function doesn't return anything.
+pub type GetFunctionReturnTypeFn =
+    unsafe extern "C" fn(idx: u32, has_return_value: *mut u32, ty: *mut ReflectionType) -> u32;
+/// Calls the function with the given name. Strings in `out` stay valid until the next call.
+pub type CallFunctionFn = unsafe extern "C" fn(
+    name: Utf8Str,
+    entity: Entity,
+    arguments: *const ReflectValue,
+    len: usize,
+    has_return_value: *mut u32,
+    out: *mut ReflectValue,
+) -> u32;
+
+#[repr(C)]
+pub struct FunctionFns {
+    pub number_of_functions: NumberOfFunctionsFn,
+    pub get_function_name: GetFunctionNameFn,
+    pub get_function_number_of_arguments: GetFunctionNumberOfArgumentsFn,
+    pub get_function_argument: GetFunctionArgumentFn,
+    pub get_function_return_type: GetFunctionReturnTypeFn,
+    pub call_function: CallFunctionFn,
+}
@@ -0,0 +1,51 @@
+use crate::{AActorOpaque, RustAlloc, Utf8Str};
+
+pub type ULevelOpaque = std::ffi::c_void;
+
+/// Returns the level that the actor is placed in, which is the persistent level for actors that
+/// were spawned at runtime
+pub type GetActorLevelFn = unsafe extern "C" fn(actor: *const AActorOpaque) -> *mut ULevelOpaque;
+/// Writes the map name of the level, without the play in editor prefix
+pub type GetLevelNameFn = unsafe extern "C" fn(level: *const ULevelOpaque, out: *mut RustAlloc);
+pub type IsPersistentLevelFn = unsafe extern "C" fn(level: *const ULevelOpaque) -> u32;
+/// Starts streaming in a sublevel. With `block` the world loads it in one go when it updates
+/// streaming in its next tick. Returns 0 if the world has no streaming level with that name.
+pub type LoadStreamLevelFn =
+    unsafe extern "C" fn(name: Utf8Str, make_visible: u32, block: u32) -> u32;
+/// Starts streaming out a sublevel. Returns 0 if the world has no streaming level with that name.
+pub type UnloadStreamLevelFn = unsafe extern "C" fn(name: Utf8Str) -> u32;
+/// Shows or hides a sublevel that is loaded. Returns 0 if the world has no streaming level with
+/// that name.
+pub type SetStreamLevelVisibleFn = unsafe extern "C" fn(name: Utf8Str, visible: u32) -> u32;
+/// Travels to another map. This ends play in the current world.
+pub type OpenLevelFn = unsafe extern "C" fn(name: Utf8Str);
+
+extern "C" {
+    pub fn GetActorLevel(actor: *const AActorOpaque) -> *mut ULevelOpaque;
+    pub fn GetLevelName(level: *const ULevelOpaque, out: *mut RustAlloc);
+    pub fn IsPersistentLevel(level: *const ULevelOpaque) -> u32;
+    pub fn LoadStreamLevel(name: Utf8Str, make_visible: u32, block: u32) -> u32;
+    pub fn UnloadStreamLevel(name: Utf8Str) -> u32;
+    pub fn SetStreamLevelVisible(name: Utf8Str, visible: u32) -> u32;
+    pub fn OpenLevel(name: Utf8Str);
+}
+
+#[repr(C)]
+pub struct LevelFns {
+    pub get_actor_level: GetActorLevelFn,
+    pub get_level_name: GetLevelNameFn,
+    pub is_persistent_level: IsPersistentLevelFn,
+    pub load_stream_level: LoadStreamLevelFn,
+    pub unload_stream_level: UnloadStreamLevelFn,
+    pub set_stream_level_visible: SetStreamLevelVisibleFn,
+    pub open_level: OpenLevelFn,
+}
+
+/// Sent for every streaming level whose state changed since the last tick
+#[repr(C)]
+pub struct LevelStreamingEvent {
+    /// Null if the level is not loaded anymore
+    pub level: *mut ULevelOpaque,
+    /// Only valid during the event
+    pub name: Utf8Str,
+}
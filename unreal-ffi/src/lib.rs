@@ -1,12 +1,30 @@
 use glam::{Quat, Vec3};
 use std::{ffi::c_void, os::raw::c_char};
 pub mod actor;
+pub mod asset;
+pub mod debug_draw;
+pub mod function;
+pub mod level;
+pub mod object;
 pub mod physics;
+pub mod save_game;
 pub mod sound;
+pub mod state;
+pub mod time;
+pub mod visual_log;
 
 pub use actor::*;
+pub use asset::*;
+pub use debug_draw::*;
+pub use function::*;
+pub use level::*;
+pub use object::*;
 pub use physics::*;
+pub use save_game::*;
 pub use sound::*;
+pub use state::*;
+pub use time::*;
+pub use visual_log::*;
 
 #[repr(u8)]
 #[derive(Debug)]
@@ -15,12 +33,20 @@ pub enum ResultCode {
     Panic = 1,
 }
 #[repr(u32)]
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum UObjectType {
     UClass,
+    USound,
+    UStaticMesh,
+    UMaterial,
+    UTexture,
+    UParticleSystem,
+    UDataAsset,
+    UAnimationAsset,
 }
 
 #[repr(C)]
+#[derive(Copy, Clone)]
 pub struct Utf8Str {
     pub ptr: *const c_char,
     pub len: usize,
@@ -77,6 +103,12 @@ impl Color {
         b: 255,
         a: 255,
     };
+    pub const WHITE: Self = Self {
+        r: 255,
+        g: 255,
+        b: 255,
+        a: 255,
+    };
 }
 
 #[repr(C)]
@@ -135,7 +167,31 @@ pub type UClassOpague = c_void;
 pub type UObjectOpague = c_void;
 pub type USoundBaseOpague = c_void;
 
-pub type LogFn = extern "C" fn(*const c_char, i32);
+/// Converted to `ELogVerbosity` with `ToELogVerbosity`, the values differ from unreal's
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LogVerbosity {
+    Error = 0,
+    Warning = 1,
+    Display = 2,
+    Log = 3,
+    Verbose = 4,
+    VeryVerbose = 5,
+}
+
+#[repr(C)]
+pub struct LogRecord {
+    /// The unreal log category, which is derived from the `log` target
+    pub category: Utf8Str,
+    pub verbosity: LogVerbosity,
+    pub message: Utf8Str,
+    /// Empty if unknown
+    pub file: Utf8Str,
+    /// 0 if unknown
+    pub line: u32,
+}
+
+pub type LogFn = unsafe extern "C" fn(record: *const LogRecord);
 pub type IterateActorsFn = unsafe extern "C" fn(array: *mut *mut AActorOpaque, len: *mut u64);
 pub type GetActionStateFn =
     unsafe extern "C" fn(name: *const c_char, len: usize, state: ActionState, out: *mut u32);
@@ -147,29 +203,9 @@ pub type SpawnActorFn = unsafe extern "C" fn(
     scale: Vector3,
 ) -> *mut AActorOpaque;
 pub type GetMouseDeltaFn = unsafe extern "C" fn(x: &mut f32, y: &mut f32);
-pub type VisualLogSegmentFn =
-    unsafe extern "C" fn(owner: *const AActorOpaque, start: Vector3, end: Vector3, color: Color);
-pub type VisualLogCapsuleFn = unsafe extern "C" fn(
-    category: Utf8Str,
-    owner: *const AActorOpaque,
-    position: Vector3,
-    rotation: Quaternion,
-    half_height: f32,
-    radius: f32,
-    color: Color,
-);
-
-pub type VisualLogLocationFn = unsafe extern "C" fn(
-    category: Utf8Str,
-    owner: *const AActorOpaque,
-    position: Vector3,
-    radius: f32,
-    color: Color,
-);
-
 extern "C" {
     pub fn TickActor(actor: *mut AActorOpaque, dt: f32);
-    pub fn Log(s: *const c_char, len: i32);
+    pub fn Log(record: *const LogRecord);
     pub fn IterateActors(array: *mut *mut AActorOpaque, len: *mut u64);
     pub fn GetActionState(name: *const c_char, len: usize, state: ActionState, out: *mut u32);
     pub fn GetAxisValue(name: *const c_char, len: usize, value: &mut f32);
@@ -180,24 +216,7 @@ extern "C" {
         scale: Vector3,
     ) -> *mut AActorOpaque;
     pub fn GetMouseDelta(x: &mut f32, y: &mut f32);
-
-    pub fn VisualLogSegment(owner: *const AActorOpaque, start: Vector3, end: Vector3, color: Color);
-    pub fn VisualLogCapsule(
-        category: Utf8Str,
-        owner: *const AActorOpaque,
-        position: Vector3,
-        rotation: Quaternion,
-        half_height: f32,
-        radius: f32,
-        color: Color,
-    );
-    pub fn VisualLogLocation(
-        category: Utf8Str,
-        owner: *const AActorOpaque,
-        position: Vector3,
-        radius: f32,
-        color: Color,
-    );
+    pub fn SendBlueprintEvent(uuid: Uuid, fields: *const ReflectValue, len: usize);
 }
 
 #[repr(C)]
@@ -210,11 +229,16 @@ pub struct UnrealBindings {
     pub get_axis_value: GetAxisValueFn,
     pub spawn_actor: SpawnActorFn,
     pub get_mouse_delta: GetMouseDeltaFn,
-    pub visual_log_segment: VisualLogSegmentFn,
-    pub visual_log_capsule: VisualLogCapsuleFn,
-    pub visual_log_location: VisualLogLocationFn,
+    pub visual_log_fns: VisualLogFns,
+    pub debug_draw: DebugDrawFn,
+    pub send_blueprint_event: SendBlueprintEventFn,
     pub editor_component_fns: EditorComponentFns,
     pub sound_fns: SoundFns,
+    pub asset_fns: AssetFns,
+    pub object_fns: ObjectFns,
+    pub time_fns: TimeFns,
+    pub level_fns: LevelFns,
+    pub save_game_fns: SaveGameFns,
 }
 unsafe impl Sync for UnrealBindings {}
 unsafe impl Send for UnrealBindings {}
@@ -274,11 +298,30 @@ pub struct Uuid {
 pub type EntryUnrealBindingsFn =
     unsafe extern "C" fn(bindings: UnrealBindings, rust_bindings: *mut RustBindings) -> u32;
 pub type BeginPlayFn = unsafe extern "C" fn() -> ResultCode;
-pub type TickFn = unsafe extern "C" fn(dt: f32) -> ResultCode;
+pub type EndPlayFn = unsafe extern "C" fn(reason: EndPlayReason) -> ResultCode;
+pub type TickFn = unsafe extern "C" fn(time: TickTime) -> ResultCode;
+/// Runs the stages of a tick group. Called once per frame for every group, after `tick`.
+pub type TickGroupFn = unsafe extern "C" fn(group: TickGroup) -> ResultCode;
 pub type RetrieveUuids = unsafe extern "C" fn(ptr: *mut Uuid, len: *mut usize);
 pub type GetVelocityRustFn =
     unsafe extern "C" fn(actor: *const AActorOpaque, velocity: &mut Vector3);
 
+/// Mirrors `EEndPlayReason`
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EndPlayReason {
+    /// The game mode was destroyed explicitly
+    Destroyed = 0,
+    /// The world is being unloaded for a level transition
+    LevelTransition = 1,
+    /// Play in editor was stopped
+    EndPlayInEditor = 2,
+    /// The level of the game mode was removed from the world
+    RemovedFromWorld = 3,
+    /// The application is shutting down
+    Quit = 4,
+}
+
 #[repr(u32)]
 pub enum EventType {
     ActorSpawned = 0,
@@ -286,6 +329,11 @@ pub enum EventType {
     ActorEndOverlap = 2,
     ActorOnHit = 3,
     ActorDestroy = 4,
+    AssetLoaded = 5,
+    LevelLoaded = 6,
+    LevelUnloaded = 7,
+    LevelShown = 8,
+    LevelHidden = 9,
 }
 
 #[repr(C)]
@@ -297,18 +345,34 @@ pub struct ActorSpawnedEvent {
 pub struct ActorBeginOverlap {
     pub overlapped_actor: *mut AActorOpaque,
     pub other: *mut AActorOpaque,
+    pub overlapped_component: *mut UPrimtiveOpaque,
+    pub other_component: *mut UPrimtiveOpaque,
+    pub other_body_index: i32,
+    pub from_sweep: u32,
+    /// Only valid if `from_sweep` is 1
+    pub sweep_result: HitResult,
 }
 #[repr(C)]
 pub struct ActorEndOverlap {
     pub overlapped_actor: *mut AActorOpaque,
     pub other: *mut AActorOpaque,
+    pub overlapped_component: *mut UPrimtiveOpaque,
+    pub other_component: *mut UPrimtiveOpaque,
+    pub other_body_index: i32,
 }
 
 #[repr(C)]
 pub struct ActorHitEvent {
     pub self_actor: *mut AActorOpaque,
     pub other: *mut AActorOpaque,
+    pub self_component: *mut UPrimtiveOpaque,
+    pub other_component: *mut UPrimtiveOpaque,
     pub normal_impulse: Vector3,
+    pub hit: HitResult,
+    /// Empty if no bone was hit. Only valid during the event.
+    pub bone_name: Utf8Str,
+    /// Velocity of the self component relative to the other component
+    pub relative_velocity: Vector3,
 }
 
 #[repr(C)]
@@ -316,19 +380,40 @@ pub struct ActorDestroyEvent {
     pub actor: *mut AActorOpaque,
 }
 
+#[repr(C)]
+pub struct AssetLoadedEvent {
+    pub handle: u64,
+    /// Null if the asset could not be loaded
+    pub object: *mut UObjectOpague,
+}
+
 #[repr(C)]
 pub struct RustBindings {
     pub retrieve_uuids: RetrieveUuids,
     pub tick: TickFn,
+    pub tick_group: TickGroupFn,
     pub begin_play: BeginPlayFn,
+    pub end_play: EndPlayFn,
     pub unreal_event: UnrealEventFn,
+    pub send_event: SendEventFn,
     pub reflection_fns: ReflectionFns,
+    pub function_fns: FunctionFns,
     pub allocate_fns: AllocateFns,
+    pub state_fns: StateFns,
 }
 
 pub type UnrealEventFn = unsafe extern "C" fn(ty: *const EventType, data: *const c_void);
+/// Sends a user defined event, that was registered in Rust, from unreal into the ECS. Returns 0 if
+/// the event is unknown or the fields don't match. Events sent while Rust is running, eg from a
+/// blueprint that handles an event from Rust, are queued and always return 1.
+pub type SendEventFn =
+    unsafe extern "C" fn(uuid: Uuid, fields: *const ReflectValue, len: usize) -> u32;
+/// Sends a user defined event from Rust to the blueprint event dispatchers.
+pub type SendBlueprintEventFn =
+    unsafe extern "C" fn(uuid: Uuid, fields: *const ReflectValue, len: usize);
 
 #[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ReflectionType {
     Float,
     Vector3,
@@ -336,9 +421,47 @@ pub enum ReflectionType {
     Quaternion,
     UClass,
     USound,
+    UStaticMesh,
+    UMaterial,
+    UTexture,
+    UParticleSystem,
+    UDataAsset,
+    UAnimationAsset,
+    SoftObjectPath,
+    GameplayTags,
     Composite,
 }
 
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct SoftObjectPathValue {
+    pub ty: UObjectType,
+    pub path: Utf8Str,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union ReflectValueData {
+    pub float32: f32,
+    pub vector3: Vector3,
+    pub boolean: u32,
+    pub quaternion: Quaternion,
+    pub uobject: *mut UObjectOpague,
+    pub soft_object_path: SoftObjectPathValue,
+    /// Tag names separated by `,`
+    pub gameplay_tags: Utf8Str,
+}
+
+/// A single reflected value that is passed by value across the ffi boundary, eg the fields of an
+/// event. `ty` decides which field of `data` is valid. Strings are borrowed from the sender and
+/// are only valid for the duration of the call.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct ReflectValue {
+    pub ty: ReflectionType,
+    pub data: ReflectValueData,
+}
+
 pub type NumberOfFieldsFn = unsafe extern "C" fn(uuid: Uuid, out: *mut u32) -> u32;
 pub type GetTypeNameFn = unsafe extern "C" fn(uuid: Uuid, name: *mut Utf8Str) -> u32;
 pub type GetFieldNameFn =
@@ -356,10 +479,27 @@ pub type GetFieldQuatValueFn =
     unsafe extern "C" fn(uuid: Uuid, entity: Entity, field_idx: u32, out: *mut Quaternion) -> u32;
 pub type HasComponentFn = unsafe extern "C" fn(entity: Entity, uuid: Uuid) -> u32;
 pub type IsEditorComponentFn = unsafe extern "C" fn(uuid: Uuid) -> u32;
+pub type IsEventFn = unsafe extern "C" fn(uuid: Uuid) -> u32;
+
+impl From<UObjectType> for ReflectionType {
+    fn from(ty: UObjectType) -> Self {
+        match ty {
+            UObjectType::UClass => ReflectionType::UClass,
+            UObjectType::USound => ReflectionType::USound,
+            UObjectType::UStaticMesh => ReflectionType::UStaticMesh,
+            UObjectType::UMaterial => ReflectionType::UMaterial,
+            UObjectType::UTexture => ReflectionType::UTexture,
+            UObjectType::UParticleSystem => ReflectionType::UParticleSystem,
+            UObjectType::UDataAsset => ReflectionType::UDataAsset,
+            UObjectType::UAnimationAsset => ReflectionType::UAnimationAsset,
+        }
+    }
+}
 
 #[repr(C)]
 pub struct ReflectionFns {
     pub is_editor_component: IsEditorComponentFn,
+    pub is_event: IsEventFn,
     pub number_of_fields: NumberOfFieldsFn,
     pub has_component: HasComponentFn,
     pub get_type_name: GetTypeNameFn,
@@ -446,6 +586,18 @@ extern "C" {
         ty: UObjectType,
         out: *mut *mut UObjectOpague,
     ) -> u32;
+    pub fn GetEditorComponentSoftObjectPath(
+        actor: *const AActorOpaque,
+        uuid: Uuid,
+        field: Utf8Str,
+        out: *mut RustAlloc,
+    ) -> u32;
+    pub fn GetEditorComponentGameplayTags(
+        actor: *const AActorOpaque,
+        uuid: Uuid,
+        field: Utf8Str,
+        out: *mut RustAlloc,
+    ) -> u32;
 }
 
 pub type GetEditorComponentUuidsFn =
@@ -485,6 +637,19 @@ pub type GetEditorComponentUObjectFn = unsafe extern "C" fn(
     ty: UObjectType,
     out: *mut *mut UObjectOpague,
 ) -> u32;
+pub type GetEditorComponentSoftObjectPathFn = unsafe extern "C" fn(
+    actor: *const AActorOpaque,
+    uuid: Uuid,
+    field: Utf8Str,
+    out: *mut RustAlloc,
+) -> u32;
+/// Writes the tag names separated by `,` into `out`
+pub type GetEditorComponentGameplayTagsFn = unsafe extern "C" fn(
+    actor: *const AActorOpaque,
+    uuid: Uuid,
+    field: Utf8Str,
+    out: *mut RustAlloc,
+) -> u32;
 
 #[repr(C)]
 pub struct EditorComponentFns {
@@ -494,4 +659,6 @@ pub struct EditorComponentFns {
     pub get_editor_component_bool: GetEditorComponentBoolFn,
     pub get_editor_component_float: GetEditorComponentFloatFn,
     pub get_editor_component_uobject: GetEditorComponentUObjectFn,
+    pub get_editor_component_soft_object_path: GetEditorComponentSoftObjectPathFn,
+    pub get_editor_component_gameplay_tags: GetEditorComponentGameplayTagsFn,
 }
@@ -0,0 +1,46 @@
+use crate::{ReflectValue, UObjectOpague, Utf8Str};
+
+/// Calls a `UFunction` on any `UObject`, including actors. Only the return value is written back,
+/// out parameters are ignored. `has_return_value` is only 0 for functions without a return value,
+/// a return value that can't be read fails the call. Strings in `out` stay valid until the next
+/// call.
+pub type CallUFunctionFn = unsafe extern "C" fn(
+    object: *mut UObjectOpague,
+    name: Utf8Str,
+    arguments: *const ReflectValue,
+    len: usize,
+    has_return_value: *mut u32,
+    out: *mut ReflectValue,
+) -> u32;
+/// Strings in `out` stay valid until the next call.
+pub type GetUPropertyFn =
+    unsafe extern "C" fn(object: *mut UObjectOpague, name: Utf8Str, out: *mut ReflectValue) -> u32;
+pub type SetUPropertyFn = unsafe extern "C" fn(
+    object: *mut UObjectOpague,
+    name: Utf8Str,
+    value: *const ReflectValue,
+) -> u32;
+
+extern "C" {
+    pub fn CallUFunction(
+        object: *mut UObjectOpague,
+        name: Utf8Str,
+        arguments: *const ReflectValue,
+        len: usize,
+        has_return_value: *mut u32,
+        out: *mut ReflectValue,
+    ) -> u32;
+    pub fn GetUProperty(object: *mut UObjectOpague, name: Utf8Str, out: *mut ReflectValue) -> u32;
+    pub fn SetUProperty(
+        object: *mut UObjectOpague,
+        name: Utf8Str,
+        value: *const ReflectValue,
+    ) -> u32;
+}
+
+#[repr(C)]
+pub struct ObjectFns {
+    pub call_function: CallUFunctionFn,
+    pub get_property: GetUPropertyFn,
+    pub set_property: SetUPropertyFn,
+}
@@ -1,4 +1,4 @@
-use crate::{AActorOpaque, Quaternion, UPrimtiveOpaque, Vector3};
+use crate::{AActorOpaque, Quaternion, UObjectOpague, UPrimtiveOpaque, Vector3};
 
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -89,6 +89,10 @@ pub struct HitResult {
     pub impact_location: Vector3,
     pub pentration_depth: f32,
     pub start_penetrating: u32,
+    /// Null if the surface has no physical material
+    pub physical_material: *mut UObjectOpague,
+    /// Mirrors `EPhysicalSurface`
+    pub surface_type: u32,
 }
 
 impl Default for HitResult {
@@ -103,6 +107,8 @@ impl Default for HitResult {
             impact_normal: Default::default(),
             pentration_depth: Default::default(),
             start_penetrating: Default::default(),
+            physical_material: std::ptr::null_mut(),
+            surface_type: Default::default(),
         }
     }
 }
@@ -135,13 +141,14 @@ pub type SweepFn = unsafe extern "C" fn(
     result: &mut HitResult,
 ) -> u32;
 
+/// Writes up to `max_results` overlaps into `results` and returns the number of overlaps written.
 pub type OverlapMultiFn = unsafe extern "C" fn(
     collision_shape: CollisionShape,
     position: Vector3,
     rotation: Quaternion,
     params: LineTraceParams,
     max_results: usize,
-    result: *mut *mut OverlapResult,
+    results: *mut OverlapResult,
 ) -> u32;
 
 pub type GetCollisionShapeFn =
@@ -202,7 +209,7 @@ extern "C" {
         rotation: Quaternion,
         params: LineTraceParams,
         max_results: usize,
-        result: *mut *mut OverlapResult,
+        results: *mut OverlapResult,
     ) -> u32;
 
     pub fn GetCollisionShape(primitive: *const UPrimtiveOpaque, shape: *mut CollisionShape) -> u32;
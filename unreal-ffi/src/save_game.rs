@@ -0,0 +1,25 @@
+use crate::{RustAlloc, Utf8Str};
+
+/// Writes `data` to a save slot through unreal's save game system. Returns 0 on failure.
+pub type SaveDataToSlotFn =
+    unsafe extern "C" fn(slot: Utf8Str, user_index: i32, data: *const u8, len: usize) -> u32;
+/// Returns 0 if the slot doesn't exist or could not be read
+pub type LoadDataFromSlotFn =
+    unsafe extern "C" fn(slot: Utf8Str, user_index: i32, out: *mut RustAlloc) -> u32;
+pub type DoesSaveGameExistFn = unsafe extern "C" fn(slot: Utf8Str, user_index: i32) -> u32;
+pub type DeleteSaveGameInSlotFn = unsafe extern "C" fn(slot: Utf8Str, user_index: i32) -> u32;
+
+extern "C" {
+    pub fn SaveDataToSlot(slot: Utf8Str, user_index: i32, data: *const u8, len: usize) -> u32;
+    pub fn LoadDataFromSlot(slot: Utf8Str, user_index: i32, out: *mut RustAlloc) -> u32;
+    pub fn DoesSaveGameExist(slot: Utf8Str, user_index: i32) -> u32;
+    pub fn DeleteSaveGameInSlot(slot: Utf8Str, user_index: i32) -> u32;
+}
+
+#[repr(C)]
+pub struct SaveGameFns {
+    pub save_data_to_slot: SaveDataToSlotFn,
+    pub load_data_from_slot: LoadDataFromSlotFn,
+    pub does_save_game_exist: DoesSaveGameExistFn,
+    pub delete_save_game_in_slot: DeleteSaveGameInSlotFn,
+}
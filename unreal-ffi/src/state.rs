@@ -0,0 +1,15 @@
+use crate::Utf8Str;
+
+/// Queues a transition of the state machine `machine` to the state `state`. Returns 0 if the
+/// machine or state is unknown, if the machine is already in that state, or if Rust is still
+/// running, eg when a system called the blueprint that switches the state.
+pub type SetStateFn = unsafe extern "C" fn(machine: Utf8Str, state: Utf8Str) -> u32;
+/// Writes the name of the current state to `out`. The string is static. Returns 0 if the machine is
+/// unknown or if Rust is still running.
+pub type GetStateFn = unsafe extern "C" fn(machine: Utf8Str, out: *mut Utf8Str) -> u32;
+
+#[repr(C)]
+pub struct StateFns {
+    pub set_state: SetStateFn,
+    pub get_state: GetStateFn,
+}
@@ -0,0 +1,60 @@
+use crate::AActorOpaque;
+
+/// The clocks of the current frame, passed to `tick`. The game ticks even while it is paused, so
+/// that Rust can keep track of real time and drive pause menus.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct TickTime {
+    /// Scaled by the global time dilation
+    pub delta: f32,
+    /// Wall clock delta, not affected by time dilation or pause
+    pub real_delta: f32,
+    pub time_dilation: f32,
+    pub paused: u32,
+}
+
+impl Default for TickTime {
+    fn default() -> Self {
+        Self {
+            delta: 0.0,
+            real_delta: 0.0,
+            time_dilation: 1.0,
+            paused: 0,
+        }
+    }
+}
+
+/// The unreal tick groups that run Rust stages after `tick`. `tick` itself runs in
+/// `TG_PrePhysics`, from the tick of the game mode.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TickGroup {
+    /// `TG_DuringPhysics`, runs while the physics simulation is in flight
+    DuringPhysics = 0,
+    /// `TG_PostPhysics`, runs after the physics simulation finished
+    PostPhysics = 1,
+    /// Also `TG_PostPhysics`, but after `PostPhysics` and right before the player camera managers
+    /// are updated
+    LateCamera = 2,
+    /// `TG_PostUpdateWork`, runs after the camera update
+    PostUpdateWork = 3,
+}
+
+pub type SetGlobalTimeDilationFn = unsafe extern "C" fn(dilation: f32);
+/// Returns 0 if the game could not be paused, eg because a pause delegate refused it
+pub type SetGamePausedFn = unsafe extern "C" fn(paused: u32) -> u32;
+/// Sets `AActor::CustomTimeDilation`, which scales the tick of the actor and its components
+pub type SetCustomTimeDilationFn = unsafe extern "C" fn(actor: *mut AActorOpaque, dilation: f32);
+
+extern "C" {
+    pub fn SetGlobalTimeDilation(dilation: f32);
+    pub fn SetGamePaused(paused: u32) -> u32;
+    pub fn SetCustomTimeDilation(actor: *mut AActorOpaque, dilation: f32);
+}
+
+#[repr(C)]
+pub struct TimeFns {
+    pub set_global_time_dilation: SetGlobalTimeDilationFn,
+    pub set_game_paused: SetGamePausedFn,
+    pub set_custom_time_dilation: SetCustomTimeDilationFn,
+}
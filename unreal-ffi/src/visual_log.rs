@@ -0,0 +1,143 @@
+use crate::{AActorOpaque, Color, LogVerbosity, Quaternion, Utf8Str, Vector3};
+
+/// Shared data of every visual log entry
+#[repr(C)]
+pub struct VisualLogEntry {
+    pub category: Utf8Str,
+    pub verbosity: LogVerbosity,
+    pub owner: *const AActorOpaque,
+    pub color: Color,
+    /// Text that is displayed next to the shape, can be empty
+    pub label: Utf8Str,
+}
+
+#[repr(C)]
+pub struct VisualLogStatusEntry {
+    pub key: Utf8Str,
+    pub value: Utf8Str,
+}
+
+pub type VisualLogIsRecordingFn = unsafe extern "C" fn() -> u32;
+
+pub type VisualLogTextFn = unsafe extern "C" fn(entry: *const VisualLogEntry);
+
+pub type VisualLogSegmentFn =
+    unsafe extern "C" fn(entry: *const VisualLogEntry, start: Vector3, end: Vector3);
+
+pub type VisualLogArrowFn =
+    unsafe extern "C" fn(entry: *const VisualLogEntry, start: Vector3, end: Vector3);
+
+pub type VisualLogLocationFn =
+    unsafe extern "C" fn(entry: *const VisualLogEntry, position: Vector3, radius: f32);
+
+pub type VisualLogCapsuleFn = unsafe extern "C" fn(
+    entry: *const VisualLogEntry,
+    position: Vector3,
+    rotation: Quaternion,
+    half_height: f32,
+    radius: f32,
+);
+
+pub type VisualLogBoxFn =
+    unsafe extern "C" fn(entry: *const VisualLogEntry, min: Vector3, max: Vector3);
+
+pub type VisualLogOrientedBoxFn = unsafe extern "C" fn(
+    entry: *const VisualLogEntry,
+    position: Vector3,
+    rotation: Quaternion,
+    half_extent: Vector3,
+);
+
+pub type VisualLogConeFn = unsafe extern "C" fn(
+    entry: *const VisualLogEntry,
+    origin: Vector3,
+    direction: Vector3,
+    length: f32,
+    angle: f32,
+);
+
+pub type VisualLogPolylineFn =
+    unsafe extern "C" fn(entry: *const VisualLogEntry, points: *const Vector3, len: usize);
+
+pub type VisualLogHistogramFn = unsafe extern "C" fn(
+    entry: *const VisualLogEntry,
+    graph_name: Utf8Str,
+    data_name: Utf8Str,
+    x: f32,
+    y: f32,
+);
+
+pub type VisualLogStatusFn = unsafe extern "C" fn(
+    entry: *const VisualLogEntry,
+    status: *const VisualLogStatusEntry,
+    len: usize,
+);
+
+extern "C" {
+    pub fn VisualLogIsRecording() -> u32;
+
+    pub fn VisualLogText(entry: *const VisualLogEntry);
+
+    pub fn VisualLogSegment(entry: *const VisualLogEntry, start: Vector3, end: Vector3);
+
+    pub fn VisualLogArrow(entry: *const VisualLogEntry, start: Vector3, end: Vector3);
+
+    pub fn VisualLogLocation(entry: *const VisualLogEntry, position: Vector3, radius: f32);
+
+    pub fn VisualLogCapsule(
+        entry: *const VisualLogEntry,
+        position: Vector3,
+        rotation: Quaternion,
+        half_height: f32,
+        radius: f32,
+    );
+
+    pub fn VisualLogBox(entry: *const VisualLogEntry, min: Vector3, max: Vector3);
+
+    pub fn VisualLogOrientedBox(
+        entry: *const VisualLogEntry,
+        position: Vector3,
+        rotation: Quaternion,
+        half_extent: Vector3,
+    );
+
+    pub fn VisualLogCone(
+        entry: *const VisualLogEntry,
+        origin: Vector3,
+        direction: Vector3,
+        length: f32,
+        angle: f32,
+    );
+
+    pub fn VisualLogPolyline(entry: *const VisualLogEntry, points: *const Vector3, len: usize);
+
+    pub fn VisualLogHistogram(
+        entry: *const VisualLogEntry,
+        graph_name: Utf8Str,
+        data_name: Utf8Str,
+        x: f32,
+        y: f32,
+    );
+
+    pub fn VisualLogStatus(
+        entry: *const VisualLogEntry,
+        status: *const VisualLogStatusEntry,
+        len: usize,
+    );
+}
+
+#[repr(C)]
+pub struct VisualLogFns {
+    pub is_recording: VisualLogIsRecordingFn,
+    pub text: VisualLogTextFn,
+    pub segment: VisualLogSegmentFn,
+    pub arrow: VisualLogArrowFn,
+    pub location: VisualLogLocationFn,
+    pub capsule: VisualLogCapsuleFn,
+    pub aligned_box: VisualLogBoxFn,
+    pub oriented_box: VisualLogOrientedBoxFn,
+    pub cone: VisualLogConeFn,
+    pub polyline: VisualLogPolylineFn,
+    pub histogram: VisualLogHistogramFn,
+    pub status: VisualLogStatusFn,
+}
@@ -3,6 +3,9 @@ pub use bevy_ecs::{entity::Entity, world::World};
 pub use uuid;
 pub use uuid::Uuid;
 
+#[cfg(feature = "serde")]
+pub use serde_json;
+
 pub mod registry;
 
 pub trait TypeUuid {
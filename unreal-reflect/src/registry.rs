@@ -1,7 +1,13 @@
+use std::marker::PhantomData;
+
 use bevy_ecs::{entity::Entity, prelude::World};
 use glam::{Quat, Vec3};
 use unreal_ffi as ffi;
 
+/// The format of components that are serialized through reflection
+#[cfg(feature = "serde")]
+pub type SerdeValue = serde_json::Value;
+
 #[derive(Copy, Clone, Debug)]
 pub struct UClass {
     pub ptr: *mut ffi::UObjectOpague,
@@ -16,6 +22,119 @@ pub struct USound {
 unsafe impl Send for USound {}
 unsafe impl Sync for USound {}
 
+/// Marker types for the different kinds of assets that an [`AssetRef`] can point to.
+pub trait AssetType: Send + Sync + 'static {
+    const TYPE: ffi::UObjectType;
+    const NAME: &'static str;
+}
+
+macro_rules! asset_types {
+    ($($ty: ident => $uobject_ty: ident,)*) => {
+        $(
+            #[derive(Debug)]
+            pub enum $ty {}
+            impl AssetType for $ty {
+                const TYPE: ffi::UObjectType = ffi::UObjectType::$uobject_ty;
+                const NAME: &'static str = stringify!($ty);
+            }
+        )*
+    };
+}
+
+asset_types! {
+    Class => UClass,
+    Sound => USound,
+    StaticMesh => UStaticMesh,
+    Material => UMaterial,
+    Texture => UTexture,
+    ParticleSystem => UParticleSystem,
+    DataAsset => UDataAsset,
+    AnimationAsset => UAnimationAsset,
+}
+
+/// A typed reference to an asset that was assigned in the editor, eg `AssetRef<StaticMesh>`.
+#[derive(Debug)]
+pub struct AssetRef<T> {
+    pub ptr: *mut ffi::UObjectOpague,
+    _m: PhantomData<T>,
+}
+
+impl<T> AssetRef<T> {
+    pub fn from_raw(ptr: *mut ffi::UObjectOpague) -> Self {
+        Self {
+            ptr,
+            _m: PhantomData,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.ptr.is_null()
+    }
+}
+
+unsafe impl<T> Send for AssetRef<T> {}
+unsafe impl<T> Sync for AssetRef<T> {}
+
+impl<T> Default for AssetRef<T> {
+    fn default() -> Self {
+        Self::from_raw(std::ptr::null_mut())
+    }
+}
+
+impl<T> Clone for AssetRef<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for AssetRef<T> {}
+
+/// A reference to an asset by its object path, eg `/Game/Weapons/Rifle.Rifle`. The asset is not
+/// loaded until it is requested through the asset api.
+#[derive(Debug)]
+pub struct SoftAssetRef<T> {
+    pub path: String,
+    _m: PhantomData<T>,
+}
+
+impl<T> SoftAssetRef<T> {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            _m: PhantomData,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.path.is_empty()
+    }
+}
+
+impl<T> Default for SoftAssetRef<T> {
+    fn default() -> Self {
+        Self::new(String::new())
+    }
+}
+
+impl<T> Clone for SoftAssetRef<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.path.clone())
+    }
+}
+
+impl From<AssetRef<Sound>> for USound {
+    fn from(asset: AssetRef<Sound>) -> Self {
+        USound { ptr: asset.ptr }
+    }
+}
+
+impl From<AssetRef<Class>> for UClass {
+    fn from(asset: AssetRef<Class>) -> Self {
+        UClass { ptr: asset.ptr }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum ReflectValue {
     Float(f32),
     Vector3(Vec3),
@@ -23,9 +142,14 @@ pub enum ReflectValue {
     Quat(Quat),
     UClass(UClass),
     USound(USound),
+    UObject(ffi::UObjectType, *mut ffi::UObjectOpague),
+    SoftObjectPath(ffi::UObjectType, String),
+    /// Tag names separated by `,`
+    GameplayTags(String),
     Composite,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ReflectType {
     Float,
     Vector3,
@@ -33,10 +157,265 @@ pub enum ReflectType {
     Quat,
     UClass,
     USound,
+    UObject(ffi::UObjectType),
+    SoftObjectPath(ffi::UObjectType),
+    GameplayTags,
     Composite,
 }
 
-pub trait ReflectDyn {
+impl From<ReflectType> for ffi::ReflectionType {
+    fn from(ty: ReflectType) -> Self {
+        match ty {
+            ReflectType::Bool => ffi::ReflectionType::Bool,
+            ReflectType::Float => ffi::ReflectionType::Float,
+            ReflectType::Vector3 => ffi::ReflectionType::Vector3,
+            ReflectType::Quat => ffi::ReflectionType::Quaternion,
+            ReflectType::UClass => ffi::ReflectionType::UClass,
+            ReflectType::USound => ffi::ReflectionType::USound,
+            ReflectType::UObject(ty) => ty.into(),
+            ReflectType::SoftObjectPath(_) => ffi::ReflectionType::SoftObjectPath,
+            ReflectType::GameplayTags => ffi::ReflectionType::GameplayTags,
+            ReflectType::Composite => ffi::ReflectionType::Composite,
+        }
+    }
+}
+
+macro_rules! reflect_value_from {
+    ($($ty: ty => $variant: ident,)*) => {
+        $(
+            impl From<$ty> for ReflectValue {
+                fn from(value: $ty) -> Self {
+                    ReflectValue::$variant(value)
+                }
+            }
+        )*
+    };
+}
+
+reflect_value_from! {
+    f32 => Float,
+    Vec3 => Vector3,
+    bool => Bool,
+    Quat => Quat,
+    UClass => UClass,
+    USound => USound,
+}
+
+impl<T: AssetType> From<AssetRef<T>> for ReflectValue {
+    fn from(asset: AssetRef<T>) -> Self {
+        ReflectValue::UObject(T::TYPE, asset.ptr)
+    }
+}
+
+impl ReflectValue {
+    pub fn ty(&self) -> ReflectType {
+        match self {
+            ReflectValue::Float(_) => ReflectType::Float,
+            ReflectValue::Vector3(_) => ReflectType::Vector3,
+            ReflectValue::Bool(_) => ReflectType::Bool,
+            ReflectValue::Quat(_) => ReflectType::Quat,
+            ReflectValue::UClass(_) => ReflectType::UClass,
+            ReflectValue::USound(_) => ReflectType::USound,
+            ReflectValue::UObject(ty, _) => ReflectType::UObject(*ty),
+            ReflectValue::SoftObjectPath(ty, _) => ReflectType::SoftObjectPath(*ty),
+            ReflectValue::GameplayTags(_) => ReflectType::GameplayTags,
+            ReflectValue::Composite => ReflectType::Composite,
+        }
+    }
+
+    /// The returned value borrows strings from `self`.
+    pub fn to_ffi(&self) -> Option<ffi::ReflectValue> {
+        let (ty, data) = match self {
+            ReflectValue::Float(f) => (
+                ffi::ReflectionType::Float,
+                ffi::ReflectValueData { float32: *f },
+            ),
+            ReflectValue::Vector3(v) => (
+                ffi::ReflectionType::Vector3,
+                ffi::ReflectValueData {
+                    vector3: (*v).into(),
+                },
+            ),
+            ReflectValue::Bool(b) => (
+                ffi::ReflectionType::Bool,
+                ffi::ReflectValueData { boolean: *b as u32 },
+            ),
+            ReflectValue::Quat(q) => (
+                ffi::ReflectionType::Quaternion,
+                ffi::ReflectValueData {
+                    quaternion: (*q).into(),
+                },
+            ),
+            ReflectValue::UClass(class) => (
+                ffi::ReflectionType::UClass,
+                ffi::ReflectValueData { uobject: class.ptr },
+            ),
+            ReflectValue::USound(sound) => (
+                ffi::ReflectionType::USound,
+                ffi::ReflectValueData { uobject: sound.ptr },
+            ),
+            ReflectValue::UObject(ty, ptr) => {
+                ((*ty).into(), ffi::ReflectValueData { uobject: *ptr })
+            }
+            ReflectValue::SoftObjectPath(ty, path) => (
+                ffi::ReflectionType::SoftObjectPath,
+                ffi::ReflectValueData {
+                    soft_object_path: ffi::SoftObjectPathValue {
+                        ty: *ty,
+                        path: ffi::Utf8Str::from(path.as_str()),
+                    },
+                },
+            ),
+            ReflectValue::GameplayTags(tags) => (
+                ffi::ReflectionType::GameplayTags,
+                ffi::ReflectValueData {
+                    gameplay_tags: ffi::Utf8Str::from(tags.as_str()),
+                },
+            ),
+            ReflectValue::Composite => return None,
+        };
+        Some(ffi::ReflectValue { ty, data })
+    }
+
+    /// # Safety
+    /// `value.data` must match `value.ty`, and the strings of soft object paths and gameplay tags
+    /// must point to valid utf8.
+    pub unsafe fn from_ffi(value: &ffi::ReflectValue) -> Option<Self> {
+        let data = &value.data;
+        Some(match value.ty {
+            ffi::ReflectionType::Float => ReflectValue::Float(data.float32),
+            ffi::ReflectionType::Vector3 => ReflectValue::Vector3(data.vector3.into()),
+            ffi::ReflectionType::Bool => ReflectValue::Bool(data.boolean != 0),
+            ffi::ReflectionType::Quaternion => ReflectValue::Quat(data.quaternion.into()),
+            ffi::ReflectionType::UClass => ReflectValue::UClass(UClass { ptr: data.uobject }),
+            ffi::ReflectionType::USound => ReflectValue::USound(USound { ptr: data.uobject }),
+            ffi::ReflectionType::UStaticMesh => {
+                ReflectValue::UObject(ffi::UObjectType::UStaticMesh, data.uobject)
+            }
+            ffi::ReflectionType::UMaterial => {
+                ReflectValue::UObject(ffi::UObjectType::UMaterial, data.uobject)
+            }
+            ffi::ReflectionType::UTexture => {
+                ReflectValue::UObject(ffi::UObjectType::UTexture, data.uobject)
+            }
+            ffi::ReflectionType::UParticleSystem => {
+                ReflectValue::UObject(ffi::UObjectType::UParticleSystem, data.uobject)
+            }
+            ffi::ReflectionType::UDataAsset => {
+                ReflectValue::UObject(ffi::UObjectType::UDataAsset, data.uobject)
+            }
+            ffi::ReflectionType::UAnimationAsset => {
+                ReflectValue::UObject(ffi::UObjectType::UAnimationAsset, data.uobject)
+            }
+            ffi::ReflectionType::SoftObjectPath => {
+                let soft = data.soft_object_path;
+                ReflectValue::SoftObjectPath(soft.ty, utf8_str_to_string(soft.path)?)
+            }
+            ffi::ReflectionType::GameplayTags => {
+                ReflectValue::GameplayTags(utf8_str_to_string(data.gameplay_tags)?)
+            }
+            ffi::ReflectionType::Composite => return None,
+        })
+    }
+}
+
+unsafe fn utf8_str_to_string(s: ffi::Utf8Str) -> Option<String> {
+    if s.ptr.is_null() {
+        return Some(String::new());
+    }
+    let bytes = std::slice::from_raw_parts(s.ptr as *const u8, s.len);
+    std::str::from_utf8(bytes).ok().map(str::to_string)
+}
+
+/// Converts a [`ReflectValue`] back into a typed value. This is the inverse of
+/// [`ReflectDyn::get_value`].
+pub trait FromReflectValue: Sized {
+    fn from_value(value: &ReflectValue) -> Option<Self>;
+}
+
+impl FromReflectValue for f32 {
+    fn from_value(value: &ReflectValue) -> Option<Self> {
+        match value {
+            ReflectValue::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+}
+
+impl FromReflectValue for bool {
+    fn from_value(value: &ReflectValue) -> Option<Self> {
+        match value {
+            ReflectValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+impl FromReflectValue for Vec3 {
+    fn from_value(value: &ReflectValue) -> Option<Self> {
+        match value {
+            ReflectValue::Vector3(v) => Some(*v),
+            _ => None,
+        }
+    }
+}
+
+impl FromReflectValue for Quat {
+    fn from_value(value: &ReflectValue) -> Option<Self> {
+        match value {
+            ReflectValue::Quat(q) => Some(*q),
+            _ => None,
+        }
+    }
+}
+
+impl FromReflectValue for UClass {
+    fn from_value(value: &ReflectValue) -> Option<Self> {
+        match value {
+            ReflectValue::UClass(class) => Some(*class),
+            ReflectValue::UObject(ffi::UObjectType::UClass, ptr) => Some(UClass { ptr: *ptr }),
+            _ => None,
+        }
+    }
+}
+
+impl FromReflectValue for USound {
+    fn from_value(value: &ReflectValue) -> Option<Self> {
+        match value {
+            ReflectValue::USound(sound) => Some(*sound),
+            ReflectValue::UObject(ffi::UObjectType::USound, ptr) => Some(USound { ptr: *ptr }),
+            _ => None,
+        }
+    }
+}
+
+impl<T: AssetType> FromReflectValue for AssetRef<T> {
+    fn from_value(value: &ReflectValue) -> Option<Self> {
+        match value {
+            ReflectValue::UObject(ty, ptr) if *ty == T::TYPE => Some(AssetRef::from_raw(*ptr)),
+            ReflectValue::UClass(class) if T::TYPE == ffi::UObjectType::UClass => {
+                Some(AssetRef::from_raw(class.ptr))
+            }
+            ReflectValue::USound(sound) if T::TYPE == ffi::UObjectType::USound => {
+                Some(AssetRef::from_raw(sound.ptr))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<T: AssetType> FromReflectValue for SoftAssetRef<T> {
+    // The asset type is not checked, unreal only knows the path of a soft reference until the
+    // asset is loaded.
+    fn from_value(value: &ReflectValue) -> Option<Self> {
+        match value {
+            ReflectValue::SoftObjectPath(_, path) => Some(SoftAssetRef::new(path.clone())),
+            _ => None,
+        }
+    }
+}
+
+pub trait ReflectDyn: Send + Sync {
     fn name(&self) -> &'static str;
     fn number_of_fields(&self) -> u32 {
         0
@@ -53,6 +432,49 @@ pub trait ReflectDyn {
     fn get_field_value(&self, _world: &World, _entity: Entity, _idx: u32) -> Option<ReflectValue> {
         None
     }
+    /// Returns false if the entity has no such component, or the value has the wrong type
+    fn set_field_value(
+        &self,
+        _world: &mut World,
+        _entity: Entity,
+        _idx: u32,
+        _value: &ReflectValue,
+    ) -> bool {
+        false
+    }
+    /// Returns false if the entity didn't have the component
+    fn remove_component(&self, _world: &mut World, _entity: Entity) -> bool {
+        false
+    }
+    /// Builds the component from `values`, which are indexed like the fields, and inserts it.
+    /// Editor components need a value for every field. Components with `#[reflect(default)]`
+    /// start from their default value, and fields without a value keep it. Returns false for
+    /// every other component.
+    fn insert_component(
+        &self,
+        _world: &mut World,
+        _entity: Entity,
+        _values: &[Option<ReflectValue>],
+    ) -> bool {
+        false
+    }
+    /// Serializes the whole component, including skipped fields. Only components with
+    /// `#[reflect(serde)]` implement this.
+    #[cfg(feature = "serde")]
+    fn serialize_component(&self, _world: &World, _entity: Entity) -> Option<SerdeValue> {
+        None
+    }
+    /// Inserts the component, replacing the one that the entity already has. Returns false if
+    /// the value doesn't match the component.
+    #[cfg(feature = "serde")]
+    fn deserialize_component(
+        &self,
+        _world: &mut World,
+        _entity: Entity,
+        _value: SerdeValue,
+    ) -> bool {
+        false
+    }
     fn get_value(&self) -> ReflectValue;
 }
 
@@ -82,6 +504,34 @@ impl ReflectStatic for USound {
     const TYPE: ReflectType = ReflectType::USound;
 }
 
+impl<T: AssetType> ReflectDyn for AssetRef<T> {
+    fn name(&self) -> &'static str {
+        T::NAME
+    }
+
+    fn get_value(&self) -> ReflectValue {
+        ReflectValue::UObject(T::TYPE, self.ptr)
+    }
+}
+
+impl<T: AssetType> ReflectStatic for AssetRef<T> {
+    const TYPE: ReflectType = ReflectType::UObject(T::TYPE);
+}
+
+impl<T: AssetType> ReflectDyn for SoftAssetRef<T> {
+    fn name(&self) -> &'static str {
+        T::NAME
+    }
+
+    fn get_value(&self) -> ReflectValue {
+        ReflectValue::SoftObjectPath(T::TYPE, self.path.clone())
+    }
+}
+
+impl<T: AssetType> ReflectStatic for SoftAssetRef<T> {
+    const TYPE: ReflectType = ReflectType::SoftObjectPath(T::TYPE);
+}
+
 impl ReflectDyn for Vec3 {
     fn name(&self) -> &'static str {
         "Vec3"
@@ -1,19 +1,23 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use bevy_ecs::prelude::*;
-use unreal_api::api::UnrealApi;
+use serde::{Deserialize, Serialize};
+use unreal_api::collision::{HitEvents, OverlapEvents};
 use unreal_api::core::{ActorHitEvent, Despawn};
+use unreal_api::event::BlueprintEvents;
 use unreal_api::registry::USound;
 use unreal_api::sound::{play_sound_at_location, SoundSettings};
-use unreal_api::Component;
 use unreal_api::{
     core::{ActorComponent, ActorPtr, CoreStage, ParentComponent, TransformComponent},
     ffi::{self, UClassOpague},
+    fixed_update::{FixedTime, InterpolateTransform},
     input::Input,
     math::{Quat, Vec3},
-    module::{bindings, InitUserModule, Module, UserModule},
+    module::{bindings, GameThread, InitUserModule, Module, UserModule},
     register_components,
+    time::run_if_not_paused,
 };
+use unreal_api::{unreal_function, Component, UnrealEvent};
 use unreal_movement::{
     CharacterConfigComponent, CharacterControllerComponent, MovementComponent, MovementPlugin,
 };
@@ -40,7 +44,7 @@ pub struct ClassesResource {
 unsafe impl Send for ClassesResource {}
 unsafe impl Sync for ClassesResource {}
 
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, Copy, Clone, Serialize, Deserialize)]
 pub enum CameraMode {
     #[default]
     ThirdPerson,
@@ -65,6 +69,14 @@ impl PlaySoundOnImpactComponent {
     const MINIMUM_FORCE: f32 = 30000.0;
 }
 
+/// Sent to blueprints when an object with a `PlaySoundOnImpactComponent` breaks
+#[derive(Debug, UnrealEvent)]
+#[uuid = "0c5d1a2e-8f6b-4c3d-9e7a-1b2c3d4e5f60"]
+pub struct ImpactEvent {
+    pub position: Vec3,
+    pub force: f32,
+}
+
 #[derive(Debug, Component)]
 #[uuid = "52788d7e-017b-42cd-b3bf-aa616315c0c4"]
 #[reflect(editor)]
@@ -72,8 +84,9 @@ pub struct CharacterSoundsComponent {
     pub camera_toggle: USound,
 }
 
-#[derive(Default, Debug, Component)]
+#[derive(Default, Debug, Component, Serialize, Deserialize)]
 #[uuid = "8d2df877-499b-46f3-9660-bd2e1867af0d"]
+#[reflect(default, serde)]
 pub struct CameraComponent {
     pub x: f32,
     pub y: f32,
@@ -94,7 +107,7 @@ impl PlayerInput {
 }
 
 // TODO: We probably don't need that anymore
-fn register_class_resource(mut commands: Commands) {
+fn register_class_resource(_game_thread: NonSend<GameThread>, mut commands: Commands) {
     let mut len: usize = 0;
     unsafe {
         (bindings().actor_fns.get_registered_classes)(std::ptr::null_mut(), &mut len);
@@ -115,51 +128,68 @@ fn register_class_resource(mut commands: Commands) {
     commands.insert_resource(classes_resource);
 }
 
-fn register_hit_events(mut query: Query<(&mut ActorComponent, Added<PlaySoundOnImpactComponent>)>) {
-    for (mut actor, added) in &mut query {
-        if added {
-            actor.register_on_hit();
-        }
+fn register_hit_events(
+    query: Query<Entity, Added<PlaySoundOnImpactComponent>>,
+    mut commands: Commands,
+) {
+    for entity in &query {
+        commands.entity(entity).insert(HitEvents);
     }
 }
 
 fn play_sound_on_hit(
-    api: Res<UnrealApi>,
+    _game_thread: NonSend<GameThread>,
     mut events: EventReader<ActorHitEvent>,
     query: Query<(&TransformComponent, &PlaySoundOnImpactComponent)>,
+    mut blueprint_events: ResMut<BlueprintEvents>,
     mut commands: Commands,
 ) {
+    // Hit events are sent per pair of primitives, one impact can send several of them
+    let mut impacted = HashSet::new();
     for event in events.iter() {
         if event.normal_impulse.length() <= PlaySoundOnImpactComponent::MINIMUM_FORCE {
             continue;
         }
 
-        if let Some(&entity) = api.actor_to_entity.get(&event.self_actor) {
+        if let Some(entity) = event.self_entity.filter(|entity| impacted.insert(*entity)) {
             if let Ok((trans, sound)) = query.get(entity) {
                 play_sound_at_location(
                     sound.sound,
-                    trans.position,
+                    event.impact_location,
                     trans.rotation,
                     &SoundSettings::default(),
-                )
+                );
+                blueprint_events.send(&ImpactEvent {
+                    position: event.impact_location,
+                    force: event.normal_impulse.length(),
+                });
             }
             commands.add(Despawn { entity });
         }
     }
 }
 
+/// Lets blueprints launch a character, eg from a jump pad
+#[unreal_function]
+pub fn launch_character(world: &mut World, entity: Entity, velocity: Vec3) -> bool {
+    if let Some(mut movement) = world.get_mut::<MovementComponent>(entity) {
+        movement.velocity = velocity;
+        true
+    } else {
+        false
+    }
+}
+
 fn spawn_class(
+    _game_thread: NonSend<GameThread>,
     class_resource: Res<ClassesResource>,
     query: Query<(Entity, &ActorComponent), Added<ActorComponent>>,
     mut commands: Commands,
 ) {
     for (entity, actor) in query.iter() {
-        unsafe {
-            (bindings().actor_fns.register_actor_on_overlap)(actor.actor.0);
-        }
-        unsafe {
-            (bindings().actor_fns.register_actor_on_hit)(actor.actor.0);
-        }
+        commands
+            .entity(entity)
+            .insert_bundle((OverlapEvents, HitEvents));
         unsafe {
             let class_ptr = (bindings().actor_fns.get_class)(actor.actor.0);
             if let Some(&class) = class_resource.classes.get(&class_ptr) {
@@ -169,6 +199,8 @@ fn spawn_class(
                             CharacterConfigComponent::default(),
                             CharacterControllerComponent::default(),
                             MovementComponent::default(),
+                            // The character moves in fixed steps
+                            InterpolateTransform::default(),
                         ));
                     }
                 }
@@ -187,6 +219,7 @@ fn register_player_input(mut input: ResMut<Input>) {
 }
 
 fn toggle_camera(
+    _game_thread: NonSend<GameThread>,
     input: Res<Input>,
     mut camera_query: Query<(Entity, &mut CameraComponent, &ParentComponent)>,
     mut actor_query: Query<&mut ActorComponent>,
@@ -227,7 +260,10 @@ fn update_controller_view(
         }
     }
 }
-fn rotate_camera(mut query: Query<(&mut TransformComponent, &mut CameraComponent)>) {
+fn rotate_camera(
+    _game_thread: NonSend<GameThread>,
+    mut query: Query<(&mut TransformComponent, &mut CameraComponent)>,
+) {
     fn lerp(start: f32, end: f32, t: f32) -> f32 {
         start * (1.0 - t) + end * t
     }
@@ -254,6 +290,7 @@ fn rotate_camera(mut query: Query<(&mut TransformComponent, &mut CameraComponent
 }
 
 fn spawn_camera(
+    _game_thread: NonSend<GameThread>,
     mut commands: Commands,
     mut query: Query<(Entity, &ActorComponent, Added<CharacterControllerComponent>)>,
 ) {
@@ -286,14 +323,20 @@ fn spawn_camera(
 }
 
 fn update_camera(
+    fixed: Res<FixedTime>,
     mut query: Query<(Entity, &ParentComponent, &CameraComponent)>,
     mut spatial_query: Query<&mut TransformComponent>,
+    interpolate: Query<&InterpolateTransform>,
 ) {
     for (entity, parent, camera) in query.iter_mut() {
+        // Follow the transform that unreal shows, not the last fixed step
         let spatial_parent = spatial_query
             .get_component::<TransformComponent>(parent.parent)
             .ok()
-            .cloned();
+            .map(|transform| match interpolate.get(parent.parent) {
+                Ok(interpolate) => interpolate.interpolate(transform, fixed.alpha()),
+                Err(_) => transform.clone(),
+            });
         let spatial = spatial_query
             .get_component_mut::<TransformComponent>(entity)
             .ok();
@@ -328,6 +371,8 @@ impl UserModule for MyModule {
 
         module
             .add_plugin(MovementPlugin)
+            .add_unreal_event::<ImpactEvent>()
+            .add_unreal_function::<launch_character>()
             .add_startup_system_set(
                 SystemSet::new()
                     .with_system(register_class_resource)
@@ -337,13 +382,20 @@ impl UserModule for MyModule {
             .add_system_set_to_stage(
                 CoreStage::Update,
                 SystemSet::new()
+                    .with_run_criteria(run_if_not_paused)
                     .with_system(spawn_class)
                     .with_system(spawn_camera)
                     .with_system(update_controller_view)
-                    .with_system(rotate_camera)
-                    .with_system(update_camera.after(rotate_camera))
                     .with_system(toggle_camera)
                     .with_system(play_sound_on_hit),
+            )
+            .add_system_set_to_stage(
+                // Runs after unreal has moved the character for this frame
+                CoreStage::LateCamera,
+                SystemSet::new()
+                    .with_run_criteria(run_if_not_paused)
+                    .with_system(rotate_camera)
+                    .with_system(update_camera.after(rotate_camera)),
             );
     }
 }